@@ -3,15 +3,10 @@ use std::fs;
 use criterion::*;
 use criterion::measurement::WallTime;
 
-use rsmod::rsmod::{CollisionStrategies, Normal, PathFinder};
+use rsmod::rsmod::{has_line_of_sight, visibility_map, CollisionStrategies, Normal, PathFinder};
 use rsmod::rsmod::collision::collision::CollisionFlagMap;
 
-fn bench_pathfinder(c: &mut Criterion) {
-    let mut group: BenchmarkGroup<WallTime> = c.benchmark_group("pathfinder");
-
-    // Define the throughput in operations (you can use 1 if it's per operation)
-    group.throughput(Throughput::Elements(1)); // Measure as ops/second
-
+fn lumbridge() -> CollisionFlagMap {
     let mut collision = CollisionFlagMap::new();
 
     let lumbridge: Vec<Vec<i32>> =
@@ -31,6 +26,16 @@ fn bench_pathfinder(c: &mut Criterion) {
             }
         }
     }
+    return collision;
+}
+
+fn bench_pathfinder(c: &mut Criterion) {
+    let mut group: BenchmarkGroup<WallTime> = c.benchmark_group("pathfinder");
+
+    // Define the throughput in operations (you can use 1 if it's per operation)
+    group.throughput(Throughput::Elements(1)); // Measure as ops/second
+
+    let collision: CollisionFlagMap = lumbridge();
 
     let pathfinder: PathFinder = PathFinder::new();
 
@@ -54,7 +59,7 @@ fn bench_pathfinder(c: &mut Criterion) {
                     0,
                     25,
                     &CollisionStrategies::Normal(Normal),
-                );
+                ).unwrap();
             },
             BatchSize::SmallInput,
         )
@@ -63,6 +68,34 @@ fn bench_pathfinder(c: &mut Criterion) {
     group.finish();
 }
 
-criterion_group!(benches, bench_pathfinder);
+fn bench_visibility(c: &mut Criterion) {
+    let mut group: BenchmarkGroup<WallTime> = c.benchmark_group("visibility");
+    group.throughput(Throughput::Elements(1));
+
+    let collision: CollisionFlagMap = lumbridge();
+
+    group.bench_function("visibility_map", |b| {
+        b.iter(|| unsafe { visibility_map(&collision, 0, 3232, 3220, 1, 15, 0) })
+    });
+
+    // the same area, one line of sight per tile.
+    group.bench_function("has_line_of_sight", |b| {
+        b.iter(|| unsafe {
+            let mut visible: usize = 0;
+            for z in 3220 - 15..=3220 + 15 {
+                for x in 3232 - 15..=3232 + 15 {
+                    if has_line_of_sight(&collision, 0, 3232, 3220, x, z, 1, 1, 1, 1, 0) {
+                        visible += 1;
+                    }
+                }
+            }
+            visible
+        })
+    });
+
+    group.finish();
+}
+
+criterion_group!(benches, bench_pathfinder, bench_visibility);
 
 criterion_main!(benches);
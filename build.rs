@@ -0,0 +1,59 @@
+fn main() {
+    #[cfg(feature = "capi")]
+    generate_header();
+}
+
+/**
+ * Generates `rsmod.h` into `OUT_DIR` from the `extern "C"` functions in `src/capi.rs`.
+ * The tracked `include/rsmod.h` is only rewritten when `RSMOD_WRITE_HEADER` is set,
+ * the `capi` tests fail while it is out of date.
+ */
+#[cfg(feature = "capi")]
+fn generate_header() {
+    let crate_dir: String = std::env::var("CARGO_MANIFEST_DIR").unwrap();
+    let out_dir: String = std::env::var("OUT_DIR").unwrap();
+    println!("cargo:rerun-if-changed=src/capi.rs");
+    println!("cargo:rerun-if-changed=cbindgen.toml");
+    println!("cargo:rerun-if-env-changed=RSMOD_WRITE_HEADER");
+    let source: String = std::fs::read_to_string(format!("{}/src/capi.rs", crate_dir)).unwrap();
+    std::fs::write(format!("{}/capi.rs", out_dir), line_docs(&source)).unwrap();
+    let bindings = cbindgen::Builder::new()
+        .with_src(format!("{}/capi.rs", out_dir))
+        .with_config(cbindgen::Config::from_file(format!("{}/cbindgen.toml", crate_dir)).unwrap())
+        .generate()
+        .expect("Unable to generate rsmod.h");
+    bindings.write_to_file(format!("{}/rsmod.h", out_dir));
+    if std::env::var_os("RSMOD_WRITE_HEADER").is_some() {
+        bindings.write_to_file(format!("{}/include/rsmod.h", crate_dir));
+    }
+}
+
+/**
+ * Rewrites the `/** */` doc blocks in `source` as `///` lines. cbindgen joins the lines of a
+ * block comment into one and keeps their leading `*`, line comments come out as written.
+ */
+#[cfg(feature = "capi")]
+fn line_docs(source: &str) -> String {
+    let mut out: String = String::with_capacity(source.len());
+    let mut indent: Option<String> = None;
+    for line in source.lines() {
+        let trimmed: &str = line.trim_start();
+        let prefix: &str = &line[..line.len() - trimmed.len()];
+        match &indent {
+            None if trimmed == "/**" => indent = Some(prefix.to_string()),
+            None => {
+                out.push_str(line);
+                out.push('\n');
+            }
+            Some(_) if trimmed == "*/" => indent = None,
+            Some(indent) => {
+                let text: &str = trimmed.strip_prefix('*').unwrap_or(trimmed);
+                out.push_str(indent);
+                out.push_str("///");
+                out.push_str(text.trim_end());
+                out.push('\n');
+            }
+        }
+    }
+    return out;
+}
@@ -0,0 +1,481 @@
+/* Generated by cbindgen from src/capi.rs, do not edit. */
+
+#ifndef RSMOD_PATHFINDER_H
+#define RSMOD_PATHFINDER_H
+
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+
+typedef struct CollisionFlagMap CollisionFlagMap;
+
+typedef struct PathFinder PathFinder;
+
+/**
+ * Status codes returned by every `rsmod_*` function that can fail.
+ */
+#define RSMOD_OK 0
+
+#define RSMOD_ERROR_NULL_POINTER 1
+
+#define RSMOD_ERROR_INVALID_ARGUMENT 2
+
+#define RSMOD_ERROR_BUFFER_TOO_SMALL 3
+
+#define RSMOD_ERROR_OUT_OF_RANGE 4
+
+#define RSMOD_ERROR_PANIC 5
+
+/**
+ * The tile that blocked a line, see [rsmod_line_of_sight_result].
+ * `blocker` is -1 if the line is clear, with the rest left 0, otherwise 0 to 3 for a wall on the west,
+ * east, south or north side of the tile, 4 for a loc, 5 for a loc blocking projectiles, 6 for the floor
+ * and 7 for the extra flag. `flags` holds the flags of the tile that the line checked for.
+ */
+typedef struct {
+  int32_t blocker;
+  int32_t x;
+  int32_t z;
+  int32_t y;
+  uint32_t flags;
+} RsmodRayBlock;
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+/**
+ * Creates a dense map over x and z in 0..16384. Free it with [rsmod_map_free].
+ */
+CollisionFlagMap *rsmod_map_new(void);
+
+/**
+ * Creates a map over x and z in -0x40000000..=0x3fffffff that only allocates the mapsquares written to.
+ */
+CollisionFlagMap *rsmod_map_new_sparse(void);
+
+void rsmod_map_free(CollisionFlagMap *map);
+
+/**
+ * Creates a pathfinder. A pathfinder keeps its search buffers between calls,
+ * so every thread searching at the same time needs its own.
+ */
+PathFinder *rsmod_pathfinder_new(void);
+
+void rsmod_pathfinder_free(PathFinder *pathfinder);
+
+/**
+ * Writes the flags of the tile to `out`, `0x7FFFFFFF` if its zone is not allocated.
+ */
+int32_t rsmod_map_get(const CollisionFlagMap *map,
+                      int32_t x,
+                      int32_t z,
+                      int32_t y,
+                      uint32_t *out);
+
+int32_t rsmod_map_set(CollisionFlagMap *map,
+                      int32_t x,
+                      int32_t z,
+                      int32_t y,
+                      uint32_t mask);
+
+int32_t rsmod_map_add(CollisionFlagMap *map,
+                      int32_t x,
+                      int32_t z,
+                      int32_t y,
+                      uint32_t mask);
+
+int32_t rsmod_map_remove(CollisionFlagMap *map,
+                         int32_t x,
+                         int32_t z,
+                         int32_t y,
+                         uint32_t mask);
+
+int32_t rsmod_map_allocate_if_absent(CollisionFlagMap *map,
+                                     int32_t x,
+                                     int32_t z,
+                                     int32_t y);
+
+int32_t rsmod_map_deallocate_if_present(CollisionFlagMap *map,
+                                        int32_t x,
+                                        int32_t z,
+                                        int32_t y);
+
+int32_t rsmod_map_is_zone_allocated(const CollisionFlagMap *map,
+                                    int32_t x,
+                                    int32_t z,
+                                    int32_t y,
+                                    bool *out);
+
+/**
+ * Replaces the zone with the 64 masks at `flags`, indexed by `(x & 7) | ((z & 7) << 3)`.
+ */
+int32_t rsmod_map_set_zone(CollisionFlagMap *map,
+                           int32_t x,
+                           int32_t z,
+                           int32_t y,
+                           const uint32_t *flags);
+
+/**
+ * Replaces all 4 levels of a mapsquare from `len` masks, see `CollisionFlagMap::load_mapsquare`.
+ */
+int32_t rsmod_map_load_mapsquare(CollisionFlagMap *map,
+                                 int32_t mapsquare_x,
+                                 int32_t mapsquare_z,
+                                 const uint32_t *flags,
+                                 uintptr_t len);
+
+int32_t rsmod_map_change_floor(CollisionFlagMap *map,
+                               int32_t x,
+                               int32_t z,
+                               int32_t y,
+                               bool add);
+
+int32_t rsmod_map_change_roof(CollisionFlagMap *map,
+                              int32_t x,
+                              int32_t z,
+                              int32_t y,
+                              bool add);
+
+int32_t rsmod_map_change_npc(CollisionFlagMap *map,
+                             int32_t x,
+                             int32_t z,
+                             int32_t y,
+                             int32_t size,
+                             bool add);
+
+int32_t rsmod_map_change_player(CollisionFlagMap *map,
+                                int32_t x,
+                                int32_t z,
+                                int32_t y,
+                                int32_t size,
+                                bool add);
+
+/**
+ * Flags the (already rotated) `width` x `length` footprint of a ground loc.
+ */
+int32_t rsmod_map_change_loc(CollisionFlagMap *map,
+                             int32_t x,
+                             int32_t z,
+                             int32_t y,
+                             int32_t width,
+                             int32_t length,
+                             bool blockrange,
+                             bool breakroutefinding,
+                             bool add);
+
+/**
+ * Flags a loc of any shape, rotating its footprint and walls by `angle`.
+ */
+int32_t rsmod_map_change_loc_shape(CollisionFlagMap *map,
+                                   int32_t x,
+                                   int32_t z,
+                                   int32_t y,
+                                   uint8_t width,
+                                   uint8_t length,
+                                   uint8_t angle,
+                                   int8_t shape,
+                                   bool blockrange,
+                                   bool breakroutefinding,
+                                   bool add);
+
+/**
+ * Flags a wall of shape 0-3. Returns [RSMOD_ERROR_INVALID_ARGUMENT] for any other shape, diagonal
+ * walls (9) included as they are ground layer locs, see [rsmod_map_change_loc_shape] for those.
+ */
+int32_t rsmod_map_change_wall(CollisionFlagMap *map,
+                              int32_t x,
+                              int32_t z,
+                              int32_t y,
+                              uint8_t angle,
+                              int8_t shape,
+                              bool blockrange,
+                              bool breakroutefinding,
+                              bool add);
+
+int32_t rsmod_map_change_wall_straight(CollisionFlagMap *map,
+                                       int32_t x,
+                                       int32_t z,
+                                       int32_t y,
+                                       uint8_t angle,
+                                       bool blockrange,
+                                       bool breakroutefinding,
+                                       bool add);
+
+int32_t rsmod_map_change_wall_corner(CollisionFlagMap *map,
+                                     int32_t x,
+                                     int32_t z,
+                                     int32_t y,
+                                     uint8_t angle,
+                                     bool blockrange,
+                                     bool breakroutefinding,
+                                     bool add);
+
+int32_t rsmod_map_change_wall_l(CollisionFlagMap *map,
+                                int32_t x,
+                                int32_t z,
+                                int32_t y,
+                                uint8_t angle,
+                                bool blockrange,
+                                bool breakroutefinding,
+                                bool add);
+
+/**
+ * Finds a path with the breadth-first search, writing the packed waypoints to `out`.
+ * `collision` is one of `CollisionType`. An empty result means there is no path.
+ * Returns [RSMOD_ERROR_INVALID_ARGUMENT] unless `angle` is in 0..=3 and `shape` is a loc shape, -1 or -2.
+ * Returns [RSMOD_ERROR_OUT_OF_RANGE] if a waypoint can't be packed, e.g. far out in a sparse map, see [rsmod_find_path_wide].
+ */
+int32_t rsmod_find_path(PathFinder *pathfinder,
+                        const CollisionFlagMap *map,
+                        int32_t y,
+                        int32_t src_x,
+                        int32_t src_z,
+                        int32_t dest_x,
+                        int32_t dest_z,
+                        uint8_t src_size,
+                        uint8_t dest_width,
+                        uint8_t dest_height,
+                        uint8_t angle,
+                        int8_t shape,
+                        bool move_near,
+                        uint8_t block_access_flags,
+                        uint8_t max_waypoints,
+                        uint8_t collision,
+                        uint32_t *out,
+                        uintptr_t capacity,
+                        uintptr_t *len);
+
+/**
+ * Like [rsmod_find_path], but packs the waypoints into 64 bit wide coordinates: z in the low 31 bits,
+ * x in the next 31, both signed, and y in the top 2. Use it for paths past 16383 in a sparse map.
+ */
+int32_t rsmod_find_path_wide(PathFinder *pathfinder,
+                             const CollisionFlagMap *map,
+                             int32_t y,
+                             int32_t src_x,
+                             int32_t src_z,
+                             int32_t dest_x,
+                             int32_t dest_z,
+                             uint8_t src_size,
+                             uint8_t dest_width,
+                             uint8_t dest_height,
+                             uint8_t angle,
+                             int8_t shape,
+                             bool move_near,
+                             uint8_t block_access_flags,
+                             uint8_t max_waypoints,
+                             uint8_t collision,
+                             uint64_t *out,
+                             uintptr_t capacity,
+                             uintptr_t *len);
+
+int32_t rsmod_find_naive_path(const CollisionFlagMap *map,
+                              int32_t y,
+                              int32_t src_x,
+                              int32_t src_z,
+                              int32_t dest_x,
+                              int32_t dest_z,
+                              uint8_t src_width,
+                              uint8_t src_height,
+                              uint8_t dest_width,
+                              uint8_t dest_height,
+                              uint32_t extra_flag,
+                              uint8_t collision,
+                              uint32_t *out,
+                              uintptr_t capacity,
+                              uintptr_t *len);
+
+/**
+ * Like `rsmod_find_naive_path`, but stepping out from under the target is driven by `seed`
+ * so the same seed always gives the same step.
+ */
+int32_t rsmod_find_naive_path_seeded(const CollisionFlagMap *map,
+                                     int32_t y,
+                                     int32_t src_x,
+                                     int32_t src_z,
+                                     int32_t dest_x,
+                                     int32_t dest_z,
+                                     uint8_t src_width,
+                                     uint8_t src_height,
+                                     uint8_t dest_width,
+                                     uint8_t dest_height,
+                                     uint32_t extra_flag,
+                                     uint8_t collision,
+                                     uint64_t seed,
+                                     uint32_t *out,
+                                     uintptr_t capacity,
+                                     uintptr_t *len);
+
+int32_t rsmod_can_travel(const CollisionFlagMap *map,
+                         int32_t y,
+                         int32_t x,
+                         int32_t z,
+                         int8_t offset_x,
+                         int8_t offset_z,
+                         uint8_t size,
+                         uint32_t extra_flag,
+                         uint8_t collision,
+                         bool *out);
+
+int32_t rsmod_has_line_of_sight(const CollisionFlagMap *map,
+                                int32_t y,
+                                int32_t src_x,
+                                int32_t src_z,
+                                int32_t dest_x,
+                                int32_t dest_z,
+                                uint8_t src_width,
+                                uint8_t src_height,
+                                uint8_t dest_width,
+                                uint8_t dest_height,
+                                uint32_t extra_flag,
+                                bool *out);
+
+int32_t rsmod_has_line_of_walk(const CollisionFlagMap *map,
+                               int32_t y,
+                               int32_t src_x,
+                               int32_t src_z,
+                               int32_t dest_x,
+                               int32_t dest_z,
+                               uint8_t src_width,
+                               uint8_t src_height,
+                               uint8_t dest_width,
+                               uint8_t dest_height,
+                               uint32_t extra_flag,
+                               bool *out);
+
+/**
+ * Writes the packed tiles of the line of sight to `out`, none if it is blocked.
+ * Returns [RSMOD_ERROR_OUT_OF_RANGE] if a tile can't be packed.
+ */
+int32_t rsmod_line_of_sight(const CollisionFlagMap *map,
+                            int32_t y,
+                            int32_t src_x,
+                            int32_t src_z,
+                            int32_t dest_x,
+                            int32_t dest_z,
+                            uint8_t src_width,
+                            uint8_t src_height,
+                            uint8_t dest_width,
+                            uint8_t dest_height,
+                            uint32_t extra_flag,
+                            uint32_t *out,
+                            uintptr_t capacity,
+                            uintptr_t *len);
+
+/**
+ * Writes the packed tiles of the line of walk to `out`, none if it is blocked.
+ * Returns [RSMOD_ERROR_OUT_OF_RANGE] if a tile can't be packed.
+ */
+int32_t rsmod_line_of_walk(const CollisionFlagMap *map,
+                           int32_t y,
+                           int32_t src_x,
+                           int32_t src_z,
+                           int32_t dest_x,
+                           int32_t dest_z,
+                           uint8_t src_width,
+                           uint8_t src_height,
+                           uint8_t dest_width,
+                           uint8_t dest_height,
+                           uint32_t extra_flag,
+                           uint32_t *out,
+                           uintptr_t capacity,
+                           uintptr_t *len);
+
+/**
+ * Like [rsmod_line_of_sight], but writes the tiles up to an obstruction to `out` and what blocked the line to `block`.
+ * Returns [RSMOD_ERROR_OUT_OF_RANGE] if a tile can't be packed.
+ */
+int32_t rsmod_line_of_sight_result(const CollisionFlagMap *map,
+                                   int32_t y,
+                                   int32_t src_x,
+                                   int32_t src_z,
+                                   int32_t dest_x,
+                                   int32_t dest_z,
+                                   uint8_t src_width,
+                                   uint8_t src_height,
+                                   uint8_t dest_width,
+                                   uint8_t dest_height,
+                                   uint32_t extra_flag,
+                                   uint32_t *out,
+                                   uintptr_t capacity,
+                                   uintptr_t *len,
+                                   RsmodRayBlock *block);
+
+/**
+ * Like [rsmod_line_of_sight_result], but packs the tiles into 64 bit wide coordinates like [rsmod_find_path_wide].
+ */
+int32_t rsmod_line_of_sight_wide(const CollisionFlagMap *map,
+                                 int32_t y,
+                                 int32_t src_x,
+                                 int32_t src_z,
+                                 int32_t dest_x,
+                                 int32_t dest_z,
+                                 uint8_t src_width,
+                                 uint8_t src_height,
+                                 uint8_t dest_width,
+                                 uint8_t dest_height,
+                                 uint32_t extra_flag,
+                                 uint64_t *out,
+                                 uintptr_t capacity,
+                                 uintptr_t *len,
+                                 RsmodRayBlock *block);
+
+/**
+ * Like [rsmod_line_of_walk], but writes the tiles up to an obstruction to `out` and what blocked the line to `block`.
+ * Returns [RSMOD_ERROR_OUT_OF_RANGE] if a tile can't be packed.
+ */
+int32_t rsmod_line_of_walk_result(const CollisionFlagMap *map,
+                                  int32_t y,
+                                  int32_t src_x,
+                                  int32_t src_z,
+                                  int32_t dest_x,
+                                  int32_t dest_z,
+                                  uint8_t src_width,
+                                  uint8_t src_height,
+                                  uint8_t dest_width,
+                                  uint8_t dest_height,
+                                  uint32_t extra_flag,
+                                  uint32_t *out,
+                                  uintptr_t capacity,
+                                  uintptr_t *len,
+                                  RsmodRayBlock *block);
+
+/**
+ * Like [rsmod_line_of_walk_result], but packs the tiles into 64 bit wide coordinates like [rsmod_find_path_wide].
+ */
+int32_t rsmod_line_of_walk_wide(const CollisionFlagMap *map,
+                                int32_t y,
+                                int32_t src_x,
+                                int32_t src_z,
+                                int32_t dest_x,
+                                int32_t dest_z,
+                                uint8_t src_width,
+                                uint8_t src_height,
+                                uint8_t dest_width,
+                                uint8_t dest_height,
+                                uint32_t extra_flag,
+                                uint64_t *out,
+                                uintptr_t capacity,
+                                uintptr_t *len,
+                                RsmodRayBlock *block);
+
+int32_t rsmod_reached(const CollisionFlagMap *map,
+                      int32_t y,
+                      int32_t src_x,
+                      int32_t src_z,
+                      int32_t dest_x,
+                      int32_t dest_z,
+                      uint8_t dest_width,
+                      uint8_t dest_height,
+                      uint8_t src_size,
+                      uint8_t angle,
+                      int8_t shape,
+                      uint8_t block_access_flags,
+                      bool *out);
+
+#ifdef __cplusplus
+} // extern "C"
+#endif // __cplusplus
+
+#endif /* RSMOD_PATHFINDER_H */
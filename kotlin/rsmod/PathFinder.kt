@@ -1,6 +1,7 @@
 package rsmod
 
 import java.io.File
+import java.nio.ByteBuffer
 
 object PathFinder {
     init {
@@ -25,6 +26,13 @@ object PathFinder {
         collision: Int
     ): IntArray
 
+    /**
+     * Returns the one tile a `srcWidth` x `srcHeight` mover ends on walking towards the `destWidth` x `destHeight`
+     * target, diagonally first, stopping once it shares a side with the target or can't get any closer.
+     * A mover under the target steps out of the way in a random direction instead.
+     * Before this the target was treated as 1x1, `srcHeight` was ignored and the walk stopped as soon
+     * as either axis lined up with the target, see CHANGELOG.md.
+     */
     @JvmStatic
     external fun findNaivePath(
         y: Int,
@@ -40,6 +48,87 @@ object PathFinder {
         collision: Int
     ): IntArray
 
+    /**
+     * [findNaivePath] stepping out from under the target with a seeded RNG, the same seed always giving the same step.
+     */
+    @JvmStatic
+    external fun findNaivePathSeeded(
+        y: Int,
+        srcX: Int,
+        srcZ: Int,
+        destX: Int,
+        destZ: Int,
+        srcWidth: Int,
+        srcHeight: Int,
+        destWidth: Int,
+        destHeight: Int,
+        extraFlag: Int,
+        collision: Int,
+        seed: Long
+    ): IntArray
+
+    /**
+     * Finds a path into [out] instead of a new array, see [PathResult] for the returned value.
+     * Throws [IllegalArgumentException] for sizes outside of 1..255, an angle outside of 0..3, an unknown
+     * shape other than -1 or -2, or an unknown collision type.
+     */
+    @JvmStatic
+    external fun findPathInto(
+        y: Int,
+        srcX: Int,
+        srcZ: Int,
+        destX: Int,
+        destZ: Int,
+        srcSize: Int,
+        destWidth: Int,
+        destHeight: Int,
+        angle: Int,
+        shape: Int,
+        moveNear: Boolean,
+        blockAccessFlags: Int,
+        maxWaypoints: Int,
+        collision: Int,
+        out: IntArray
+    ): Long
+
+    /**
+     * [findPathInto] writing into a direct buffer in native byte order.
+     */
+    @JvmStatic
+    external fun findPathIntoBuffer(
+        y: Int,
+        srcX: Int,
+        srcZ: Int,
+        destX: Int,
+        destZ: Int,
+        srcSize: Int,
+        destWidth: Int,
+        destHeight: Int,
+        angle: Int,
+        shape: Int,
+        moveNear: Boolean,
+        blockAccessFlags: Int,
+        maxWaypoints: Int,
+        collision: Int,
+        out: ByteBuffer
+    ): Long
+
+    @JvmStatic
+    external fun findNaivePathInto(
+        y: Int,
+        srcX: Int,
+        srcZ: Int,
+        destX: Int,
+        destZ: Int,
+        srcWidth: Int,
+        srcHeight: Int,
+        destWidth: Int,
+        destHeight: Int,
+        extraFlag: Int,
+        collision: Int,
+        out: IntArray
+    ): Long
+
     @JvmStatic
     external fun changeFloor(
         x: Int,
@@ -60,6 +149,61 @@ object PathFinder {
         add: Boolean
     )
 
+    @JvmStatic
+    external fun changeLocShape(
+        x: Int,
+        z: Int,
+        y: Int,
+        width: Int,
+        length: Int,
+        angle: Int,
+        shape: Int,
+        blockrange: Boolean,
+        breakroutefinding: Boolean,
+        add: Boolean
+    )
+
+    /**
+     * Throws [IllegalArgumentException] for an id outside of 0 until 0x100000, sizes outside of 1..255
+     * or a forceapproach outside of 0..255.
+     */
+    @JvmStatic
+    external fun registerLoc(
+        id: Int,
+        width: Int,
+        length: Int,
+        blockwalk: Boolean,
+        blockrange: Boolean,
+        breakroutefinding: Boolean,
+        forceapproach: Int,
+        active: Boolean
+    )
+
+    @JvmStatic
+    external fun addLoc(
+        id: Int,
+        x: Int,
+        z: Int,
+        y: Int,
+        shape: Int,
+        angle: Int
+    ): Boolean
+
+    @JvmStatic
+    external fun removeLoc(
+        id: Int,
+        x: Int,
+        z: Int,
+        y: Int,
+        shape: Int,
+        angle: Int
+    ): Boolean
+
+    @JvmStatic
+    external fun locBlockAccessFlags(
+        id: Int
+    ): Int
+
     @JvmStatic
     external fun changeNpc(
         x: Int,
@@ -86,6 +230,10 @@ object PathFinder {
         add: Boolean
     )
 
+    /**
+     * Flags a wall of shape 0-3. Throws [IllegalArgumentException] for any other shape, diagonal walls (9)
+     * included as they are ground layer locs, see [changeLocShape] for those.
+     */
     @JvmStatic
     external fun changeWall(
         x: Int,
@@ -131,6 +279,45 @@ object PathFinder {
         add: Boolean
     )
 
+    @JvmStatic
+    external fun toggleDoor(
+        x: Int,
+        z: Int,
+        y: Int,
+        closedShape: Int,
+        closedAngle: Int,
+        openShape: Int,
+        openAngle: Int,
+        openOffsetX: Int,
+        openOffsetZ: Int,
+        blockrange: Boolean,
+        breakroutefinding: Boolean,
+        open: Boolean
+    ): IntArray
+
+    /**
+     * Throws [IllegalArgumentException] unless [flags] holds exactly 64 masks.
+     */
+    @JvmStatic
+    external fun setZone(
+        x: Int,
+        z: Int,
+        y: Int,
+        flags: IntArray
+    )
+
+    @JvmStatic
+    external fun applyEdits(
+        edits: IntArray
+    )
+
+    @JvmStatic
+    external fun loadMapsquare(
+        mapsquareX: Int,
+        mapsquareZ: Int,
+        buffer: ByteBuffer
+    )
+
     @JvmStatic
     external fun allocateIfAbsent(
         x: Int,
@@ -152,6 +339,24 @@ object PathFinder {
         y: Int
     ): Boolean
 
+    @JvmStatic
+    external fun enableJournal(
+        recordTiles: Boolean
+    )
+
+    @JvmStatic
+    external fun disableJournal()
+
+    @JvmStatic
+    external fun zoneVersion(
+        x: Int,
+        z: Int,
+        y: Int
+    ): Int
+
+    @JvmStatic
+    external fun drainChangedZones(): IntArray
+
     @JvmStatic
     external fun isFlagged(
         x: Int,
@@ -228,6 +433,72 @@ object PathFinder {
         extraFlag: Int
     ): IntArray
 
+    @JvmStatic
+    external fun lineOfSightInto(
+        y: Int,
+        srcX: Int,
+        srcZ: Int,
+        destX: Int,
+        destZ: Int,
+        srcWidth: Int,
+        srcHeight: Int,
+        destWidth: Int,
+        destHeight: Int,
+        extraFlag: Int,
+        out: IntArray
+    ): Long
+
+    @JvmStatic
+    external fun lineOfWalkInto(
+        y: Int,
+        srcX: Int,
+        srcZ: Int,
+        destX: Int,
+        destZ: Int,
+        srcWidth: Int,
+        srcHeight: Int,
+        destWidth: Int,
+        destHeight: Int,
+        extraFlag: Int,
+        out: IntArray
+    ): Long
+
+    /**
+     * [lineOfSight] keeping the tiles up to an obstruction, wrap it in [RayResult] to see what blocked the line.
+     * Throws [IllegalArgumentException] for sizes outside of 1..255.
+     */
+    @JvmStatic
+    external fun lineOfSightResult(
+        y: Int,
+        srcX: Int,
+        srcZ: Int,
+        destX: Int,
+        destZ: Int,
+        srcWidth: Int,
+        srcHeight: Int,
+        destWidth: Int,
+        destHeight: Int,
+        extraFlag: Int
+    ): IntArray
+
+    /**
+     * [lineOfWalk] keeping the tiles up to an obstruction, wrap it in [RayResult] to see what blocked the line.
+     * Throws [IllegalArgumentException] for sizes outside of 1..255.
+     */
+    @JvmStatic
+    external fun lineOfWalkResult(
+        y: Int,
+        srcX: Int,
+        srcZ: Int,
+        destX: Int,
+        destZ: Int,
+        srcWidth: Int,
+        srcHeight: Int,
+        destWidth: Int,
+        destHeight: Int,
+        extraFlag: Int
+    ): IntArray
+
     @JvmStatic
     external fun reached(
         y: Int,
@@ -243,6 +514,9 @@ object PathFinder {
         blockAccessFlags: Int
     ): Boolean
 
+    /**
+     * Returns the layer of a loc shape, throwing [IllegalArgumentException] for an unknown shape.
+     */
     @JvmStatic
     external fun locShapeLayer(
         shape: Int
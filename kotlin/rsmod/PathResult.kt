@@ -0,0 +1,23 @@
+package rsmod
+
+/**
+ * Result of the `*Into` functions, packed as `(status << 32) | waypoints`.
+ * When [status] is [BUFFER_TOO_SMALL] nothing was written to the output
+ * and [waypoints] is how many it needs, so it can be retried with a larger one.
+ */
+@JvmInline
+value class PathResult(val packed: Long) {
+    val status: Int
+        get() = (packed ushr 32).toInt()
+
+    val waypoints: Int
+        get() = packed.toInt()
+
+    val isOk: Boolean
+        get() = status == OK
+
+    companion object {
+        const val OK = 0
+        const val BUFFER_TOO_SMALL = 3
+    }
+}
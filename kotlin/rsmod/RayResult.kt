@@ -0,0 +1,47 @@
+package rsmod
+
+/**
+ * Result of the `*Result` line functions, laid out as `[blocker, x, z, y, flags, tiles...]`.
+ * [tiles] holds the packed tiles the line entered, up to where it was blocked.
+ * When the line is clear [blocker] is -1 and the blocking tile and [flags] are 0.
+ */
+@JvmInline
+value class RayResult(val packed: IntArray) {
+    /** One of the blocker constants below, or -1 if the line is clear. */
+    val blocker: Int
+        get() = packed[0]
+
+    val isClear: Boolean
+        get() = blocker == CLEAR
+
+    /** Coordinates of the tile that blocked the line. */
+    val x: Int
+        get() = packed[1]
+
+    val z: Int
+        get() = packed[2]
+
+    val y: Int
+        get() = packed[3]
+
+    /** Flags of the blocking tile that the line checked for. */
+    val flags: Int
+        get() = packed[4]
+
+    val tiles: IntArray
+        get() = packed.copyOfRange(HEADER, packed.size)
+
+    companion object {
+        const val HEADER = 5
+
+        const val CLEAR = -1
+        const val WALL_WEST = 0
+        const val WALL_EAST = 1
+        const val WALL_SOUTH = 2
+        const val WALL_NORTH = 3
+        const val LOC = 4
+        const val LOC_PROJ_BLOCKER = 5
+        const val FLOOR = 6
+        const val EXTRA_FLAG = 7
+    }
+}
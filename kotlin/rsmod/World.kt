@@ -0,0 +1,568 @@
+package rsmod
+
+import java.io.File
+import java.nio.ByteBuffer
+
+/**
+ * Handle based variant of [PathFinder] so several worlds can live in one process.
+ * A world handle of 0 refers to the world used by [PathFinder], as does a pathfinder handle of 0.
+ * Pathfinder handles hold scratch space and are best kept one per thread.
+ * Collision changes that reach outside of the world throw [IllegalArgumentException] without changing anything.
+ * Paths and lines with a tile that can't be packed into a coordinate, e.g. far out in a sparse world,
+ * throw [IllegalArgumentException] as well.
+ * Anything going wrong inside the library itself is thrown as an [IllegalStateException] rather than crashing the JVM.
+ */
+object World {
+    init {
+        System.load(File("./lib/rsmod.dll").absolutePath)
+    }
+
+    @JvmStatic
+    external fun create(): Long
+
+    @JvmStatic
+    external fun destroy(
+        world: Long
+    )
+
+    @JvmStatic
+    external fun createPathFinder(): Long
+
+    @JvmStatic
+    external fun destroyPathFinder(
+        pathfinder: Long
+    )
+
+    @JvmStatic
+    external fun findPath(
+        world: Long,
+        pathfinder: Long,
+        y: Int,
+        srcX: Int,
+        srcZ: Int,
+        destX: Int,
+        destZ: Int,
+        srcSize: Int,
+        destWidth: Int,
+        destHeight: Int,
+        angle: Int,
+        shape: Int,
+        moveNear: Boolean,
+        blockAccessFlags: Int,
+        maxWaypoints: Int,
+        collision: Int
+    ): IntArray
+
+    /**
+     * Returns the one tile a `srcWidth` x `srcHeight` mover ends on walking towards the `destWidth` x `destHeight`
+     * target, diagonally first, stopping once it shares a side with the target or can't get any closer.
+     * A mover under the target steps out of the way in a random direction instead.
+     * Before this the target was treated as 1x1, `srcHeight` was ignored and the walk stopped as soon
+     * as either axis lined up with the target, see CHANGELOG.md.
+     */
+    @JvmStatic
+    external fun findNaivePath(
+        world: Long,
+        y: Int,
+        srcX: Int,
+        srcZ: Int,
+        destX: Int,
+        destZ: Int,
+        srcWidth: Int,
+        srcHeight: Int,
+        destWidth: Int,
+        destHeight: Int,
+        extraFlag: Int,
+        collision: Int
+    ): IntArray
+
+    /**
+     * [findNaivePath] stepping out from under the target with a seeded RNG, the same seed always giving the same step.
+     */
+    @JvmStatic
+    external fun findNaivePathSeeded(
+        world: Long,
+        y: Int,
+        srcX: Int,
+        srcZ: Int,
+        destX: Int,
+        destZ: Int,
+        srcWidth: Int,
+        srcHeight: Int,
+        destWidth: Int,
+        destHeight: Int,
+        extraFlag: Int,
+        collision: Int,
+        seed: Long
+    ): IntArray
+
+    /**
+     * Finds a path into [out] instead of a new array, see [PathResult] for the returned value.
+     * Throws [IllegalArgumentException] for sizes outside of 1..255, an angle outside of 0..3, an unknown
+     * shape other than -1 or -2, or an unknown collision type.
+     */
+    @JvmStatic
+    external fun findPathInto(
+        world: Long,
+        pathfinder: Long,
+        y: Int,
+        srcX: Int,
+        srcZ: Int,
+        destX: Int,
+        destZ: Int,
+        srcSize: Int,
+        destWidth: Int,
+        destHeight: Int,
+        angle: Int,
+        shape: Int,
+        moveNear: Boolean,
+        blockAccessFlags: Int,
+        maxWaypoints: Int,
+        collision: Int,
+        out: IntArray
+    ): Long
+
+    /**
+     * [findPathInto] writing into a direct buffer in native byte order.
+     */
+    @JvmStatic
+    external fun findPathIntoBuffer(
+        world: Long,
+        pathfinder: Long,
+        y: Int,
+        srcX: Int,
+        srcZ: Int,
+        destX: Int,
+        destZ: Int,
+        srcSize: Int,
+        destWidth: Int,
+        destHeight: Int,
+        angle: Int,
+        shape: Int,
+        moveNear: Boolean,
+        blockAccessFlags: Int,
+        maxWaypoints: Int,
+        collision: Int,
+        out: ByteBuffer
+    ): Long
+
+    @JvmStatic
+    external fun findNaivePathInto(
+        world: Long,
+        y: Int,
+        srcX: Int,
+        srcZ: Int,
+        destX: Int,
+        destZ: Int,
+        srcWidth: Int,
+        srcHeight: Int,
+        destWidth: Int,
+        destHeight: Int,
+        extraFlag: Int,
+        collision: Int,
+        out: IntArray
+    ): Long
+
+    @JvmStatic
+    external fun changeFloor(
+        world: Long,
+        x: Int,
+        z: Int,
+        y: Int,
+        add: Boolean
+    )
+
+    @JvmStatic
+    external fun changeLoc(
+        world: Long,
+        x: Int,
+        z: Int,
+        y: Int,
+        width: Int,
+        length: Int,
+        blockrange: Boolean,
+        breakroutefinding: Boolean,
+        add: Boolean
+    )
+
+    @JvmStatic
+    external fun changeLocShape(
+        world: Long,
+        x: Int,
+        z: Int,
+        y: Int,
+        width: Int,
+        length: Int,
+        angle: Int,
+        shape: Int,
+        blockrange: Boolean,
+        breakroutefinding: Boolean,
+        add: Boolean
+    )
+
+    @JvmStatic
+    external fun addLoc(
+        world: Long,
+        id: Int,
+        x: Int,
+        z: Int,
+        y: Int,
+        shape: Int,
+        angle: Int
+    ): Boolean
+
+    @JvmStatic
+    external fun removeLoc(
+        world: Long,
+        id: Int,
+        x: Int,
+        z: Int,
+        y: Int,
+        shape: Int,
+        angle: Int
+    ): Boolean
+
+    @JvmStatic
+    external fun changeNpc(
+        world: Long,
+        x: Int,
+        z: Int,
+        y: Int,
+        size: Int,
+        add: Boolean
+    )
+
+    @JvmStatic
+    external fun changePlayer(
+        world: Long,
+        x: Int,
+        z: Int,
+        y: Int,
+        size: Int,
+        add: Boolean
+    )
+
+    @JvmStatic
+    external fun changeRoof(
+        world: Long,
+        x: Int,
+        z: Int,
+        y: Int,
+        add: Boolean
+    )
+
+    /**
+     * Flags a wall of shape 0-3. Throws [IllegalArgumentException] for any other shape, diagonal walls (9)
+     * included as they are ground layer locs, see [changeLocShape] for those.
+     */
+    @JvmStatic
+    external fun changeWall(
+        world: Long,
+        x: Int,
+        z: Int,
+        y: Int,
+        angle: Int,
+        shape: Int,
+        blockrange: Boolean,
+        breakroutefinding: Boolean,
+        add: Boolean
+    )
+
+    @JvmStatic
+    external fun changeWallStraight(
+        world: Long,
+        x: Int,
+        z: Int,
+        y: Int,
+        angle: Int,
+        blockrange: Boolean,
+        breakroutefinding: Boolean,
+        add: Boolean
+    )
+
+    @JvmStatic
+    external fun changeWallCorner(
+        world: Long,
+        x: Int,
+        z: Int,
+        y: Int,
+        angle: Int,
+        blockrange: Boolean,
+        breakroutefinding: Boolean,
+        add: Boolean
+    )
+
+    @JvmStatic
+    external fun changeWallL(
+        world: Long,
+        x: Int,
+        z: Int,
+        y: Int,
+        angle: Int,
+        blockrange: Boolean,
+        breakroutefinding: Boolean,
+        add: Boolean
+    )
+
+    @JvmStatic
+    external fun toggleDoor(
+        world: Long,
+        x: Int,
+        z: Int,
+        y: Int,
+        closedShape: Int,
+        closedAngle: Int,
+        openShape: Int,
+        openAngle: Int,
+        openOffsetX: Int,
+        openOffsetZ: Int,
+        blockrange: Boolean,
+        breakroutefinding: Boolean,
+        open: Boolean
+    ): IntArray
+
+    /**
+     * Throws [IllegalArgumentException] unless [flags] holds exactly 64 masks.
+     */
+    @JvmStatic
+    external fun setZone(
+        world: Long,
+        x: Int,
+        z: Int,
+        y: Int,
+        flags: IntArray
+    )
+
+    @JvmStatic
+    external fun applyEdits(
+        world: Long,
+        edits: IntArray
+    )
+
+    @JvmStatic
+    external fun loadMapsquare(
+        world: Long,
+        mapsquareX: Int,
+        mapsquareZ: Int,
+        buffer: ByteBuffer
+    )
+
+    @JvmStatic
+    external fun allocateIfAbsent(
+        world: Long,
+        x: Int,
+        z: Int,
+        y: Int
+    )
+
+    @JvmStatic
+    external fun deallocateIfPresent(
+        world: Long,
+        x: Int,
+        z: Int,
+        y: Int
+    )
+
+    @JvmStatic
+    external fun isZoneAllocated(
+        world: Long,
+        x: Int,
+        z: Int,
+        y: Int
+    ): Boolean
+
+    @JvmStatic
+    external fun enableJournal(
+        world: Long,
+        recordTiles: Boolean
+    )
+
+    @JvmStatic
+    external fun disableJournal(
+        world: Long
+    )
+
+    @JvmStatic
+    external fun zoneVersion(
+        world: Long,
+        x: Int,
+        z: Int,
+        y: Int
+    ): Int
+
+    /**
+     * Throws [IllegalArgumentException], leaving the zones to drain, if one of them can't be packed.
+     */
+    @JvmStatic
+    external fun drainChangedZones(
+        world: Long
+    ): IntArray
+
+    @JvmStatic
+    external fun isFlagged(
+        world: Long,
+        x: Int,
+        z: Int,
+        y: Int,
+        masks: Int
+    ): Boolean
+
+    @JvmStatic
+    external fun canTravel(
+        world: Long,
+        y: Int,
+        x: Int,
+        z: Int,
+        offsetX: Int,
+        offsetZ: Int,
+        size: Int,
+        extraFlag: Int,
+        collision: Int
+    ): Boolean
+
+    @JvmStatic
+    external fun hasLineOfSight(
+        world: Long,
+        y: Int,
+        srcX: Int,
+        srcZ: Int,
+        destX: Int,
+        destZ: Int,
+        srcWidth: Int,
+        srcHeight: Int,
+        destWidth: Int,
+        destHeight: Int,
+        extraFlag: Int
+    ): Boolean
+
+    @JvmStatic
+    external fun hasLineOfWalk(
+        world: Long,
+        y: Int,
+        srcX: Int,
+        srcZ: Int,
+        destX: Int,
+        destZ: Int,
+        srcWidth: Int,
+        srcHeight: Int,
+        destWidth: Int,
+        destHeight: Int,
+        extraFlag: Int
+    ): Boolean
+
+    @JvmStatic
+    external fun lineOfSight(
+        world: Long,
+        y: Int,
+        srcX: Int,
+        srcZ: Int,
+        destX: Int,
+        destZ: Int,
+        srcWidth: Int,
+        srcHeight: Int,
+        destWidth: Int,
+        destHeight: Int,
+        extraFlag: Int
+    ): IntArray
+
+    @JvmStatic
+    external fun lineOfWalk(
+        world: Long,
+        y: Int,
+        srcX: Int,
+        srcZ: Int,
+        destX: Int,
+        destZ: Int,
+        srcWidth: Int,
+        srcHeight: Int,
+        destWidth: Int,
+        destHeight: Int,
+        extraFlag: Int
+    ): IntArray
+
+    @JvmStatic
+    external fun lineOfSightInto(
+        world: Long,
+        y: Int,
+        srcX: Int,
+        srcZ: Int,
+        destX: Int,
+        destZ: Int,
+        srcWidth: Int,
+        srcHeight: Int,
+        destWidth: Int,
+        destHeight: Int,
+        extraFlag: Int,
+        out: IntArray
+    ): Long
+
+    @JvmStatic
+    external fun lineOfWalkInto(
+        world: Long,
+        y: Int,
+        srcX: Int,
+        srcZ: Int,
+        destX: Int,
+        destZ: Int,
+        srcWidth: Int,
+        srcHeight: Int,
+        destWidth: Int,
+        destHeight: Int,
+        extraFlag: Int,
+        out: IntArray
+    ): Long
+
+    /**
+     * [lineOfSight] keeping the tiles up to an obstruction, wrap it in [RayResult] to see what blocked the line.
+     * Throws [IllegalArgumentException] for sizes outside of 1..255.
+     */
+    @JvmStatic
+    external fun lineOfSightResult(
+        world: Long,
+        y: Int,
+        srcX: Int,
+        srcZ: Int,
+        destX: Int,
+        destZ: Int,
+        srcWidth: Int,
+        srcHeight: Int,
+        destWidth: Int,
+        destHeight: Int,
+        extraFlag: Int
+    ): IntArray
+
+    /**
+     * [lineOfWalk] keeping the tiles up to an obstruction, wrap it in [RayResult] to see what blocked the line.
+     * Throws [IllegalArgumentException] for sizes outside of 1..255.
+     */
+    @JvmStatic
+    external fun lineOfWalkResult(
+        world: Long,
+        y: Int,
+        srcX: Int,
+        srcZ: Int,
+        destX: Int,
+        destZ: Int,
+        srcWidth: Int,
+        srcHeight: Int,
+        destWidth: Int,
+        destHeight: Int,
+        extraFlag: Int
+    ): IntArray
+
+    @JvmStatic
+    external fun reached(
+        world: Long,
+        y: Int,
+        srcX: Int,
+        srcZ: Int,
+        destX: Int,
+        destZ: Int,
+        destWidth: Int,
+        destHeight: Int,
+        srcSize: Int,
+        angle: Int,
+        shape: Int,
+        blockAccessFlags: Int
+    ): Boolean
+}
\ No newline at end of file
@@ -0,0 +1,883 @@
+use std::panic::{catch_unwind, AssertUnwindSafe};
+
+use crate::rsmod::{
+    can_travel, find_naive_path, find_naive_path_with, get_collision_strategy, has_line_of_sight, has_line_of_walk,
+    line_of_sight, line_of_sight_result, line_of_sight_wide, line_of_walk, line_of_walk_result, line_of_walk_wide, CollisionType, CoordOutOfRange, LocShape, PathFinder, RayResult, SeededRandom,
+};
+use crate::rsmod::collision::collision::CollisionFlagMap;
+use crate::rsmod::collision::collision_change::{ChangeWallError, CollisionFlagWriter};
+use crate::rsmod::reach_strategy::ReachStrategy;
+
+/**
+ * Status codes returned by every `rsmod_*` function that can fail.
+ */
+pub const RSMOD_OK: i32 = 0;
+pub const RSMOD_ERROR_NULL_POINTER: i32 = 1;
+pub const RSMOD_ERROR_INVALID_ARGUMENT: i32 = 2;
+pub const RSMOD_ERROR_BUFFER_TOO_SMALL: i32 = 3;
+pub const RSMOD_ERROR_OUT_OF_RANGE: i32 = 4;
+pub const RSMOD_ERROR_PANIC: i32 = 5;
+
+/**
+ * Runs `f`, turning a panic into [RSMOD_ERROR_PANIC] so it never unwinds into the host.
+ */
+#[inline(always)]
+fn guard(f: impl FnOnce() -> i32) -> i32 {
+    return catch_unwind(AssertUnwindSafe(f)).unwrap_or(RSMOD_ERROR_PANIC);
+}
+
+/**
+ * Maps a write that may fall outside of the map to [RSMOD_OK] or [RSMOD_ERROR_OUT_OF_RANGE].
+ */
+#[inline(always)]
+fn range_status(result: Result<(), CoordOutOfRange>) -> i32 {
+    return match result {
+        Ok(_) => RSMOD_OK,
+        Err(_) => RSMOD_ERROR_OUT_OF_RANGE,
+    };
+}
+
+/**
+ * Writes packed coordinates to `out`, or returns [RSMOD_ERROR_OUT_OF_RANGE] if they couldn't be packed.
+ */
+#[inline(always)]
+unsafe fn write_packed<T: Copy>(packed: Result<Vec<T>, CoordOutOfRange>, out: *mut T, capacity: usize, len: *mut usize) -> i32 {
+    return match packed {
+        Ok(packed) => write_output(&packed, out, capacity, len),
+        Err(_) => RSMOD_ERROR_OUT_OF_RANGE,
+    };
+}
+
+/**
+ * The tile that blocked a line, see [rsmod_line_of_sight_result].
+ * `blocker` is -1 if the line is clear, with the rest left 0, otherwise 0 to 3 for a wall on the west,
+ * east, south or north side of the tile, 4 for a loc, 5 for a loc blocking projectiles, 6 for the floor
+ * and 7 for the extra flag. `flags` holds the flags of the tile that the line checked for.
+ */
+#[repr(C)]
+pub struct RsmodRayBlock {
+    pub blocker: i32,
+    pub x: i32,
+    pub z: i32,
+    pub y: i32,
+    pub flags: u32,
+}
+
+/**
+ * Writes what blocked a ray to `block` and its tiles to `out`, see [write_output].
+ */
+#[inline(always)]
+unsafe fn write_ray<T: Copy>(ray: Result<RayResult<T>, CoordOutOfRange>, out: *mut T, capacity: usize, len: *mut usize, block: *mut RsmodRayBlock) -> i32 {
+    let Some(block) = block.as_mut() else { return RSMOD_ERROR_NULL_POINTER };
+    let Ok(ray) = ray else { return RSMOD_ERROR_OUT_OF_RANGE };
+    *block = match ray.blocked {
+        Some(blocked) => RsmodRayBlock {
+            blocker: blocked.blocker as i32,
+            x: blocked.x,
+            z: blocked.z,
+            y: blocked.y,
+            flags: blocked.flags,
+        },
+        None => RsmodRayBlock { blocker: -1, x: 0, z: 0, y: 0, flags: 0 },
+    };
+    return write_output(&ray.tiles, out, capacity, len);
+}
+
+#[inline(always)]
+fn collision_type(collision: u8) -> Option<CollisionType> {
+    return match collision {
+        0 => Some(CollisionType::NORMAL),
+        1 => Some(CollisionType::BLOCKED),
+        2 => Some(CollisionType::INDOORS),
+        3 => Some(CollisionType::OUTDOORS),
+        4 => Some(CollisionType::LINE_OF_SIGHT),
+        _ => None,
+    };
+}
+
+/**
+ * Copies `data` into the caller's buffer and writes how many values it holds to `len`.
+ * `len` is written even if the buffer is too small, so the caller can retry with the right size.
+ */
+#[inline(always)]
+unsafe fn write_output<T: Copy>(data: &[T], out: *mut T, capacity: usize, len: *mut usize) -> i32 {
+    if len.is_null() {
+        return RSMOD_ERROR_NULL_POINTER;
+    }
+    *len = data.len();
+    if data.len() > capacity {
+        return RSMOD_ERROR_BUFFER_TOO_SMALL;
+    }
+    if !data.is_empty() {
+        if out.is_null() {
+            return RSMOD_ERROR_NULL_POINTER;
+        }
+        std::ptr::copy_nonoverlapping(data.as_ptr(), out, data.len());
+    }
+    return RSMOD_OK;
+}
+
+/**
+ * Creates a dense map over x and z in 0..16384. Free it with [rsmod_map_free].
+ */
+#[no_mangle]
+pub extern "C" fn rsmod_map_new() -> *mut CollisionFlagMap {
+    return Box::into_raw(Box::new(CollisionFlagMap::new()));
+}
+
+/**
+ * Creates a map over x and z in -0x40000000..=0x3fffffff that only allocates the mapsquares written to.
+ */
+#[no_mangle]
+pub extern "C" fn rsmod_map_new_sparse() -> *mut CollisionFlagMap {
+    return Box::into_raw(Box::new(CollisionFlagMap::sparse()));
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn rsmod_map_free(map: *mut CollisionFlagMap) {
+    if !map.is_null() {
+        drop(Box::from_raw(map));
+    }
+}
+
+/**
+ * Creates a pathfinder. A pathfinder keeps its search buffers between calls,
+ * so every thread searching at the same time needs its own.
+ */
+#[no_mangle]
+pub extern "C" fn rsmod_pathfinder_new() -> *mut PathFinder {
+    return Box::into_raw(Box::new(PathFinder::new()));
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn rsmod_pathfinder_free(pathfinder: *mut PathFinder) {
+    if !pathfinder.is_null() {
+        drop(Box::from_raw(pathfinder));
+    }
+}
+
+/**
+ * Writes the flags of the tile to `out`, `0x7FFFFFFF` if its zone is not allocated.
+ */
+#[no_mangle]
+pub unsafe extern "C" fn rsmod_map_get(map: *const CollisionFlagMap, x: i32, z: i32, y: i32, out: *mut u32) -> i32 {
+    let Some(map) = map.as_ref() else { return RSMOD_ERROR_NULL_POINTER };
+    if out.is_null() {
+        return RSMOD_ERROR_NULL_POINTER;
+    }
+    *out = map.get(x, z, y);
+    return RSMOD_OK;
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn rsmod_map_set(map: *mut CollisionFlagMap, x: i32, z: i32, y: i32, mask: u32) -> i32 {
+    let Some(map) = map.as_mut() else { return RSMOD_ERROR_NULL_POINTER };
+    return match map.try_set(x, z, y, mask) {
+        Ok(_) => RSMOD_OK,
+        Err(_) => RSMOD_ERROR_OUT_OF_RANGE,
+    };
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn rsmod_map_add(map: *mut CollisionFlagMap, x: i32, z: i32, y: i32, mask: u32) -> i32 {
+    let Some(map) = map.as_mut() else { return RSMOD_ERROR_NULL_POINTER };
+    return match map.try_add(x, z, y, mask) {
+        Ok(_) => RSMOD_OK,
+        Err(_) => RSMOD_ERROR_OUT_OF_RANGE,
+    };
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn rsmod_map_remove(map: *mut CollisionFlagMap, x: i32, z: i32, y: i32, mask: u32) -> i32 {
+    let Some(map) = map.as_mut() else { return RSMOD_ERROR_NULL_POINTER };
+    return match map.try_remove(x, z, y, mask) {
+        Ok(_) => RSMOD_OK,
+        Err(_) => RSMOD_ERROR_OUT_OF_RANGE,
+    };
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn rsmod_map_allocate_if_absent(map: *mut CollisionFlagMap, x: i32, z: i32, y: i32) -> i32 {
+    let Some(map) = map.as_mut() else { return RSMOD_ERROR_NULL_POINTER };
+    return match map.try_allocate_if_absent(x, z, y) {
+        Ok(_) => RSMOD_OK,
+        Err(_) => RSMOD_ERROR_OUT_OF_RANGE,
+    };
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn rsmod_map_deallocate_if_present(map: *mut CollisionFlagMap, x: i32, z: i32, y: i32) -> i32 {
+    let Some(map) = map.as_mut() else { return RSMOD_ERROR_NULL_POINTER };
+    map.deallocate_if_present(x, z, y);
+    return RSMOD_OK;
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn rsmod_map_is_zone_allocated(map: *const CollisionFlagMap, x: i32, z: i32, y: i32, out: *mut bool) -> i32 {
+    let Some(map) = map.as_ref() else { return RSMOD_ERROR_NULL_POINTER };
+    if out.is_null() {
+        return RSMOD_ERROR_NULL_POINTER;
+    }
+    *out = map.is_zone_allocated(x, z, y);
+    return RSMOD_OK;
+}
+
+/**
+ * Replaces the zone with the 64 masks at `flags`, indexed by `(x & 7) | ((z & 7) << 3)`.
+ */
+#[no_mangle]
+pub unsafe extern "C" fn rsmod_map_set_zone(map: *mut CollisionFlagMap, x: i32, z: i32, y: i32, flags: *const u32) -> i32 {
+    let Some(map) = map.as_mut() else { return RSMOD_ERROR_NULL_POINTER };
+    if flags.is_null() {
+        return RSMOD_ERROR_NULL_POINTER;
+    }
+    return range_status(map.try_set_zone(x, z, y, &*(flags as *const [u32; 64])));
+}
+
+/**
+ * Replaces all 4 levels of a mapsquare from `len` masks, see `CollisionFlagMap::load_mapsquare`.
+ */
+#[no_mangle]
+pub unsafe extern "C" fn rsmod_map_load_mapsquare(
+    map: *mut CollisionFlagMap,
+    mapsquare_x: i32,
+    mapsquare_z: i32,
+    flags: *const u32,
+    len: usize,
+) -> i32 {
+    let Some(map) = map.as_mut() else { return RSMOD_ERROR_NULL_POINTER };
+    if flags.is_null() {
+        return RSMOD_ERROR_NULL_POINTER;
+    }
+    if !map.contains(mapsquare_x << 6, mapsquare_z << 6, 0) || !map.contains((mapsquare_x << 6) + 63, (mapsquare_z << 6) + 63, 0) {
+        return RSMOD_ERROR_OUT_OF_RANGE;
+    }
+    if !map.load_mapsquare(mapsquare_x, mapsquare_z, std::slice::from_raw_parts(flags, len)) {
+        return RSMOD_ERROR_INVALID_ARGUMENT;
+    }
+    return RSMOD_OK;
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn rsmod_map_change_floor(map: *mut CollisionFlagMap, x: i32, z: i32, y: i32, add: bool) -> i32 {
+    let Some(map) = map.as_mut() else { return RSMOD_ERROR_NULL_POINTER };
+    return guard(|| range_status(map.change_floor(x, z, y, add)));
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn rsmod_map_change_roof(map: *mut CollisionFlagMap, x: i32, z: i32, y: i32, add: bool) -> i32 {
+    let Some(map) = map.as_mut() else { return RSMOD_ERROR_NULL_POINTER };
+    return guard(|| range_status(map.change_roof(x, z, y, add)));
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn rsmod_map_change_npc(map: *mut CollisionFlagMap, x: i32, z: i32, y: i32, size: i32, add: bool) -> i32 {
+    let Some(map) = map.as_mut() else { return RSMOD_ERROR_NULL_POINTER };
+    return guard(|| range_status(map.change_npc(x, z, y, size, add)));
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn rsmod_map_change_player(map: *mut CollisionFlagMap, x: i32, z: i32, y: i32, size: i32, add: bool) -> i32 {
+    let Some(map) = map.as_mut() else { return RSMOD_ERROR_NULL_POINTER };
+    return guard(|| range_status(map.change_player(x, z, y, size, add)));
+}
+
+/**
+ * Flags the (already rotated) `width` x `length` footprint of a ground loc.
+ */
+#[no_mangle]
+pub unsafe extern "C" fn rsmod_map_change_loc(
+    map: *mut CollisionFlagMap,
+    x: i32,
+    z: i32,
+    y: i32,
+    width: i32,
+    length: i32,
+    blockrange: bool,
+    breakroutefinding: bool,
+    add: bool,
+) -> i32 {
+    let Some(map) = map.as_mut() else { return RSMOD_ERROR_NULL_POINTER };
+    return guard(|| range_status(map.change_loc(x, z, y, width, length, blockrange, breakroutefinding, add)));
+}
+
+/**
+ * Flags a loc of any shape, rotating its footprint and walls by `angle`.
+ */
+#[no_mangle]
+pub unsafe extern "C" fn rsmod_map_change_loc_shape(
+    map: *mut CollisionFlagMap,
+    x: i32,
+    z: i32,
+    y: i32,
+    width: u8,
+    length: u8,
+    angle: u8,
+    shape: i8,
+    blockrange: bool,
+    breakroutefinding: bool,
+    add: bool,
+) -> i32 {
+    let Some(map) = map.as_mut() else { return RSMOD_ERROR_NULL_POINTER };
+    let Some(shape) = LocShape::try_from_i8(shape) else { return RSMOD_ERROR_INVALID_ARGUMENT };
+    return guard(|| range_status(map.change_loc_shape(x, z, y, width, length, angle & 0x3, shape, blockrange, breakroutefinding, add)));
+}
+
+/**
+ * Flags a wall of shape 0-3. Returns [RSMOD_ERROR_INVALID_ARGUMENT] for any other shape, diagonal
+ * walls (9) included as they are ground layer locs, see [rsmod_map_change_loc_shape] for those.
+ */
+#[no_mangle]
+pub unsafe extern "C" fn rsmod_map_change_wall(
+    map: *mut CollisionFlagMap,
+    x: i32,
+    z: i32,
+    y: i32,
+    angle: u8,
+    shape: i8,
+    blockrange: bool,
+    breakroutefinding: bool,
+    add: bool,
+) -> i32 {
+    let Some(map) = map.as_mut() else { return RSMOD_ERROR_NULL_POINTER };
+    let Some(shape) = LocShape::try_from_i8(shape) else { return RSMOD_ERROR_INVALID_ARGUMENT };
+    return guard(|| match map.change_wall(x, z, y, angle & 0x3, shape, blockrange, breakroutefinding, add) {
+        Ok(_) => RSMOD_OK,
+        Err(ChangeWallError::OutOfRange(_)) => RSMOD_ERROR_OUT_OF_RANGE,
+        Err(ChangeWallError::NotAWall(_)) => RSMOD_ERROR_INVALID_ARGUMENT,
+    });
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn rsmod_map_change_wall_straight(
+    map: *mut CollisionFlagMap,
+    x: i32,
+    z: i32,
+    y: i32,
+    angle: u8,
+    blockrange: bool,
+    breakroutefinding: bool,
+    add: bool,
+) -> i32 {
+    let Some(map) = map.as_mut() else { return RSMOD_ERROR_NULL_POINTER };
+    if angle > 3 {
+        return RSMOD_ERROR_INVALID_ARGUMENT;
+    }
+    return guard(|| range_status(map.change_wall_straight(x, z, y, angle, blockrange, breakroutefinding, add)));
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn rsmod_map_change_wall_corner(
+    map: *mut CollisionFlagMap,
+    x: i32,
+    z: i32,
+    y: i32,
+    angle: u8,
+    blockrange: bool,
+    breakroutefinding: bool,
+    add: bool,
+) -> i32 {
+    let Some(map) = map.as_mut() else { return RSMOD_ERROR_NULL_POINTER };
+    if angle > 3 {
+        return RSMOD_ERROR_INVALID_ARGUMENT;
+    }
+    return guard(|| range_status(map.change_wall_corner(x, z, y, angle, blockrange, breakroutefinding, add)));
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn rsmod_map_change_wall_l(
+    map: *mut CollisionFlagMap,
+    x: i32,
+    z: i32,
+    y: i32,
+    angle: u8,
+    blockrange: bool,
+    breakroutefinding: bool,
+    add: bool,
+) -> i32 {
+    let Some(map) = map.as_mut() else { return RSMOD_ERROR_NULL_POINTER };
+    if angle > 3 {
+        return RSMOD_ERROR_INVALID_ARGUMENT;
+    }
+    return guard(|| range_status(map.change_wall_l(x, z, y, angle, blockrange, breakroutefinding, add)));
+}
+
+/**
+ * Finds a path with the breadth-first search, writing the packed waypoints to `out`.
+ * `collision` is one of `CollisionType`. An empty result means there is no path.
+ * Returns [RSMOD_ERROR_INVALID_ARGUMENT] unless `angle` is in 0..=3 and `shape` is a loc shape, -1 or -2.
+ * Returns [RSMOD_ERROR_OUT_OF_RANGE] if a waypoint can't be packed, e.g. far out in a sparse map, see [rsmod_find_path_wide].
+ */
+#[no_mangle]
+pub unsafe extern "C" fn rsmod_find_path(
+    pathfinder: *mut PathFinder,
+    map: *const CollisionFlagMap,
+    y: i32,
+    src_x: i32,
+    src_z: i32,
+    dest_x: i32,
+    dest_z: i32,
+    src_size: u8,
+    dest_width: u8,
+    dest_height: u8,
+    angle: u8,
+    shape: i8,
+    move_near: bool,
+    block_access_flags: u8,
+    max_waypoints: u8,
+    collision: u8,
+    out: *mut u32,
+    capacity: usize,
+    len: *mut usize,
+) -> i32 {
+    let Some(pathfinder) = pathfinder.as_mut() else { return RSMOD_ERROR_NULL_POINTER };
+    let Some(map) = map.as_ref() else { return RSMOD_ERROR_NULL_POINTER };
+    let Some(collision) = collision_type(collision) else { return RSMOD_ERROR_INVALID_ARGUMENT };
+    if !ReachStrategy::is_valid_target(angle, shape) {
+        return RSMOD_ERROR_INVALID_ARGUMENT;
+    }
+    return guard(|| {
+        let waypoints: Result<Vec<u32>, CoordOutOfRange> = pathfinder.find_path(
+            map,
+            y,
+            src_x,
+            src_z,
+            dest_x,
+            dest_z,
+            src_size,
+            dest_width,
+            dest_height,
+            angle,
+            shape,
+            move_near,
+            block_access_flags,
+            max_waypoints,
+            &get_collision_strategy(collision),
+        );
+        return write_packed(waypoints, out, capacity, len);
+    });
+}
+
+/**
+ * Like [rsmod_find_path], but packs the waypoints into 64 bit wide coordinates: z in the low 31 bits,
+ * x in the next 31, both signed, and y in the top 2. Use it for paths past 16383 in a sparse map.
+ */
+#[no_mangle]
+pub unsafe extern "C" fn rsmod_find_path_wide(
+    pathfinder: *mut PathFinder,
+    map: *const CollisionFlagMap,
+    y: i32,
+    src_x: i32,
+    src_z: i32,
+    dest_x: i32,
+    dest_z: i32,
+    src_size: u8,
+    dest_width: u8,
+    dest_height: u8,
+    angle: u8,
+    shape: i8,
+    move_near: bool,
+    block_access_flags: u8,
+    max_waypoints: u8,
+    collision: u8,
+    out: *mut u64,
+    capacity: usize,
+    len: *mut usize,
+) -> i32 {
+    let Some(pathfinder) = pathfinder.as_mut() else { return RSMOD_ERROR_NULL_POINTER };
+    let Some(map) = map.as_ref() else { return RSMOD_ERROR_NULL_POINTER };
+    let Some(collision) = collision_type(collision) else { return RSMOD_ERROR_INVALID_ARGUMENT };
+    if !ReachStrategy::is_valid_target(angle, shape) {
+        return RSMOD_ERROR_INVALID_ARGUMENT;
+    }
+    return guard(|| {
+        let waypoints: Result<Vec<u64>, CoordOutOfRange> = pathfinder.find_path_wide(
+            map,
+            y,
+            src_x,
+            src_z,
+            dest_x,
+            dest_z,
+            src_size,
+            dest_width,
+            dest_height,
+            angle,
+            shape,
+            move_near,
+            block_access_flags,
+            max_waypoints,
+            &get_collision_strategy(collision),
+        );
+        return write_packed(waypoints, out, capacity, len);
+    });
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn rsmod_find_naive_path(
+    map: *const CollisionFlagMap,
+    y: i32,
+    src_x: i32,
+    src_z: i32,
+    dest_x: i32,
+    dest_z: i32,
+    src_width: u8,
+    src_height: u8,
+    dest_width: u8,
+    dest_height: u8,
+    extra_flag: u32,
+    collision: u8,
+    out: *mut u32,
+    capacity: usize,
+    len: *mut usize,
+) -> i32 {
+    let Some(map) = map.as_ref() else { return RSMOD_ERROR_NULL_POINTER };
+    let Some(collision) = collision_type(collision) else { return RSMOD_ERROR_INVALID_ARGUMENT };
+    return guard(|| {
+        let waypoints: Result<Vec<u32>, CoordOutOfRange> = find_naive_path(
+            map,
+            y,
+            src_x,
+            src_z,
+            dest_x,
+            dest_z,
+            src_width,
+            src_height,
+            dest_width,
+            dest_height,
+            extra_flag,
+            &get_collision_strategy(collision),
+        );
+        return write_packed(waypoints, out, capacity, len);
+    });
+}
+
+/**
+ * Like `rsmod_find_naive_path`, but stepping out from under the target is driven by `seed`
+ * so the same seed always gives the same step.
+ */
+#[no_mangle]
+pub unsafe extern "C" fn rsmod_find_naive_path_seeded(
+    map: *const CollisionFlagMap,
+    y: i32,
+    src_x: i32,
+    src_z: i32,
+    dest_x: i32,
+    dest_z: i32,
+    src_width: u8,
+    src_height: u8,
+    dest_width: u8,
+    dest_height: u8,
+    extra_flag: u32,
+    collision: u8,
+    seed: u64,
+    out: *mut u32,
+    capacity: usize,
+    len: *mut usize,
+) -> i32 {
+    let Some(map) = map.as_ref() else { return RSMOD_ERROR_NULL_POINTER };
+    let Some(collision) = collision_type(collision) else { return RSMOD_ERROR_INVALID_ARGUMENT };
+    return guard(|| {
+        let waypoints: Result<Vec<u32>, CoordOutOfRange> = find_naive_path_with(
+            map,
+            y,
+            src_x,
+            src_z,
+            dest_x,
+            dest_z,
+            src_width,
+            src_height,
+            dest_width,
+            dest_height,
+            extra_flag,
+            &get_collision_strategy(collision),
+            &mut SeededRandom::new(seed),
+        );
+        return write_packed(waypoints, out, capacity, len);
+    });
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn rsmod_can_travel(
+    map: *const CollisionFlagMap,
+    y: i32,
+    x: i32,
+    z: i32,
+    offset_x: i8,
+    offset_z: i8,
+    size: u8,
+    extra_flag: u32,
+    collision: u8,
+    out: *mut bool,
+) -> i32 {
+    let Some(map) = map.as_ref() else { return RSMOD_ERROR_NULL_POINTER };
+    let Some(collision) = collision_type(collision) else { return RSMOD_ERROR_INVALID_ARGUMENT };
+    if out.is_null() {
+        return RSMOD_ERROR_NULL_POINTER;
+    }
+    return guard(|| {
+        *out = can_travel(map, y, x, z, offset_x, offset_z, size, extra_flag, &get_collision_strategy(collision));
+        return RSMOD_OK;
+    });
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn rsmod_has_line_of_sight(
+    map: *const CollisionFlagMap,
+    y: i32,
+    src_x: i32,
+    src_z: i32,
+    dest_x: i32,
+    dest_z: i32,
+    src_width: u8,
+    src_height: u8,
+    dest_width: u8,
+    dest_height: u8,
+    extra_flag: u32,
+    out: *mut bool,
+) -> i32 {
+    let Some(map) = map.as_ref() else { return RSMOD_ERROR_NULL_POINTER };
+    if out.is_null() {
+        return RSMOD_ERROR_NULL_POINTER;
+    }
+    return guard(|| {
+        *out = has_line_of_sight(map, y, src_x, src_z, dest_x, dest_z, src_width, src_height, dest_width, dest_height, extra_flag);
+        return RSMOD_OK;
+    });
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn rsmod_has_line_of_walk(
+    map: *const CollisionFlagMap,
+    y: i32,
+    src_x: i32,
+    src_z: i32,
+    dest_x: i32,
+    dest_z: i32,
+    src_width: u8,
+    src_height: u8,
+    dest_width: u8,
+    dest_height: u8,
+    extra_flag: u32,
+    out: *mut bool,
+) -> i32 {
+    let Some(map) = map.as_ref() else { return RSMOD_ERROR_NULL_POINTER };
+    if out.is_null() {
+        return RSMOD_ERROR_NULL_POINTER;
+    }
+    return guard(|| {
+        *out = has_line_of_walk(map, y, src_x, src_z, dest_x, dest_z, src_width, src_height, dest_width, dest_height, extra_flag);
+        return RSMOD_OK;
+    });
+}
+
+/**
+ * Writes the packed tiles of the line of sight to `out`, none if it is blocked.
+ * Returns [RSMOD_ERROR_OUT_OF_RANGE] if a tile can't be packed.
+ */
+#[no_mangle]
+pub unsafe extern "C" fn rsmod_line_of_sight(
+    map: *const CollisionFlagMap,
+    y: i32,
+    src_x: i32,
+    src_z: i32,
+    dest_x: i32,
+    dest_z: i32,
+    src_width: u8,
+    src_height: u8,
+    dest_width: u8,
+    dest_height: u8,
+    extra_flag: u32,
+    out: *mut u32,
+    capacity: usize,
+    len: *mut usize,
+) -> i32 {
+    let Some(map) = map.as_ref() else { return RSMOD_ERROR_NULL_POINTER };
+    return guard(|| {
+        let tiles: Result<Vec<u32>, CoordOutOfRange> = line_of_sight(map, y, src_x, src_z, dest_x, dest_z, src_width, src_height, dest_width, dest_height, extra_flag);
+        return write_packed(tiles, out, capacity, len);
+    });
+}
+
+/**
+ * Writes the packed tiles of the line of walk to `out`, none if it is blocked.
+ * Returns [RSMOD_ERROR_OUT_OF_RANGE] if a tile can't be packed.
+ */
+#[no_mangle]
+pub unsafe extern "C" fn rsmod_line_of_walk(
+    map: *const CollisionFlagMap,
+    y: i32,
+    src_x: i32,
+    src_z: i32,
+    dest_x: i32,
+    dest_z: i32,
+    src_width: u8,
+    src_height: u8,
+    dest_width: u8,
+    dest_height: u8,
+    extra_flag: u32,
+    out: *mut u32,
+    capacity: usize,
+    len: *mut usize,
+) -> i32 {
+    let Some(map) = map.as_ref() else { return RSMOD_ERROR_NULL_POINTER };
+    return guard(|| {
+        let tiles: Result<Vec<u32>, CoordOutOfRange> = line_of_walk(map, y, src_x, src_z, dest_x, dest_z, src_width, src_height, dest_width, dest_height, extra_flag);
+        return write_packed(tiles, out, capacity, len);
+    });
+}
+
+/**
+ * Like [rsmod_line_of_sight], but writes the tiles up to an obstruction to `out` and what blocked the line to `block`.
+ * Returns [RSMOD_ERROR_OUT_OF_RANGE] if a tile can't be packed.
+ */
+#[no_mangle]
+pub unsafe extern "C" fn rsmod_line_of_sight_result(
+    map: *const CollisionFlagMap,
+    y: i32,
+    src_x: i32,
+    src_z: i32,
+    dest_x: i32,
+    dest_z: i32,
+    src_width: u8,
+    src_height: u8,
+    dest_width: u8,
+    dest_height: u8,
+    extra_flag: u32,
+    out: *mut u32,
+    capacity: usize,
+    len: *mut usize,
+    block: *mut RsmodRayBlock,
+) -> i32 {
+    let Some(map) = map.as_ref() else { return RSMOD_ERROR_NULL_POINTER };
+    return guard(|| {
+        let ray: Result<RayResult, CoordOutOfRange> = line_of_sight_result(map, y, src_x, src_z, dest_x, dest_z, src_width, src_height, dest_width, dest_height, extra_flag);
+        return write_ray(ray, out, capacity, len, block);
+    });
+}
+
+/**
+ * Like [rsmod_line_of_sight_result], but packs the tiles into 64 bit wide coordinates like [rsmod_find_path_wide].
+ */
+#[no_mangle]
+pub unsafe extern "C" fn rsmod_line_of_sight_wide(
+    map: *const CollisionFlagMap,
+    y: i32,
+    src_x: i32,
+    src_z: i32,
+    dest_x: i32,
+    dest_z: i32,
+    src_width: u8,
+    src_height: u8,
+    dest_width: u8,
+    dest_height: u8,
+    extra_flag: u32,
+    out: *mut u64,
+    capacity: usize,
+    len: *mut usize,
+    block: *mut RsmodRayBlock,
+) -> i32 {
+    let Some(map) = map.as_ref() else { return RSMOD_ERROR_NULL_POINTER };
+    return guard(|| {
+        let ray: Result<RayResult<u64>, CoordOutOfRange> = line_of_sight_wide(map, y, src_x, src_z, dest_x, dest_z, src_width, src_height, dest_width, dest_height, extra_flag);
+        return write_ray(ray, out, capacity, len, block);
+    });
+}
+
+/**
+ * Like [rsmod_line_of_walk], but writes the tiles up to an obstruction to `out` and what blocked the line to `block`.
+ * Returns [RSMOD_ERROR_OUT_OF_RANGE] if a tile can't be packed.
+ */
+#[no_mangle]
+pub unsafe extern "C" fn rsmod_line_of_walk_result(
+    map: *const CollisionFlagMap,
+    y: i32,
+    src_x: i32,
+    src_z: i32,
+    dest_x: i32,
+    dest_z: i32,
+    src_width: u8,
+    src_height: u8,
+    dest_width: u8,
+    dest_height: u8,
+    extra_flag: u32,
+    out: *mut u32,
+    capacity: usize,
+    len: *mut usize,
+    block: *mut RsmodRayBlock,
+) -> i32 {
+    let Some(map) = map.as_ref() else { return RSMOD_ERROR_NULL_POINTER };
+    return guard(|| {
+        let ray: Result<RayResult, CoordOutOfRange> = line_of_walk_result(map, y, src_x, src_z, dest_x, dest_z, src_width, src_height, dest_width, dest_height, extra_flag);
+        return write_ray(ray, out, capacity, len, block);
+    });
+}
+
+/**
+ * Like [rsmod_line_of_walk_result], but packs the tiles into 64 bit wide coordinates like [rsmod_find_path_wide].
+ */
+#[no_mangle]
+pub unsafe extern "C" fn rsmod_line_of_walk_wide(
+    map: *const CollisionFlagMap,
+    y: i32,
+    src_x: i32,
+    src_z: i32,
+    dest_x: i32,
+    dest_z: i32,
+    src_width: u8,
+    src_height: u8,
+    dest_width: u8,
+    dest_height: u8,
+    extra_flag: u32,
+    out: *mut u64,
+    capacity: usize,
+    len: *mut usize,
+    block: *mut RsmodRayBlock,
+) -> i32 {
+    let Some(map) = map.as_ref() else { return RSMOD_ERROR_NULL_POINTER };
+    return guard(|| {
+        let ray: Result<RayResult<u64>, CoordOutOfRange> = line_of_walk_wide(map, y, src_x, src_z, dest_x, dest_z, src_width, src_height, dest_width, dest_height, extra_flag);
+        return write_ray(ray, out, capacity, len, block);
+    });
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn rsmod_reached(
+    map: *const CollisionFlagMap,
+    y: i32,
+    src_x: i32,
+    src_z: i32,
+    dest_x: i32,
+    dest_z: i32,
+    dest_width: u8,
+    dest_height: u8,
+    src_size: u8,
+    angle: u8,
+    shape: i8,
+    block_access_flags: u8,
+    out: *mut bool,
+) -> i32 {
+    let Some(map) = map.as_ref() else { return RSMOD_ERROR_NULL_POINTER };
+    if out.is_null() {
+        return RSMOD_ERROR_NULL_POINTER;
+    }
+    if !ReachStrategy::is_valid_target(angle, shape) {
+        return RSMOD_ERROR_INVALID_ARGUMENT;
+    }
+    return guard(|| {
+        *out = ReachStrategy::reached(
+            map,
+            y,
+            src_x,
+            src_z,
+            dest_x,
+            dest_z,
+            dest_width,
+            dest_height,
+            src_size,
+            angle,
+            shape,
+            block_access_flags,
+        );
+        return RSMOD_OK;
+    });
+}
@@ -0,0 +1,2242 @@
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::sync::{Mutex, MutexGuard, PoisonError, RwLock, RwLockReadGuard, RwLockWriteGuard};
+use jni::JNIEnv;
+use jni::objects::{JByteBuffer, JClass, JIntArray, ReleaseMode};
+use jni::sys::{jint, jboolean, jintArray, jlong};
+use once_cell::sync::Lazy;
+
+use crate::{COLLISION_FLAGS, PATHFINDER};
+use crate::rsmod::{
+    can_travel, CollisionType, CoordGrid, CoordOutOfRange, Door, DoorState, find_naive_path, find_naive_path_with, has_line_of_sight,
+    get_collision_strategy, has_line_of_walk, line_of_sight, line_of_sight_result, line_of_walk, line_of_walk_result,
+    LocShape, LocType, LocTypeRegistry, PathFinder, RayResult, SeededRandom,
+};
+use crate::rsmod::collision::collision::CollisionFlagMap;
+use crate::rsmod::collision::collision_change::{ChangeWallError, CollisionFlagWriter};
+use crate::rsmod::collision_edit::{CollisionEdit, CollisionOp};
+use crate::rsmod::reach_strategy::ReachStrategy;
+
+static LOC_TYPES: Lazy<Mutex<LocTypeRegistry>> =
+    Lazy::new(|| Mutex::new(LocTypeRegistry::new()));
+
+const ILLEGAL_ARGUMENT: &str = "java/lang/IllegalArgumentException";
+const ILLEGAL_STATE: &str = "java/lang/IllegalStateException";
+
+/**
+ * Statuses of the `*Into` functions, matching the C ABI.
+ * They are returned as `(status << 32) | waypoints`.
+ */
+const STATUS_OK: jlong = 0;
+const STATUS_BUFFER_TOO_SMALL: jlong = 3;
+
+/**
+ * Returns null, with the exception from the JVM pending, if the array could not be made.
+ */
+fn vec_to_jint_array(env: &JNIEnv, data: Vec<u32>) -> jintArray {
+    let jint_vec: Vec<jint> = data.iter().map(|&v| v as jint).collect();
+    let array = match env.new_int_array(jint_vec.len() as i32) {
+        Ok(array) => array,
+        Err(_) => return std::ptr::null_mut(),
+    };
+    if env.set_int_array_region(&array, 0, &jint_vec).is_err() {
+        return std::ptr::null_mut();
+    }
+    array.as_raw()
+}
+
+/**
+ * Runs `f`, turning a panic into an `IllegalStateException` instead of unwinding into the JVM.
+ * Returns `default` if `f` panicked.
+ */
+fn guard<'local, R>(env: &mut JNIEnv<'local>, default: R, f: impl FnOnce(&mut JNIEnv<'local>) -> R) -> R {
+    match catch_unwind(AssertUnwindSafe(|| f(env))) {
+        Ok(result) => result,
+        Err(payload) => {
+            let message: String = match payload.downcast::<String>() {
+                Ok(message) => *message,
+                Err(payload) => match payload.downcast::<&str>() {
+                    Ok(message) => message.to_string(),
+                    Err(_) => "rsmod panicked".to_string(),
+                },
+            };
+            env.throw_new(ILLEGAL_STATE, message);
+            default
+        }
+    }
+}
+
+/**
+ * Throws an `IllegalArgumentException` and returns None for an unknown collision type.
+ */
+fn collision_type(env: &mut JNIEnv, collision: jint) -> Option<CollisionType> {
+    return match collision {
+        0 => Some(CollisionType::NORMAL),
+        1 => Some(CollisionType::BLOCKED),
+        2 => Some(CollisionType::INDOORS),
+        3 => Some(CollisionType::OUTDOORS),
+        4 => Some(CollisionType::LINE_OF_SIGHT),
+        _ => {
+            env.throw_new(ILLEGAL_ARGUMENT, format!("Unknown collision type {}", collision));
+            None
+        }
+    };
+}
+
+/**
+ * Throws an `IllegalArgumentException` and returns None for an unknown loc shape.
+ */
+fn loc_shape(env: &mut JNIEnv, shape: jint) -> Option<LocShape> {
+    let parsed: Option<LocShape> = i8::try_from(shape).ok().and_then(LocShape::try_from_i8);
+    if parsed.is_none() {
+        env.throw_new(ILLEGAL_ARGUMENT, format!("Invalid loc shape: {}", shape));
+    }
+    return parsed;
+}
+
+/**
+ * Throws an `IllegalArgumentException` and returns false if `value` is outside of `min..=max`.
+ */
+fn check_range(env: &mut JNIEnv, name: &str, value: jint, min: jint, max: jint) -> bool {
+    if value < min || value > max {
+        env.throw_new(ILLEGAL_ARGUMENT, format!("{} must be within {}..={}, was {}", name, min, max, value));
+        return false;
+    }
+    return true;
+}
+
+/**
+ * Throws an `IllegalArgumentException` and returns None for a coordinate outside of the world.
+ */
+fn in_world<T>(env: &mut JNIEnv, result: Result<T, CoordOutOfRange>) -> Option<T> {
+    return match result {
+        Ok(value) => Some(value),
+        Err(error) => {
+            env.throw_new(ILLEGAL_ARGUMENT, error.to_string());
+            None
+        }
+    };
+}
+
+/**
+ * [vec_to_jint_array] for packed coordinates, returning null with an `IllegalArgumentException`
+ * pending if they couldn't be packed.
+ */
+fn packed_to_jint_array(env: &mut JNIEnv, packed: Result<Vec<u32>, CoordOutOfRange>) -> jintArray {
+    return match in_world(env, packed) {
+        Some(packed) => vec_to_jint_array(env, packed),
+        None => std::ptr::null_mut(),
+    };
+}
+
+/**
+ * [RayResult::flatten]s a ray for the `*Result` functions, returning null with an
+ * `IllegalArgumentException` pending if its tiles couldn't be packed.
+ */
+fn ray_to_jint_array(env: &mut JNIEnv, ray: Result<RayResult, CoordOutOfRange>) -> jintArray {
+    return match in_world(env, ray) {
+        Some(ray) => vec_to_jint_array(env, ray.flatten().into_iter().map(|value| value as u32).collect()),
+        None => std::ptr::null_mut(),
+    };
+}
+
+#[inline(always)]
+fn check_sizes(env: &mut JNIEnv, sizes: &[(&str, jint)]) -> bool {
+    return sizes.iter().all(|&(name, size)| check_range(env, name, size, 1, 255));
+}
+
+#[inline(always)]
+fn pack_result(capacity: usize, waypoints: usize) -> jlong {
+    let status: jlong = if waypoints > capacity { STATUS_BUFFER_TOO_SMALL } else { STATUS_OK };
+    return (status << 32) | waypoints as jlong;
+}
+
+/**
+ * Copies `waypoints` into `out`, or nothing if they don't all fit, see [pack_result] for the returned value.
+ */
+fn write_array(env: &mut JNIEnv, out: &JIntArray, waypoints: &[u32]) -> jlong {
+    if out.is_null() {
+        env.throw_new(ILLEGAL_ARGUMENT, "Waypoint array must not be null");
+        return 0;
+    }
+    let capacity: usize = match env.get_array_length(out) {
+        Ok(length) => length as usize,
+        Err(_) => return 0,
+    };
+    if waypoints.len() > capacity {
+        return pack_result(capacity, waypoints.len());
+    }
+    let written: Vec<jint> = waypoints.iter().map(|&v| v as jint).collect();
+    if env.set_int_array_region(out, 0, &written).is_err() {
+        return 0;
+    }
+    return pack_result(capacity, waypoints.len());
+}
+
+/**
+ * Copies `waypoints` into a direct buffer in native byte order, or nothing if they don't all fit,
+ * see [pack_result] for the returned value.
+ */
+unsafe fn write_buffer(env: &mut JNIEnv, out: &JByteBuffer, waypoints: &[u32]) -> jlong {
+    if out.is_null() {
+        env.throw_new(ILLEGAL_ARGUMENT, "Waypoint buffer must not be null");
+        return 0;
+    }
+    let address: *mut u8 = match env.get_direct_buffer_address(out) {
+        Ok(address) => address,
+        Err(_) => {
+            env.throw_new(ILLEGAL_ARGUMENT, "Waypoint buffer must be direct");
+            return 0;
+        }
+    };
+    if address.align_offset(4) != 0 {
+        env.throw_new(ILLEGAL_ARGUMENT, "Waypoint buffer must be aligned to 4 bytes");
+        return 0;
+    }
+    let capacity: usize = env.get_direct_buffer_capacity(out).unwrap_or(0) / 4;
+    if waypoints.len() <= capacity {
+        std::ptr::copy_nonoverlapping(waypoints.as_ptr(), address as *mut u32, waypoints.len());
+    }
+    return pack_result(capacity, waypoints.len());
+}
+
+#[inline(always)]
+unsafe fn as_world<'a>(world: jlong) -> &'a RwLock<CollisionFlagMap> {
+    if world == 0 {
+        return &COLLISION_FLAGS;
+    }
+    return &*(world as *const RwLock<CollisionFlagMap>);
+}
+
+#[inline(always)]
+unsafe fn as_pathfinder<'a>(pathfinder: jlong) -> &'a Mutex<PathFinder> {
+    if pathfinder == 0 {
+        return &PATHFINDER;
+    }
+    return &*(pathfinder as *const Mutex<PathFinder>);
+}
+
+/**
+ * Locks a world for reading. A panic under the lock was already thrown as an `IllegalStateException`
+ * by [guard], and writes check every tile before changing any, so a poisoned lock is taken over
+ * rather than failing every call after it.
+ */
+#[inline(always)]
+unsafe fn read_world<'a>(world: jlong) -> RwLockReadGuard<'a, CollisionFlagMap> {
+    return as_world(world).read().unwrap_or_else(PoisonError::into_inner);
+}
+
+/**
+ * Locks a world for writing, see [read_world] for poisoned locks.
+ */
+#[inline(always)]
+unsafe fn write_world<'a>(world: jlong) -> RwLockWriteGuard<'a, CollisionFlagMap> {
+    return as_world(world).write().unwrap_or_else(PoisonError::into_inner);
+}
+
+/**
+ * Locks a pathfinder, which resets its scratch space on every search so a poisoned lock is taken over.
+ */
+#[inline(always)]
+unsafe fn lock_pathfinder<'a>(pathfinder: jlong) -> MutexGuard<'a, PathFinder> {
+    return as_pathfinder(pathfinder).lock().unwrap_or_else(PoisonError::into_inner);
+}
+
+#[inline(always)]
+fn loc_types() -> MutexGuard<'static, LocTypeRegistry> {
+    return LOC_TYPES.lock().unwrap_or_else(PoisonError::into_inner);
+}
+
+#[no_mangle]
+pub unsafe extern "system" fn Java_rsmod_PathFinder_findPath(
+    env: JNIEnv,
+    _class: JClass,
+    y: jint,
+    srcX: jint,
+    srcZ: jint,
+    destX: jint,
+    destZ: jint,
+    srcSize: jint,
+    destWidth: jint,
+    destHeight: jint,
+    angle: jint,
+    shape: jint,
+    moveNear: jboolean,
+    blockAccessFlags: jint,
+    maxWaypoints: jint,
+    collision: jint,
+) -> jintArray {
+    Java_rsmod_World_findPath(env, _class, 0, 0, y, srcX, srcZ, destX, destZ, srcSize, destWidth, destHeight, angle, shape, moveNear, blockAccessFlags, maxWaypoints, collision)
+}
+
+#[no_mangle]
+pub unsafe extern "system" fn Java_rsmod_PathFinder_findNaivePath(
+    env: JNIEnv,
+    _class: JClass,
+    y: jint,
+    srcX: jint,
+    srcZ: jint,
+    destX: jint,
+    destZ: jint,
+    srcWidth: jint,
+    srcHeight: jint,
+    destWidth: jint,
+    destHeight: jint,
+    extraFlag: jint,
+    collision: jint,
+) -> jintArray {
+    Java_rsmod_World_findNaivePath(env, _class, 0, y, srcX, srcZ, destX, destZ, srcWidth, srcHeight, destWidth, destHeight, extraFlag, collision)
+}
+
+#[no_mangle]
+pub unsafe extern "system" fn Java_rsmod_PathFinder_findPathInto(
+    env: JNIEnv,
+    _class: JClass,
+    y: jint,
+    srcX: jint,
+    srcZ: jint,
+    destX: jint,
+    destZ: jint,
+    srcSize: jint,
+    destWidth: jint,
+    destHeight: jint,
+    angle: jint,
+    shape: jint,
+    moveNear: jboolean,
+    blockAccessFlags: jint,
+    maxWaypoints: jint,
+    collision: jint,
+    out: JIntArray,
+) -> jlong {
+    Java_rsmod_World_findPathInto(env, _class, 0, 0, y, srcX, srcZ, destX, destZ, srcSize, destWidth, destHeight, angle, shape, moveNear, blockAccessFlags, maxWaypoints, collision, out)
+}
+
+#[no_mangle]
+pub unsafe extern "system" fn Java_rsmod_PathFinder_findPathIntoBuffer(
+    env: JNIEnv,
+    _class: JClass,
+    y: jint,
+    srcX: jint,
+    srcZ: jint,
+    destX: jint,
+    destZ: jint,
+    srcSize: jint,
+    destWidth: jint,
+    destHeight: jint,
+    angle: jint,
+    shape: jint,
+    moveNear: jboolean,
+    blockAccessFlags: jint,
+    maxWaypoints: jint,
+    collision: jint,
+    out: JByteBuffer,
+) -> jlong {
+    Java_rsmod_World_findPathIntoBuffer(env, _class, 0, 0, y, srcX, srcZ, destX, destZ, srcSize, destWidth, destHeight, angle, shape, moveNear, blockAccessFlags, maxWaypoints, collision, out)
+}
+
+#[no_mangle]
+pub unsafe extern "system" fn Java_rsmod_PathFinder_findNaivePathSeeded(
+    env: JNIEnv,
+    _class: JClass,
+    y: jint,
+    srcX: jint,
+    srcZ: jint,
+    destX: jint,
+    destZ: jint,
+    srcWidth: jint,
+    srcHeight: jint,
+    destWidth: jint,
+    destHeight: jint,
+    extraFlag: jint,
+    collision: jint,
+    seed: jlong,
+) -> jintArray {
+    Java_rsmod_World_findNaivePathSeeded(env, _class, 0, y, srcX, srcZ, destX, destZ, srcWidth, srcHeight, destWidth, destHeight, extraFlag, collision, seed)
+}
+
+#[no_mangle]
+pub unsafe extern "system" fn Java_rsmod_PathFinder_findNaivePathInto(
+    env: JNIEnv,
+    _class: JClass,
+    y: jint,
+    srcX: jint,
+    srcZ: jint,
+    destX: jint,
+    destZ: jint,
+    srcWidth: jint,
+    srcHeight: jint,
+    destWidth: jint,
+    destHeight: jint,
+    extraFlag: jint,
+    collision: jint,
+    out: JIntArray,
+) -> jlong {
+    Java_rsmod_World_findNaivePathInto(env, _class, 0, y, srcX, srcZ, destX, destZ, srcWidth, srcHeight, destWidth, destHeight, extraFlag, collision, out)
+}
+
+#[no_mangle]
+pub unsafe extern "system" fn Java_rsmod_PathFinder_changeFloor(
+    env: JNIEnv,
+    _class: JClass,
+    x: jint,
+    z: jint,
+    y: jint,
+    add: jboolean,
+) {
+    Java_rsmod_World_changeFloor(env, _class, 0, x, z, y, add)
+}
+
+#[no_mangle]
+pub unsafe extern "system" fn Java_rsmod_PathFinder_changeLoc(
+    env: JNIEnv,
+    _class: JClass,
+    x: jint,
+    z: jint,
+    y: jint,
+    width: jint,
+    length: jint,
+    blockrange: jboolean,
+    breakroutefinding: jboolean,
+    add: jboolean,
+) {
+    Java_rsmod_World_changeLoc(env, _class, 0, x, z, y, width, length, blockrange, breakroutefinding, add)
+}
+
+#[no_mangle]
+pub unsafe extern "system" fn Java_rsmod_PathFinder_changeLocShape(
+    env: JNIEnv,
+    _class: JClass,
+    x: jint,
+    z: jint,
+    y: jint,
+    width: jint,
+    length: jint,
+    angle: jint,
+    shape: jint,
+    blockrange: jboolean,
+    breakroutefinding: jboolean,
+    add: jboolean,
+) {
+    Java_rsmod_World_changeLocShape(env, _class, 0, x, z, y, width, length, angle, shape, blockrange, breakroutefinding, add)
+}
+
+#[no_mangle]
+pub unsafe extern "system" fn Java_rsmod_PathFinder_registerLoc(
+    mut env: JNIEnv,
+    _class: JClass,
+    id: jint,
+    width: jint,
+    length: jint,
+    blockwalk: jboolean,
+    blockrange: jboolean,
+    breakroutefinding: jboolean,
+    forceapproach: jint,
+    active: jboolean,
+) {
+    guard(&mut env, (), |env| {
+        // shapes are only checked when a loc is added or removed, see [loc_shape].
+        if !check_range(env, "id", id, 0, LocTypeRegistry::ID_LIMIT as jint - 1)
+            || !check_sizes(env, &[("width", width), ("length", length)])
+            || !check_range(env, "forceapproach", forceapproach, 0, 255) {
+            return;
+        }
+        loc_types().register(id as u32, LocType::new(
+            width as u8,
+            length as u8,
+            blockwalk != 0,
+            blockrange != 0,
+            breakroutefinding != 0,
+            forceapproach as u8,
+            active != 0,
+        ));
+    })
+}
+
+#[no_mangle]
+pub unsafe extern "system" fn Java_rsmod_PathFinder_addLoc(
+    env: JNIEnv,
+    _class: JClass,
+    id: jint,
+    x: jint,
+    z: jint,
+    y: jint,
+    shape: jint,
+    angle: jint,
+) -> jboolean {
+    Java_rsmod_World_addLoc(env, _class, 0, id, x, z, y, shape, angle)
+}
+
+#[no_mangle]
+pub unsafe extern "system" fn Java_rsmod_PathFinder_removeLoc(
+    env: JNIEnv,
+    _class: JClass,
+    id: jint,
+    x: jint,
+    z: jint,
+    y: jint,
+    shape: jint,
+    angle: jint,
+) -> jboolean {
+    Java_rsmod_World_removeLoc(env, _class, 0, id, x, z, y, shape, angle)
+}
+
+#[no_mangle]
+pub unsafe extern "system" fn Java_rsmod_PathFinder_locBlockAccessFlags(
+    mut env: JNIEnv,
+    _class: JClass,
+    id: jint,
+) -> jint {
+    guard(&mut env, 0, |_| {
+        match loc_types().get(id as u32) {
+            Some(loc) => loc.block_access_flags() as jint,
+            None => 0,
+        }
+    })
+}
+
+unsafe fn change_registered_loc(
+    env: &mut JNIEnv,
+    world: jlong,
+    id: jint,
+    x: jint,
+    z: jint,
+    y: jint,
+    shape: jint,
+    angle: jint,
+    add: bool,
+) -> jboolean {
+    let shape: LocShape = match loc_shape(env, shape) {
+        Some(shape) => shape,
+        None => return 0,
+    };
+    let types = loc_types();
+    let mut flags = write_world(world);
+    let found: Result<bool, CoordOutOfRange> = if add {
+        types.add_loc(&mut flags, id as u32, x, z, y, shape, (angle & 0x3) as u8)
+    } else {
+        types.remove_loc(&mut flags, id as u32, x, z, y, shape, (angle & 0x3) as u8)
+    };
+    drop(flags);
+    drop(types);
+    if in_world(env, found) == Some(true) { 1 } else { 0 }
+}
+
+#[no_mangle]
+pub unsafe extern "system" fn Java_rsmod_PathFinder_changeNpc(
+    env: JNIEnv,
+    _class: JClass,
+    x: jint,
+    z: jint,
+    y: jint,
+    size: jint,
+    add: jboolean,
+) {
+    Java_rsmod_World_changeNpc(env, _class, 0, x, z, y, size, add)
+}
+
+#[no_mangle]
+pub unsafe extern "system" fn Java_rsmod_PathFinder_changePlayer(
+    env: JNIEnv,
+    _class: JClass,
+    x: jint,
+    z: jint,
+    y: jint,
+    size: jint,
+    add: jboolean,
+) {
+    Java_rsmod_World_changePlayer(env, _class, 0, x, z, y, size, add)
+}
+
+#[no_mangle]
+pub unsafe extern "system" fn Java_rsmod_PathFinder_changeRoof(
+    env: JNIEnv,
+    _class: JClass,
+    x: jint,
+    z: jint,
+    y: jint,
+    add: jboolean,
+) {
+    Java_rsmod_World_changeRoof(env, _class, 0, x, z, y, add)
+}
+
+#[no_mangle]
+pub unsafe extern "system" fn Java_rsmod_PathFinder_changeWall(
+    env: JNIEnv,
+    _class: JClass,
+    x: jint,
+    z: jint,
+    y: jint,
+    angle: jint,
+    shape: jint,
+    blockrange: jboolean,
+    breakroutefinding: jboolean,
+    add: jboolean,
+) {
+    Java_rsmod_World_changeWall(env, _class, 0, x, z, y, angle, shape, blockrange, breakroutefinding, add)
+}
+
+#[no_mangle]
+pub unsafe extern "system" fn Java_rsmod_PathFinder_changeWallStraight(
+    env: JNIEnv,
+    _class: JClass,
+    x: jint,
+    z: jint,
+    y: jint,
+    angle: jint,
+    blockrange: jboolean,
+    breakroutefinding: jboolean,
+    add: jboolean,
+) {
+    Java_rsmod_World_changeWallStraight(env, _class, 0, x, z, y, angle, blockrange, breakroutefinding, add)
+}
+
+#[no_mangle]
+pub unsafe extern "system" fn Java_rsmod_PathFinder_changeWallCorner(
+    env: JNIEnv,
+    _class: JClass,
+    x: jint,
+    z: jint,
+    y: jint,
+    angle: jint,
+    blockrange: jboolean,
+    breakroutefinding: jboolean,
+    add: jboolean,
+) {
+    Java_rsmod_World_changeWallCorner(env, _class, 0, x, z, y, angle, blockrange, breakroutefinding, add)
+}
+
+#[no_mangle]
+pub unsafe extern "system" fn Java_rsmod_PathFinder_changeWallL(
+    env: JNIEnv,
+    _class: JClass,
+    x: jint,
+    z: jint,
+    y: jint,
+    angle: jint,
+    blockrange: jboolean,
+    breakroutefinding: jboolean,
+    add: jboolean,
+) {
+    Java_rsmod_World_changeWallL(env, _class, 0, x, z, y, angle, blockrange, breakroutefinding, add)
+}
+
+#[no_mangle]
+pub unsafe extern "system" fn Java_rsmod_PathFinder_toggleDoor(
+    env: JNIEnv,
+    _class: JClass,
+    x: jint,
+    z: jint,
+    y: jint,
+    closedShape: jint,
+    closedAngle: jint,
+    openShape: jint,
+    openAngle: jint,
+    openOffsetX: jint,
+    openOffsetZ: jint,
+    blockrange: jboolean,
+    breakroutefinding: jboolean,
+    open: jboolean,
+) -> jintArray {
+    Java_rsmod_World_toggleDoor(env, _class, 0, x, z, y, closedShape, closedAngle, openShape, openAngle, openOffsetX, openOffsetZ, blockrange, breakroutefinding, open)
+}
+
+#[no_mangle]
+pub unsafe extern "system" fn Java_rsmod_PathFinder_setZone(
+    env: JNIEnv,
+    _class: JClass,
+    x: jint,
+    z: jint,
+    y: jint,
+    flags: JIntArray,
+) {
+    Java_rsmod_World_setZone(env, _class, 0, x, z, y, flags)
+}
+
+/**
+ * Applies a flat list of `x, z, y, mask, op` edits under one lock.
+ * `op` is 0 to set, 1 to add and 2 to remove the mask.
+ */
+#[no_mangle]
+pub unsafe extern "system" fn Java_rsmod_PathFinder_applyEdits(
+    env: JNIEnv,
+    _class: JClass,
+    edits: JIntArray,
+) {
+    Java_rsmod_World_applyEdits(env, _class, 0, edits)
+}
+
+/**
+ * Loads a mapsquare straight out of a direct buffer in native byte order,
+ * laid out as described by [CollisionFlagMap::load_mapsquare].
+ */
+#[no_mangle]
+pub unsafe extern "system" fn Java_rsmod_PathFinder_loadMapsquare(
+    env: JNIEnv,
+    _class: JClass,
+    mapsquareX: jint,
+    mapsquareZ: jint,
+    buffer: JByteBuffer,
+) {
+    Java_rsmod_World_loadMapsquare(env, _class, 0, mapsquareX, mapsquareZ, buffer)
+}
+
+#[no_mangle]
+pub unsafe extern "system" fn Java_rsmod_PathFinder_allocateIfAbsent(
+    env: JNIEnv,
+    _class: JClass,
+    x: jint,
+    z: jint,
+    y: jint,
+) {
+    Java_rsmod_World_allocateIfAbsent(env, _class, 0, x, z, y)
+}
+
+#[no_mangle]
+pub unsafe extern "system" fn Java_rsmod_PathFinder_deallocateIfPresent(
+    env: JNIEnv,
+    _class: JClass,
+    x: jint,
+    z: jint,
+    y: jint,
+) {
+    Java_rsmod_World_deallocateIfPresent(env, _class, 0, x, z, y)
+}
+
+#[no_mangle]
+pub unsafe extern "system" fn Java_rsmod_PathFinder_isZoneAllocated(
+    env: JNIEnv,
+    _class: JClass,
+    x: jint,
+    z: jint,
+    y: jint,
+) -> jboolean {
+    Java_rsmod_World_isZoneAllocated(env, _class, 0, x, z, y)
+}
+
+#[no_mangle]
+pub unsafe extern "system" fn Java_rsmod_PathFinder_enableJournal(
+    env: JNIEnv,
+    _class: JClass,
+    recordTiles: jboolean,
+) {
+    Java_rsmod_World_enableJournal(env, _class, 0, recordTiles)
+}
+
+#[no_mangle]
+pub unsafe extern "system" fn Java_rsmod_PathFinder_disableJournal(
+    env: JNIEnv,
+    _class: JClass,
+) {
+    Java_rsmod_World_disableJournal(env, _class, 0)
+}
+
+#[no_mangle]
+pub unsafe extern "system" fn Java_rsmod_PathFinder_zoneVersion(
+    env: JNIEnv,
+    _class: JClass,
+    x: jint,
+    z: jint,
+    y: jint,
+) -> jint {
+    Java_rsmod_World_zoneVersion(env, _class, 0, x, z, y)
+}
+
+#[no_mangle]
+pub unsafe extern "system" fn Java_rsmod_PathFinder_drainChangedZones(
+    env: JNIEnv,
+    _class: JClass,
+) -> jintArray {
+    Java_rsmod_World_drainChangedZones(env, _class, 0)
+}
+
+#[no_mangle]
+pub unsafe extern "system" fn Java_rsmod_PathFinder_isFlagged(
+    env: JNIEnv,
+    _class: JClass,
+    x: jint,
+    z: jint,
+    y: jint,
+    masks: jint,
+) -> jboolean {
+    Java_rsmod_World_isFlagged(env, _class, 0, x, z, y, masks)
+}
+
+#[no_mangle]
+pub unsafe extern "system" fn Java_rsmod_PathFinder_canTravel(
+    env: JNIEnv,
+    _class: JClass,
+    y: jint,
+    x: jint,
+    z: jint,
+    offsetX: jint,
+    offsetZ: jint,
+    size: jint,
+    extraFlag: jint,
+    collision: jint,
+) -> jboolean {
+    Java_rsmod_World_canTravel(env, _class, 0, y, x, z, offsetX, offsetZ, size, extraFlag, collision)
+}
+
+#[no_mangle]
+pub unsafe extern "system" fn Java_rsmod_PathFinder_hasLineOfSight(
+    env: JNIEnv,
+    _class: JClass,
+    y: jint,
+    srcX: jint,
+    srcZ: jint,
+    destX: jint,
+    destZ: jint,
+    srcWidth: jint,
+    srcHeight: jint,
+    destWidth: jint,
+    destHeight: jint,
+    extraFlag: jint,
+) -> jboolean {
+    Java_rsmod_World_hasLineOfSight(env, _class, 0, y, srcX, srcZ, destX, destZ, srcWidth, srcHeight, destWidth, destHeight, extraFlag)
+}
+
+#[no_mangle]
+pub unsafe extern "system" fn Java_rsmod_PathFinder_hasLineOfWalk(
+    env: JNIEnv,
+    _class: JClass,
+    y: jint,
+    srcX: jint,
+    srcZ: jint,
+    destX: jint,
+    destZ: jint,
+    srcWidth: jint,
+    srcHeight: jint,
+    destWidth: jint,
+    destHeight: jint,
+    extraFlag: jint,
+) -> jboolean {
+    Java_rsmod_World_hasLineOfWalk(env, _class, 0, y, srcX, srcZ, destX, destZ, srcWidth, srcHeight, destWidth, destHeight, extraFlag)
+}
+
+#[no_mangle]
+pub unsafe extern "system" fn Java_rsmod_PathFinder_lineOfSight(
+    env: JNIEnv,
+    _class: JClass,
+    y: jint,
+    srcX: jint,
+    srcZ: jint,
+    destX: jint,
+    destZ: jint,
+    srcWidth: jint,
+    srcHeight: jint,
+    destWidth: jint,
+    destHeight: jint,
+    extraFlag: jint,
+) -> jintArray {
+    Java_rsmod_World_lineOfSight(env, _class, 0, y, srcX, srcZ, destX, destZ, srcWidth, srcHeight, destWidth, destHeight, extraFlag)
+}
+
+#[no_mangle]
+pub unsafe extern "system" fn Java_rsmod_PathFinder_lineOfWalk(
+    env: JNIEnv,
+    _class: JClass,
+    y: jint,
+    srcX: jint,
+    srcZ: jint,
+    destX: jint,
+    destZ: jint,
+    srcWidth: jint,
+    srcHeight: jint,
+    destWidth: jint,
+    destHeight: jint,
+    extraFlag: jint,
+) -> jintArray {
+    Java_rsmod_World_lineOfWalk(env, _class, 0, y, srcX, srcZ, destX, destZ, srcWidth, srcHeight, destWidth, destHeight, extraFlag)
+}
+
+#[no_mangle]
+pub unsafe extern "system" fn Java_rsmod_PathFinder_lineOfSightInto(
+    env: JNIEnv,
+    _class: JClass,
+    y: jint,
+    srcX: jint,
+    srcZ: jint,
+    destX: jint,
+    destZ: jint,
+    srcWidth: jint,
+    srcHeight: jint,
+    destWidth: jint,
+    destHeight: jint,
+    extraFlag: jint,
+    out: JIntArray,
+) -> jlong {
+    Java_rsmod_World_lineOfSightInto(env, _class, 0, y, srcX, srcZ, destX, destZ, srcWidth, srcHeight, destWidth, destHeight, extraFlag, out)
+}
+
+#[no_mangle]
+pub unsafe extern "system" fn Java_rsmod_PathFinder_lineOfWalkInto(
+    env: JNIEnv,
+    _class: JClass,
+    y: jint,
+    srcX: jint,
+    srcZ: jint,
+    destX: jint,
+    destZ: jint,
+    srcWidth: jint,
+    srcHeight: jint,
+    destWidth: jint,
+    destHeight: jint,
+    extraFlag: jint,
+    out: JIntArray,
+) -> jlong {
+    Java_rsmod_World_lineOfWalkInto(env, _class, 0, y, srcX, srcZ, destX, destZ, srcWidth, srcHeight, destWidth, destHeight, extraFlag, out)
+}
+
+#[no_mangle]
+pub unsafe extern "system" fn Java_rsmod_PathFinder_lineOfSightResult(
+    env: JNIEnv,
+    _class: JClass,
+    y: jint,
+    srcX: jint,
+    srcZ: jint,
+    destX: jint,
+    destZ: jint,
+    srcWidth: jint,
+    srcHeight: jint,
+    destWidth: jint,
+    destHeight: jint,
+    extraFlag: jint,
+) -> jintArray {
+    Java_rsmod_World_lineOfSightResult(env, _class, 0, y, srcX, srcZ, destX, destZ, srcWidth, srcHeight, destWidth, destHeight, extraFlag)
+}
+
+#[no_mangle]
+pub unsafe extern "system" fn Java_rsmod_PathFinder_lineOfWalkResult(
+    env: JNIEnv,
+    _class: JClass,
+    y: jint,
+    srcX: jint,
+    srcZ: jint,
+    destX: jint,
+    destZ: jint,
+    srcWidth: jint,
+    srcHeight: jint,
+    destWidth: jint,
+    destHeight: jint,
+    extraFlag: jint,
+) -> jintArray {
+    Java_rsmod_World_lineOfWalkResult(env, _class, 0, y, srcX, srcZ, destX, destZ, srcWidth, srcHeight, destWidth, destHeight, extraFlag)
+}
+
+#[no_mangle]
+pub unsafe extern "system" fn Java_rsmod_PathFinder_reached(
+    env: JNIEnv,
+    _class: JClass,
+    y: jint,
+    srcX: jint,
+    srcZ: jint,
+    destX: jint,
+    destZ: jint,
+    destWidth: jint,
+    destHeight: jint,
+    srcSize: jint,
+    angle: jint,
+    shape: jint,
+    blockAccessFlags: jint,
+) -> jboolean {
+    Java_rsmod_World_reached(env, _class, 0, y, srcX, srcZ, destX, destZ, destWidth, destHeight, srcSize, angle, shape, blockAccessFlags)
+}
+
+#[no_mangle]
+pub unsafe extern "system" fn Java_rsmod_PathFinder_locShapeLayer(
+    mut env: JNIEnv,
+    _class: JClass,
+    shape: jint
+) -> jint {
+    guard(&mut env, -1, |env| {
+        match loc_shape(env, shape) {
+            Some(shape) => shape.layer() as jint,
+            None => -1,
+        }
+    })
+}
+
+// this is only to test benchmarking lumbridge.
+#[no_mangle]
+pub unsafe extern "system" fn Java_rsmod_PathFinder___set(
+    env: JNIEnv,
+    _class: JClass,
+    x: jint,
+    z: jint,
+    y: jint,
+    mask: jint,
+) {
+    Java_rsmod_World___set(env, _class, 0, x, z, y, mask)
+}
+
+/**
+ * Creates an empty world, returning the handle to pass into the `World` functions.
+ */
+#[no_mangle]
+pub unsafe extern "system" fn Java_rsmod_World_create(
+    mut env: JNIEnv,
+    _class: JClass,
+) -> jlong {
+    guard(&mut env, 0, |_| {
+        Box::into_raw(Box::new(RwLock::new(CollisionFlagMap::new()))) as jlong
+    })
+}
+
+/**
+ * Frees a world made by `create`. The handle must not be used afterwards.
+ */
+#[no_mangle]
+pub unsafe extern "system" fn Java_rsmod_World_destroy(
+    mut env: JNIEnv,
+    _class: JClass,
+    world: jlong,
+) {
+    guard(&mut env, (), |_| {
+        if world != 0 {
+            drop(Box::from_raw(world as *mut RwLock<CollisionFlagMap>));
+        }
+    })
+}
+
+/**
+ * Creates pathfinder scratch space, ideally one per thread, usable with any world.
+ */
+#[no_mangle]
+pub unsafe extern "system" fn Java_rsmod_World_createPathFinder(
+    mut env: JNIEnv,
+    _class: JClass,
+) -> jlong {
+    guard(&mut env, 0, |_| {
+        Box::into_raw(Box::new(Mutex::new(PathFinder::new()))) as jlong
+    })
+}
+
+/**
+ * Frees a pathfinder made by `createPathFinder`. The handle must not be used afterwards.
+ */
+#[no_mangle]
+pub unsafe extern "system" fn Java_rsmod_World_destroyPathFinder(
+    mut env: JNIEnv,
+    _class: JClass,
+    pathfinder: jlong,
+) {
+    guard(&mut env, (), |_| {
+        if pathfinder != 0 {
+            drop(Box::from_raw(pathfinder as *mut Mutex<PathFinder>));
+        }
+    })
+}
+
+#[no_mangle]
+pub unsafe extern "system" fn Java_rsmod_World_findPath(
+    mut env: JNIEnv,
+    _class: JClass,
+    world: jlong,
+    pathfinder: jlong,
+    y: jint,
+    srcX: jint,
+    srcZ: jint,
+    destX: jint,
+    destZ: jint,
+    srcSize: jint,
+    destWidth: jint,
+    destHeight: jint,
+    angle: jint,
+    shape: jint,
+    moveNear: jboolean,
+    blockAccessFlags: jint,
+    maxWaypoints: jint,
+    collision: jint,
+) -> jintArray {
+    guard(&mut env, std::ptr::null_mut(), |env| packed_to_jint_array(env, lock_pathfinder(pathfinder).find_path(
+        &*read_world(world),
+        y,
+        srcX,
+        srcZ,
+        destX,
+        destZ,
+        srcSize as u8,
+        destWidth as u8,
+        destHeight as u8,
+        angle as u8,
+        shape as i8,
+        moveNear != 0,
+        blockAccessFlags as u8,
+        maxWaypoints as u8,
+        &get_collision_strategy(match collision {
+            0 => CollisionType::NORMAL,
+            1 => CollisionType::BLOCKED,
+            2 => CollisionType::INDOORS,
+            3 => CollisionType::OUTDOORS,
+            4 => CollisionType::LINE_OF_SIGHT,
+            _ => CollisionType::NORMAL,
+        }),
+    )))
+}
+
+/**
+ * Validating variant of [Java_rsmod_World_findPath] shared by the `*Into` functions,
+ * returns None with an `IllegalArgumentException` pending for bad arguments or waypoints outside of the world.
+ */
+unsafe fn checked_find_path(
+    env: &mut JNIEnv,
+    world: jlong,
+    pathfinder: jlong,
+    y: jint,
+    srcX: jint,
+    srcZ: jint,
+    destX: jint,
+    destZ: jint,
+    srcSize: jint,
+    destWidth: jint,
+    destHeight: jint,
+    angle: jint,
+    shape: jint,
+    moveNear: jboolean,
+    blockAccessFlags: jint,
+    maxWaypoints: jint,
+    collision: jint,
+) -> Option<Vec<u32>> {
+    if !check_sizes(env, &[("srcSize", srcSize), ("destWidth", destWidth), ("destHeight", destHeight)])
+        || !check_range(env, "maxWaypoints", maxWaypoints, 0, 255)
+        || !check_range(env, "angle", angle, 0, 3) {
+        return None;
+    }
+    // -1 is no loc and -2 an exclusive rectangle, anything else has to be a loc shape.
+    if shape != -1 && shape != -2 {
+        loc_shape(env, shape)?;
+    }
+    let collision: CollisionType = collision_type(env, collision)?;
+    let waypoints: Result<Vec<u32>, CoordOutOfRange> = lock_pathfinder(pathfinder).find_path(
+        &*read_world(world),
+        y,
+        srcX,
+        srcZ,
+        destX,
+        destZ,
+        srcSize as u8,
+        destWidth as u8,
+        destHeight as u8,
+        angle as u8,
+        shape as i8,
+        moveNear != 0,
+        blockAccessFlags as u8,
+        maxWaypoints as u8,
+        &get_collision_strategy(collision),
+    );
+    return in_world(env, waypoints);
+}
+
+/**
+ * Finds a path into `out` instead of a new array.
+ * Returns `(status << 32) | waypoints`, when the status is 3 (buffer too small) nothing was
+ * written and the count is how many `out` needs, like the C ABI.
+ */
+#[no_mangle]
+pub unsafe extern "system" fn Java_rsmod_World_findPathInto(
+    mut env: JNIEnv,
+    _class: JClass,
+    world: jlong,
+    pathfinder: jlong,
+    y: jint,
+    srcX: jint,
+    srcZ: jint,
+    destX: jint,
+    destZ: jint,
+    srcSize: jint,
+    destWidth: jint,
+    destHeight: jint,
+    angle: jint,
+    shape: jint,
+    moveNear: jboolean,
+    blockAccessFlags: jint,
+    maxWaypoints: jint,
+    collision: jint,
+    out: JIntArray,
+) -> jlong {
+    guard(&mut env, 0, |env| {
+        return match checked_find_path(env, world, pathfinder, y, srcX, srcZ, destX, destZ, srcSize, destWidth, destHeight, angle, shape, moveNear, blockAccessFlags, maxWaypoints, collision) {
+            Some(waypoints) => write_array(env, &out, &waypoints),
+            None => 0,
+        };
+    })
+}
+
+/**
+ * [Java_rsmod_World_findPathInto] writing into a direct buffer in native byte order.
+ */
+#[no_mangle]
+pub unsafe extern "system" fn Java_rsmod_World_findPathIntoBuffer(
+    mut env: JNIEnv,
+    _class: JClass,
+    world: jlong,
+    pathfinder: jlong,
+    y: jint,
+    srcX: jint,
+    srcZ: jint,
+    destX: jint,
+    destZ: jint,
+    srcSize: jint,
+    destWidth: jint,
+    destHeight: jint,
+    angle: jint,
+    shape: jint,
+    moveNear: jboolean,
+    blockAccessFlags: jint,
+    maxWaypoints: jint,
+    collision: jint,
+    out: JByteBuffer,
+) -> jlong {
+    guard(&mut env, 0, |env| {
+        return match checked_find_path(env, world, pathfinder, y, srcX, srcZ, destX, destZ, srcSize, destWidth, destHeight, angle, shape, moveNear, blockAccessFlags, maxWaypoints, collision) {
+            Some(waypoints) => write_buffer(env, &out, &waypoints),
+            None => 0,
+        };
+    })
+}
+
+#[no_mangle]
+pub unsafe extern "system" fn Java_rsmod_World_findNaivePath(
+    mut env: JNIEnv,
+    _class: JClass,
+    world: jlong,
+    y: jint,
+    srcX: jint,
+    srcZ: jint,
+    destX: jint,
+    destZ: jint,
+    srcWidth: jint,
+    srcHeight: jint,
+    destWidth: jint,
+    destHeight: jint,
+    extraFlag: jint,
+    collision: jint,
+) -> jintArray {
+    guard(&mut env, std::ptr::null_mut(), |env| {
+        let flags = read_world(world);
+        packed_to_jint_array(env, find_naive_path(
+            &*flags,
+            y,
+            srcX,
+            srcZ,
+            destX,
+            destZ,
+            srcWidth as u8,
+            srcHeight as u8,
+            destWidth as u8,
+            destHeight as u8,
+            extraFlag as u32,
+            &get_collision_strategy(match collision {
+                0 => CollisionType::NORMAL,
+                1 => CollisionType::BLOCKED,
+                2 => CollisionType::INDOORS,
+                3 => CollisionType::OUTDOORS,
+                4 => CollisionType::LINE_OF_SIGHT,
+                _ => CollisionType::NORMAL,
+            }),
+        ))
+    })
+}
+
+/**
+ * [Java_rsmod_World_findNaivePath] stepping out from under the target with a [SeededRandom],
+ * so the same seed always gives the same step.
+ */
+#[no_mangle]
+pub unsafe extern "system" fn Java_rsmod_World_findNaivePathSeeded(
+    mut env: JNIEnv,
+    _class: JClass,
+    world: jlong,
+    y: jint,
+    srcX: jint,
+    srcZ: jint,
+    destX: jint,
+    destZ: jint,
+    srcWidth: jint,
+    srcHeight: jint,
+    destWidth: jint,
+    destHeight: jint,
+    extraFlag: jint,
+    collision: jint,
+    seed: jlong,
+) -> jintArray {
+    guard(&mut env, std::ptr::null_mut(), |env| {
+        let collision: CollisionType = match collision_type(env, collision) {
+            Some(collision) => collision,
+            None => return std::ptr::null_mut(),
+        };
+        packed_to_jint_array(env, find_naive_path_with(
+            &*read_world(world),
+            y,
+            srcX,
+            srcZ,
+            destX,
+            destZ,
+            srcWidth as u8,
+            srcHeight as u8,
+            destWidth as u8,
+            destHeight as u8,
+            extraFlag as u32,
+            &get_collision_strategy(collision),
+            &mut SeededRandom::new(seed as u64),
+        ))
+    })
+}
+
+/**
+ * [Java_rsmod_World_findNaivePath] into `out`, returning the same as [Java_rsmod_World_findPathInto].
+ */
+#[no_mangle]
+pub unsafe extern "system" fn Java_rsmod_World_findNaivePathInto(
+    mut env: JNIEnv,
+    _class: JClass,
+    world: jlong,
+    y: jint,
+    srcX: jint,
+    srcZ: jint,
+    destX: jint,
+    destZ: jint,
+    srcWidth: jint,
+    srcHeight: jint,
+    destWidth: jint,
+    destHeight: jint,
+    extraFlag: jint,
+    collision: jint,
+    out: JIntArray,
+) -> jlong {
+    guard(&mut env, 0, |env| {
+        if !check_sizes(env, &[("srcWidth", srcWidth), ("srcHeight", srcHeight), ("destWidth", destWidth), ("destHeight", destHeight)]) {
+            return 0;
+        }
+        let collision: CollisionType = match collision_type(env, collision) {
+            Some(collision) => collision,
+            None => return 0,
+        };
+        let waypoints: Vec<u32> = match in_world(env, find_naive_path(
+            &*read_world(world),
+            y,
+            srcX,
+            srcZ,
+            destX,
+            destZ,
+            srcWidth as u8,
+            srcHeight as u8,
+            destWidth as u8,
+            destHeight as u8,
+            extraFlag as u32,
+            &get_collision_strategy(collision),
+        )) {
+            Some(waypoints) => waypoints,
+            None => return 0,
+        };
+        return write_array(env, &out, &waypoints);
+    })
+}
+
+#[no_mangle]
+pub unsafe extern "system" fn Java_rsmod_World_changeFloor(
+    mut env: JNIEnv,
+    _class: JClass,
+    world: jlong,
+    x: jint,
+    z: jint,
+    y: jint,
+    add: jboolean,
+) {
+    guard(&mut env, (), |env| {
+        let changed: Result<(), CoordOutOfRange> = write_world(world).change_floor(x, z, y, add != 0);
+        in_world(env, changed);
+    })
+}
+
+#[no_mangle]
+pub unsafe extern "system" fn Java_rsmod_World_changeLoc(
+    mut env: JNIEnv,
+    _class: JClass,
+    world: jlong,
+    x: jint,
+    z: jint,
+    y: jint,
+    width: jint,
+    length: jint,
+    blockrange: jboolean,
+    breakroutefinding: jboolean,
+    add: jboolean,
+) {
+    guard(&mut env, (), |env| {
+        let changed: Result<(), CoordOutOfRange> = write_world(world).change_loc(x, z, y, width, length, blockrange != 0, breakroutefinding != 0, add != 0);
+        in_world(env, changed);
+    })
+}
+
+#[no_mangle]
+pub unsafe extern "system" fn Java_rsmod_World_changeLocShape(
+    mut env: JNIEnv,
+    _class: JClass,
+    world: jlong,
+    x: jint,
+    z: jint,
+    y: jint,
+    width: jint,
+    length: jint,
+    angle: jint,
+    shape: jint,
+    blockrange: jboolean,
+    breakroutefinding: jboolean,
+    add: jboolean,
+) {
+    guard(&mut env, (), |env| {
+        let shape: LocShape = match loc_shape(env, shape) {
+            Some(shape) => shape,
+            None => return,
+        };
+        let changed: Result<(), CoordOutOfRange> = write_world(world).change_loc_shape(
+            x,
+            z,
+            y,
+            width as u8,
+            length as u8,
+            (angle & 0x3) as u8,
+            shape,
+            blockrange != 0,
+            breakroutefinding != 0,
+            add != 0,
+        );
+        in_world(env, changed);
+    })
+}
+
+#[no_mangle]
+pub unsafe extern "system" fn Java_rsmod_World_addLoc(
+    mut env: JNIEnv,
+    _class: JClass,
+    world: jlong,
+    id: jint,
+    x: jint,
+    z: jint,
+    y: jint,
+    shape: jint,
+    angle: jint,
+) -> jboolean {
+    guard(&mut env, 0, |env| {
+        change_registered_loc(env, world, id, x, z, y, shape, angle, true)
+    })
+}
+
+#[no_mangle]
+pub unsafe extern "system" fn Java_rsmod_World_removeLoc(
+    mut env: JNIEnv,
+    _class: JClass,
+    world: jlong,
+    id: jint,
+    x: jint,
+    z: jint,
+    y: jint,
+    shape: jint,
+    angle: jint,
+) -> jboolean {
+    guard(&mut env, 0, |env| {
+        change_registered_loc(env, world, id, x, z, y, shape, angle, false)
+    })
+}
+
+#[no_mangle]
+pub unsafe extern "system" fn Java_rsmod_World_changeNpc(
+    mut env: JNIEnv,
+    _class: JClass,
+    world: jlong,
+    x: jint,
+    z: jint,
+    y: jint,
+    size: jint,
+    add: jboolean,
+) {
+    guard(&mut env, (), |env| {
+        let changed: Result<(), CoordOutOfRange> = write_world(world).change_npc(x, z, y, size, add != 0);
+        in_world(env, changed);
+    })
+}
+
+#[no_mangle]
+pub unsafe extern "system" fn Java_rsmod_World_changePlayer(
+    mut env: JNIEnv,
+    _class: JClass,
+    world: jlong,
+    x: jint,
+    z: jint,
+    y: jint,
+    size: jint,
+    add: jboolean,
+) {
+    guard(&mut env, (), |env| {
+        let changed: Result<(), CoordOutOfRange> = write_world(world).change_player(x, z, y, size, add != 0);
+        in_world(env, changed);
+    })
+}
+
+#[no_mangle]
+pub unsafe extern "system" fn Java_rsmod_World_changeRoof(
+    mut env: JNIEnv,
+    _class: JClass,
+    world: jlong,
+    x: jint,
+    z: jint,
+    y: jint,
+    add: jboolean,
+) {
+    guard(&mut env, (), |env| {
+        let changed: Result<(), CoordOutOfRange> = write_world(world).change_roof(x, z, y, add != 0);
+        in_world(env, changed);
+    })
+}
+
+/**
+ * Flags a wall of shape 0-3, throwing an `IllegalArgumentException` for any other shape like
+ * [CollisionFlagMap::change_wall], diagonal walls (9) included.
+ */
+#[no_mangle]
+pub unsafe extern "system" fn Java_rsmod_World_changeWall(
+    mut env: JNIEnv,
+    _class: JClass,
+    world: jlong,
+    x: jint,
+    z: jint,
+    y: jint,
+    angle: jint,
+    shape: jint,
+    blockrange: jboolean,
+    breakroutefinding: jboolean,
+    add: jboolean,
+) {
+    guard(&mut env, (), |env| {
+        let Some(shape) = loc_shape(env, shape) else { return };
+        let changed: Result<(), ChangeWallError> = write_world(world).change_wall(
+            x,
+            z,
+            y,
+            (angle & 0x3) as u8,
+            shape,
+            blockrange != 0,
+            breakroutefinding != 0,
+            add != 0,
+        );
+        if let Err(error) = changed {
+            env.throw_new(ILLEGAL_ARGUMENT, error.to_string());
+        }
+    })
+}
+
+#[no_mangle]
+pub unsafe extern "system" fn Java_rsmod_World_changeWallStraight(
+    mut env: JNIEnv,
+    _class: JClass,
+    world: jlong,
+    x: jint,
+    z: jint,
+    y: jint,
+    angle: jint,
+    blockrange: jboolean,
+    breakroutefinding: jboolean,
+    add: jboolean,
+) {
+    guard(&mut env, (), |env| {
+        if !check_range(env, "angle", angle, 0, 3) {
+            return;
+        }
+        let changed: Result<(), CoordOutOfRange> = write_world(world).change_wall_straight(x, z, y, angle as u8, blockrange != 0, breakroutefinding != 0, add != 0);
+        in_world(env, changed);
+    })
+}
+
+#[no_mangle]
+pub unsafe extern "system" fn Java_rsmod_World_changeWallCorner(
+    mut env: JNIEnv,
+    _class: JClass,
+    world: jlong,
+    x: jint,
+    z: jint,
+    y: jint,
+    angle: jint,
+    blockrange: jboolean,
+    breakroutefinding: jboolean,
+    add: jboolean,
+) {
+    guard(&mut env, (), |env| {
+        if !check_range(env, "angle", angle, 0, 3) {
+            return;
+        }
+        let changed: Result<(), CoordOutOfRange> = write_world(world).change_wall_corner(x, z, y, angle as u8, blockrange != 0, breakroutefinding != 0, add != 0);
+        in_world(env, changed);
+    })
+}
+
+#[no_mangle]
+pub unsafe extern "system" fn Java_rsmod_World_changeWallL(
+    mut env: JNIEnv,
+    _class: JClass,
+    world: jlong,
+    x: jint,
+    z: jint,
+    y: jint,
+    angle: jint,
+    blockrange: jboolean,
+    breakroutefinding: jboolean,
+    add: jboolean,
+) {
+    guard(&mut env, (), |env| {
+        if !check_range(env, "angle", angle, 0, 3) {
+            return;
+        }
+        let changed: Result<(), CoordOutOfRange> = write_world(world).change_wall_l(x, z, y, angle as u8, blockrange != 0, breakroutefinding != 0, add != 0);
+        in_world(env, changed);
+    })
+}
+
+#[no_mangle]
+pub unsafe extern "system" fn Java_rsmod_World_toggleDoor(
+    mut env: JNIEnv,
+    _class: JClass,
+    world: jlong,
+    x: jint,
+    z: jint,
+    y: jint,
+    closedShape: jint,
+    closedAngle: jint,
+    openShape: jint,
+    openAngle: jint,
+    openOffsetX: jint,
+    openOffsetZ: jint,
+    blockrange: jboolean,
+    breakroutefinding: jboolean,
+    open: jboolean,
+) -> jintArray {
+    guard(&mut env, std::ptr::null_mut(), |env| {
+        let closed_shape: LocShape = match loc_shape(env, closedShape) {
+            Some(shape) => shape,
+            None => return std::ptr::null_mut(),
+        };
+        let open_shape: LocShape = match loc_shape(env, openShape) {
+            Some(shape) => shape,
+            None => return std::ptr::null_mut(),
+        };
+        let door: Door = Door::new(
+            DoorState::new(closed_shape, (closedAngle & 0x3) as u8, 0, 0),
+            DoorState::new(open_shape, (openAngle & 0x3) as u8, openOffsetX, openOffsetZ),
+            blockrange != 0,
+            breakroutefinding != 0,
+        );
+        let mut flags = write_world(world);
+        let changed: Result<Vec<u32>, CoordOutOfRange> = if open != 0 {
+            door.open(&mut flags, x, z, y)
+        } else {
+            door.close(&mut flags, x, z, y)
+        };
+        drop(flags);
+        packed_to_jint_array(env, changed)
+    })
+}
+
+#[no_mangle]
+pub unsafe extern "system" fn Java_rsmod_World_setZone(
+    mut env: JNIEnv,
+    _class: JClass,
+    world: jlong,
+    x: jint,
+    z: jint,
+    y: jint,
+    flags: JIntArray,
+) {
+    guard(&mut env, (), |env| {
+        let mut zone: [jint; 64] = [0; 64];
+        let exact: bool = !flags.is_null() && matches!(env.get_array_length(&flags), Ok(64));
+        if !exact || env.get_int_array_region(&flags, 0, &mut zone).is_err() {
+            env.exception_clear();
+            env.throw_new(ILLEGAL_ARGUMENT, "Zone flags must hold exactly 64 masks");
+            return;
+        }
+        let changed: Result<(), CoordOutOfRange> = write_world(world).try_set_zone(x, z, y, &zone.map(|mask| mask as u32));
+        in_world(env, changed);
+    })
+}
+
+#[no_mangle]
+pub unsafe extern "system" fn Java_rsmod_World_applyEdits(
+    mut env: JNIEnv,
+    _class: JClass,
+    world: jlong,
+    edits: JIntArray,
+) {
+    guard(&mut env, (), |env| {
+        let mut parsed: Vec<CollisionEdit> = vec![];
+        {
+            let elements = match env.get_array_elements(&edits, ReleaseMode::NoCopyBack) {
+                Ok(elements) => elements,
+                Err(_) => return,
+            };
+            if elements.len() % 5 != 0 {
+                drop(elements);
+                env.throw_new(ILLEGAL_ARGUMENT, "Edits must be groups of x, z, y, mask, op");
+                return;
+            }
+            parsed.reserve(elements.len() / 5);
+            for edit in elements.chunks_exact(5) {
+                let op: CollisionOp = match u8::try_from(edit[4]).ok().and_then(CollisionOp::try_from_u8) {
+                    Some(op) => op,
+                    None => {
+                        let message: String = format!("Invalid collision op: {}", edit[4]);
+                        drop(elements);
+                        env.throw_new(ILLEGAL_ARGUMENT, message);
+                        return;
+                    }
+                };
+                parsed.push(CollisionEdit::new(edit[0], edit[1], edit[2], edit[3] as u32, op));
+            }
+        }
+        let changed: Result<(), CoordOutOfRange> = write_world(world).try_apply(&parsed);
+        in_world(env, changed);
+    })
+}
+
+#[no_mangle]
+pub unsafe extern "system" fn Java_rsmod_World_loadMapsquare(
+    mut env: JNIEnv,
+    _class: JClass,
+    world: jlong,
+    mapsquareX: jint,
+    mapsquareZ: jint,
+    buffer: JByteBuffer,
+) {
+    guard(&mut env, (), |env| {
+        let address: *mut u8 = match env.get_direct_buffer_address(&buffer) {
+            Ok(address) => address,
+            Err(_) => {
+                env.throw_new(ILLEGAL_ARGUMENT, "Mapsquare buffer must be direct");
+                return;
+            }
+        };
+        let capacity: usize = env.get_direct_buffer_capacity(&buffer).unwrap_or(0);
+        if capacity != CollisionFlagMap::MAPSQUARE_TILE_COUNT * 4 || address.align_offset(4) != 0 {
+            env.throw_new(ILLEGAL_ARGUMENT, "Mapsquare buffer must hold 16384 aligned masks");
+            return;
+        }
+        let flags: &[u32] = std::slice::from_raw_parts(address as *const u32, CollisionFlagMap::MAPSQUARE_TILE_COUNT);
+        if !write_world(world).load_mapsquare(mapsquareX, mapsquareZ, flags) {
+            env.throw_new(ILLEGAL_ARGUMENT, format!("Mapsquare out of range: {}, {}", mapsquareX, mapsquareZ));
+        }
+    })
+}
+
+#[no_mangle]
+pub unsafe extern "system" fn Java_rsmod_World_allocateIfAbsent(
+    mut env: JNIEnv,
+    _class: JClass,
+    world: jlong,
+    x: jint,
+    z: jint,
+    y: jint,
+) {
+    guard(&mut env, (), |env| {
+        let changed: Result<(), CoordOutOfRange> = write_world(world).try_allocate_if_absent(x, z, y);
+        in_world(env, changed);
+    })
+}
+
+#[no_mangle]
+pub unsafe extern "system" fn Java_rsmod_World_deallocateIfPresent(
+    mut env: JNIEnv,
+    _class: JClass,
+    world: jlong,
+    x: jint,
+    z: jint,
+    y: jint,
+) {
+    guard(&mut env, (), |_| {
+        write_world(world).deallocate_if_present(x, z, y);
+    })
+}
+
+#[no_mangle]
+pub unsafe extern "system" fn Java_rsmod_World_isZoneAllocated(
+    mut env: JNIEnv,
+    _class: JClass,
+    world: jlong,
+    x: jint,
+    z: jint,
+    y: jint,
+) -> jboolean {
+    guard(&mut env, 0, |_| {
+        if read_world(world).is_zone_allocated(x, z, y) { 1 } else { 0 }
+    })
+}
+
+#[no_mangle]
+pub unsafe extern "system" fn Java_rsmod_World_enableJournal(
+    mut env: JNIEnv,
+    _class: JClass,
+    world: jlong,
+    recordTiles: jboolean,
+) {
+    guard(&mut env, (), |_| {
+        write_world(world).enable_journal(recordTiles != 0);
+    })
+}
+
+#[no_mangle]
+pub unsafe extern "system" fn Java_rsmod_World_disableJournal(
+    mut env: JNIEnv,
+    _class: JClass,
+    world: jlong,
+) {
+    guard(&mut env, (), |_| {
+        write_world(world).disable_journal();
+    })
+}
+
+#[no_mangle]
+pub unsafe extern "system" fn Java_rsmod_World_zoneVersion(
+    mut env: JNIEnv,
+    _class: JClass,
+    world: jlong,
+    x: jint,
+    z: jint,
+    y: jint,
+) -> jint {
+    guard(&mut env, 0, |_| {
+        read_world(world).zone_version(x, z, y) as jint
+    })
+}
+
+#[no_mangle]
+pub unsafe extern "system" fn Java_rsmod_World_drainChangedZones(
+    mut env: JNIEnv,
+    _class: JClass,
+    world: jlong,
+) -> jintArray {
+    guard(&mut env, std::ptr::null_mut(), |env| {
+        let mut flags = write_world(world);
+        let Some(journal) = flags.journal_mut() else {
+            drop(flags);
+            return vec_to_jint_array(env, vec![]);
+        };
+        // packed before draining, so zones of a sparse world past CoordGrid's range aren't lost.
+        let zones: Result<Vec<u32>, CoordOutOfRange> = journal
+            .changed_zones()
+            .iter()
+            .map(|zone| CoordGrid::try_new(zone.y(), zone.x(), zone.z()).map(|coord| coord.packed))
+            .collect();
+        if zones.is_ok() {
+            journal.drain_changed_zones();
+        }
+        drop(flags);
+        packed_to_jint_array(env, zones)
+    })
+}
+
+#[no_mangle]
+pub unsafe extern "system" fn Java_rsmod_World_isFlagged(
+    mut env: JNIEnv,
+    _class: JClass,
+    world: jlong,
+    x: jint,
+    z: jint,
+    y: jint,
+    masks: jint,
+) -> jboolean {
+    guard(&mut env, 0, |_| {
+        if read_world(world).is_flagged(x, z, y, masks as u32) { 1 } else { 0 }
+    })
+}
+
+#[no_mangle]
+pub unsafe extern "system" fn Java_rsmod_World_canTravel(
+    mut env: JNIEnv,
+    _class: JClass,
+    world: jlong,
+    y: jint,
+    x: jint,
+    z: jint,
+    offsetX: jint,
+    offsetZ: jint,
+    size: jint,
+    extraFlag: jint,
+    collision: jint,
+) -> jboolean {
+    guard(&mut env, 0, |_| {
+        let flags = read_world(world);
+        if can_travel(
+            &*flags,
+            y,
+            x,
+            z,
+            offsetX as i8,
+            offsetZ as i8,
+            size as u8,
+            extraFlag as u32,
+            &get_collision_strategy(match collision {
+                0 => CollisionType::NORMAL,
+                1 => CollisionType::BLOCKED,
+                2 => CollisionType::INDOORS,
+                3 => CollisionType::OUTDOORS,
+                4 => CollisionType::LINE_OF_SIGHT,
+                _ => CollisionType::NORMAL,
+            }),
+        ) { 1 } else { 0 }
+    })
+}
+
+#[no_mangle]
+pub unsafe extern "system" fn Java_rsmod_World_hasLineOfSight(
+    mut env: JNIEnv,
+    _class: JClass,
+    world: jlong,
+    y: jint,
+    srcX: jint,
+    srcZ: jint,
+    destX: jint,
+    destZ: jint,
+    srcWidth: jint,
+    srcHeight: jint,
+    destWidth: jint,
+    destHeight: jint,
+    extraFlag: jint,
+) -> jboolean {
+    guard(&mut env, 0, |_| {
+        let flags = read_world(world);
+        if has_line_of_sight(
+            &*flags,
+            y,
+            srcX,
+            srcZ,
+            destX,
+            destZ,
+            srcWidth as u8,
+            srcHeight as u8,
+            destWidth as u8,
+            destHeight as u8,
+            extraFlag as u32,
+        ) { 1 } else { 0 }
+    })
+}
+
+#[no_mangle]
+pub unsafe extern "system" fn Java_rsmod_World_hasLineOfWalk(
+    mut env: JNIEnv,
+    _class: JClass,
+    world: jlong,
+    y: jint,
+    srcX: jint,
+    srcZ: jint,
+    destX: jint,
+    destZ: jint,
+    srcWidth: jint,
+    srcHeight: jint,
+    destWidth: jint,
+    destHeight: jint,
+    extraFlag: jint,
+) -> jboolean {
+    guard(&mut env, 0, |_| {
+        let flags = read_world(world);
+        if has_line_of_walk(
+            &*flags,
+            y,
+            srcX,
+            srcZ,
+            destX,
+            destZ,
+            srcWidth as u8,
+            srcHeight as u8,
+            destWidth as u8,
+            destHeight as u8,
+            extraFlag as u32,
+        ) { 1 } else { 0 }
+    })
+}
+
+#[no_mangle]
+pub unsafe extern "system" fn Java_rsmod_World_lineOfSight(
+    mut env: JNIEnv,
+    _class: JClass,
+    world: jlong,
+    y: jint,
+    srcX: jint,
+    srcZ: jint,
+    destX: jint,
+    destZ: jint,
+    srcWidth: jint,
+    srcHeight: jint,
+    destWidth: jint,
+    destHeight: jint,
+    extraFlag: jint,
+) -> jintArray {
+    guard(&mut env, std::ptr::null_mut(), |env| {
+        let flags = read_world(world);
+        packed_to_jint_array(env, line_of_sight(
+            &*flags,
+            y,
+            srcX,
+            srcZ,
+            destX,
+            destZ,
+            srcWidth as u8,
+            srcHeight as u8,
+            destWidth as u8,
+            destHeight as u8,
+            extraFlag as u32,
+        ))
+    })
+}
+
+/**
+ * [Java_rsmod_World_lineOfSight] into `out`, returning the same as [Java_rsmod_World_findPathInto].
+ */
+#[no_mangle]
+pub unsafe extern "system" fn Java_rsmod_World_lineOfSightInto(
+    mut env: JNIEnv,
+    _class: JClass,
+    world: jlong,
+    y: jint,
+    srcX: jint,
+    srcZ: jint,
+    destX: jint,
+    destZ: jint,
+    srcWidth: jint,
+    srcHeight: jint,
+    destWidth: jint,
+    destHeight: jint,
+    extraFlag: jint,
+    out: JIntArray,
+) -> jlong {
+    guard(&mut env, 0, |env| {
+        if !check_sizes(env, &[("srcWidth", srcWidth), ("srcHeight", srcHeight), ("destWidth", destWidth), ("destHeight", destHeight)]) {
+            return 0;
+        }
+        let tiles: Vec<u32> = match in_world(env, line_of_sight(
+            &*read_world(world),
+            y,
+            srcX,
+            srcZ,
+            destX,
+            destZ,
+            srcWidth as u8,
+            srcHeight as u8,
+            destWidth as u8,
+            destHeight as u8,
+            extraFlag as u32,
+        )) {
+            Some(tiles) => tiles,
+            None => return 0,
+        };
+        return write_array(env, &out, &tiles);
+    })
+}
+
+#[no_mangle]
+pub unsafe extern "system" fn Java_rsmod_World_lineOfWalk(
+    mut env: JNIEnv,
+    _class: JClass,
+    world: jlong,
+    y: jint,
+    srcX: jint,
+    srcZ: jint,
+    destX: jint,
+    destZ: jint,
+    srcWidth: jint,
+    srcHeight: jint,
+    destWidth: jint,
+    destHeight: jint,
+    extraFlag: jint,
+) -> jintArray {
+    guard(&mut env, std::ptr::null_mut(), |env| {
+        let flags = read_world(world);
+        packed_to_jint_array(env, line_of_walk(
+            &*flags,
+            y,
+            srcX,
+            srcZ,
+            destX,
+            destZ,
+            srcWidth as u8,
+            srcHeight as u8,
+            destWidth as u8,
+            destHeight as u8,
+            extraFlag as u32,
+        ))
+    })
+}
+
+/**
+ * [Java_rsmod_World_lineOfWalk] into `out`, returning the same as [Java_rsmod_World_findPathInto].
+ */
+#[no_mangle]
+pub unsafe extern "system" fn Java_rsmod_World_lineOfWalkInto(
+    mut env: JNIEnv,
+    _class: JClass,
+    world: jlong,
+    y: jint,
+    srcX: jint,
+    srcZ: jint,
+    destX: jint,
+    destZ: jint,
+    srcWidth: jint,
+    srcHeight: jint,
+    destWidth: jint,
+    destHeight: jint,
+    extraFlag: jint,
+    out: JIntArray,
+) -> jlong {
+    guard(&mut env, 0, |env| {
+        if !check_sizes(env, &[("srcWidth", srcWidth), ("srcHeight", srcHeight), ("destWidth", destWidth), ("destHeight", destHeight)]) {
+            return 0;
+        }
+        let tiles: Vec<u32> = match in_world(env, line_of_walk(
+            &*read_world(world),
+            y,
+            srcX,
+            srcZ,
+            destX,
+            destZ,
+            srcWidth as u8,
+            srcHeight as u8,
+            destWidth as u8,
+            destHeight as u8,
+            extraFlag as u32,
+        )) {
+            Some(tiles) => tiles,
+            None => return 0,
+        };
+        return write_array(env, &out, &tiles);
+    })
+}
+
+/**
+ * Like [Java_rsmod_World_lineOfSight], but keeps the tiles up to an obstruction and reports what blocked the line,
+ * laid out as [RayResult::flatten].
+ */
+#[no_mangle]
+pub unsafe extern "system" fn Java_rsmod_World_lineOfSightResult(
+    mut env: JNIEnv,
+    _class: JClass,
+    world: jlong,
+    y: jint,
+    srcX: jint,
+    srcZ: jint,
+    destX: jint,
+    destZ: jint,
+    srcWidth: jint,
+    srcHeight: jint,
+    destWidth: jint,
+    destHeight: jint,
+    extraFlag: jint,
+) -> jintArray {
+    guard(&mut env, std::ptr::null_mut(), |env| {
+        if !check_sizes(env, &[("srcWidth", srcWidth), ("srcHeight", srcHeight), ("destWidth", destWidth), ("destHeight", destHeight)]) {
+            return std::ptr::null_mut();
+        }
+        let flags = read_world(world);
+        ray_to_jint_array(env, line_of_sight_result(
+            &*flags,
+            y,
+            srcX,
+            srcZ,
+            destX,
+            destZ,
+            srcWidth as u8,
+            srcHeight as u8,
+            destWidth as u8,
+            destHeight as u8,
+            extraFlag as u32,
+        ))
+    })
+}
+
+/**
+ * Like [Java_rsmod_World_lineOfWalk], but keeps the tiles up to an obstruction and reports what blocked the line,
+ * laid out as [RayResult::flatten].
+ */
+#[no_mangle]
+pub unsafe extern "system" fn Java_rsmod_World_lineOfWalkResult(
+    mut env: JNIEnv,
+    _class: JClass,
+    world: jlong,
+    y: jint,
+    srcX: jint,
+    srcZ: jint,
+    destX: jint,
+    destZ: jint,
+    srcWidth: jint,
+    srcHeight: jint,
+    destWidth: jint,
+    destHeight: jint,
+    extraFlag: jint,
+) -> jintArray {
+    guard(&mut env, std::ptr::null_mut(), |env| {
+        if !check_sizes(env, &[("srcWidth", srcWidth), ("srcHeight", srcHeight), ("destWidth", destWidth), ("destHeight", destHeight)]) {
+            return std::ptr::null_mut();
+        }
+        let flags = read_world(world);
+        ray_to_jint_array(env, line_of_walk_result(
+            &*flags,
+            y,
+            srcX,
+            srcZ,
+            destX,
+            destZ,
+            srcWidth as u8,
+            srcHeight as u8,
+            destWidth as u8,
+            destHeight as u8,
+            extraFlag as u32,
+        ))
+    })
+}
+
+#[no_mangle]
+pub unsafe extern "system" fn Java_rsmod_World_reached(
+    mut env: JNIEnv,
+    _class: JClass,
+    world: jlong,
+    y: jint,
+    srcX: jint,
+    srcZ: jint,
+    destX: jint,
+    destZ: jint,
+    destWidth: jint,
+    destHeight: jint,
+    srcSize: jint,
+    angle: jint,
+    shape: jint,
+    blockAccessFlags: jint,
+) -> jboolean {
+    guard(&mut env, 0, |_| {
+        let flags = read_world(world);
+        if ReachStrategy::reached(
+            &*flags,
+            y,
+            srcX,
+            srcZ,
+            destX,
+            destZ,
+            destWidth as u8,
+            destHeight as u8,
+            srcSize as u8,
+            angle as u8,
+            shape as i8,
+            blockAccessFlags as u8,
+        ) { 1 } else { 0 }
+    })
+}
+
+#[no_mangle]
+pub unsafe extern "system" fn Java_rsmod_World___set(
+    mut env: JNIEnv,
+    _class: JClass,
+    world: jlong,
+    x: jint,
+    z: jint,
+    y: jint,
+    mask: jint,
+) {
+    guard(&mut env, (), |env| {
+        let changed: Result<(), CoordOutOfRange> = write_world(world).try_set(x, z, y, mask as u32);
+        in_world(env, changed);
+    })
+}
@@ -0,0 +1,704 @@
+use std::ffi::{c_int, c_void};
+
+use pyo3::exceptions::{PyBufferError, PyIndexError, PyValueError};
+use pyo3::ffi;
+use pyo3::prelude::*;
+
+use crate::rsmod::{
+    can_travel, find_naive_path, find_naive_path_with, get_collision_strategy, has_line_of_sight, has_line_of_walk, line_of_sight, line_of_sight_wide,
+    line_of_walk, line_of_walk_wide, CollisionStrategies, CollisionType, CoordGrid, CoordOutOfRange, LocShape, PathFinder, SeededRandom, WideCoordGrid,
+};
+use crate::rsmod::collision::collision::CollisionFlagMap;
+use crate::rsmod::collision::collision_change::CollisionFlagWriter;
+use crate::rsmod::reach_strategy::ReachStrategy;
+
+/**
+ * A read-only array of u32 exposed through the buffer protocol,
+ * so `numpy.asarray` and `memoryview` see it as `uint32` without copying.
+ */
+#[pyclass(name = "U32Buffer", module = "rsmod", frozen)]
+pub struct PyU32Buffer {
+    data: Vec<u32>,
+    // the buffer protocol wants pointers to these, they never change after creation.
+    shape: isize,
+    stride: isize,
+}
+
+impl PyU32Buffer {
+    #[inline(always)]
+    fn new(data: Vec<u32>) -> PyU32Buffer {
+        let shape: isize = data.len() as isize;
+        return PyU32Buffer {
+            data,
+            shape,
+            stride: std::mem::size_of::<u32>() as isize,
+        };
+    }
+}
+
+#[pymethods]
+impl PyU32Buffer {
+    unsafe fn __getbuffer__(slf: Bound<'_, Self>, view: *mut ffi::Py_buffer, flags: c_int) -> PyResult<()> {
+        if view.is_null() {
+            return Err(PyBufferError::new_err("View is null"));
+        }
+        if flags & ffi::PyBUF_WRITABLE == ffi::PyBUF_WRITABLE {
+            return Err(PyBufferError::new_err("U32Buffer is read-only"));
+        }
+        let buffer: &PyU32Buffer = slf.get();
+        (*view).buf = buffer.data.as_ptr() as *mut c_void;
+        (*view).len = buffer.shape * buffer.stride;
+        (*view).readonly = 1;
+        (*view).itemsize = buffer.stride;
+        (*view).format = if flags & ffi::PyBUF_FORMAT == ffi::PyBUF_FORMAT {
+            c"I".as_ptr() as *mut _
+        } else {
+            std::ptr::null_mut()
+        };
+        (*view).ndim = 1;
+        (*view).shape = if flags & ffi::PyBUF_ND == ffi::PyBUF_ND {
+            &buffer.shape as *const isize as *mut isize
+        } else {
+            std::ptr::null_mut()
+        };
+        (*view).strides = if flags & ffi::PyBUF_STRIDES == ffi::PyBUF_STRIDES {
+            &buffer.stride as *const isize as *mut isize
+        } else {
+            std::ptr::null_mut()
+        };
+        (*view).suboffsets = std::ptr::null_mut();
+        (*view).internal = std::ptr::null_mut();
+        // keeps the buffer alive until the view is released.
+        (*view).obj = slf.into_any().into_ptr();
+        return Ok(());
+    }
+
+    unsafe fn __releasebuffer__(&self, _view: *mut ffi::Py_buffer) {}
+
+    fn __len__(&self) -> usize {
+        return self.data.len();
+    }
+
+    fn __getitem__(&self, index: isize) -> PyResult<u32> {
+        let index: isize = if index < 0 { index + self.data.len() as isize } else { index };
+        return usize::try_from(index)
+            .ok()
+            .and_then(|index| self.data.get(index).copied())
+            .ok_or_else(|| PyIndexError::new_err("U32Buffer index out of range"));
+    }
+
+    fn tolist(&self) -> Vec<u32> {
+        return self.data.clone();
+    }
+}
+
+#[inline(always)]
+fn collision_strategy(collision: u8) -> PyResult<CollisionStrategies> {
+    let collision: CollisionType = match collision {
+        0 => CollisionType::NORMAL,
+        1 => CollisionType::BLOCKED,
+        2 => CollisionType::INDOORS,
+        3 => CollisionType::OUTDOORS,
+        4 => CollisionType::LINE_OF_SIGHT,
+        _ => return Err(PyValueError::new_err(format!("Invalid collision type: {}", collision))),
+    };
+    return Ok(get_collision_strategy(collision));
+}
+
+/**
+ * Raises a `ValueError` for a coordinate outside of the map, or one that can't be packed.
+ */
+#[inline(always)]
+fn in_world<T>(result: Result<T, CoordOutOfRange>) -> PyResult<T> {
+    return result.map_err(|error| PyValueError::new_err(error.to_string()));
+}
+
+#[inline(always)]
+fn loc_shape(shape: i8) -> PyResult<LocShape> {
+    return LocShape::try_from_i8(shape).ok_or_else(|| PyValueError::new_err(format!("Invalid loc shape: {}", shape)));
+}
+
+#[inline(always)]
+fn loc_angle(angle: u8) -> PyResult<u8> {
+    if angle > 3 {
+        return Err(PyValueError::new_err(format!("Invalid loc angle: {}", angle)));
+    }
+    return Ok(angle);
+}
+
+/**
+ * Raises a `ValueError` for an angle or shape the path and reach functions can't handle.
+ */
+#[inline(always)]
+fn path_target(angle: u8, shape: i8) -> PyResult<()> {
+    if !ReachStrategy::is_valid_target(angle, shape) {
+        return Err(PyValueError::new_err(format!("Invalid loc angle or shape: {}, {}", angle, shape)));
+    }
+    return Ok(());
+}
+
+#[pyclass(name = "CollisionFlagMap", module = "rsmod")]
+pub struct PyCollisionFlagMap {
+    inner: CollisionFlagMap,
+}
+
+#[pymethods]
+impl PyCollisionFlagMap {
+    /**
+     * `sparse` maps cover x and z in -0x40000000..=0x3fffffff and only take room for the mapsquares written to.
+     */
+    #[new]
+    #[pyo3(signature = (sparse = false))]
+    fn new(sparse: bool) -> PyCollisionFlagMap {
+        return PyCollisionFlagMap {
+            inner: if sparse { CollisionFlagMap::sparse() } else { CollisionFlagMap::new() },
+        };
+    }
+
+    fn get(&self, x: i32, z: i32, y: i32) -> u32 {
+        return unsafe { self.inner.get(x, z, y) };
+    }
+
+    fn set(&mut self, x: i32, z: i32, y: i32, mask: u32) -> PyResult<()> {
+        return unsafe { self.inner.try_set(x, z, y, mask) }.map_err(|e| PyValueError::new_err(e.to_string()));
+    }
+
+    fn add(&mut self, x: i32, z: i32, y: i32, mask: u32) -> PyResult<()> {
+        return unsafe { self.inner.try_add(x, z, y, mask) }.map_err(|e| PyValueError::new_err(e.to_string()));
+    }
+
+    fn remove(&mut self, x: i32, z: i32, y: i32, mask: u32) -> PyResult<()> {
+        return unsafe { self.inner.try_remove(x, z, y, mask) }.map_err(|e| PyValueError::new_err(e.to_string()));
+    }
+
+    fn allocate_if_absent(&mut self, x: i32, z: i32, y: i32) -> PyResult<()> {
+        return unsafe { self.inner.try_allocate_if_absent(x, z, y) }.map_err(|e| PyValueError::new_err(e.to_string()));
+    }
+
+    fn deallocate_if_present(&mut self, x: i32, z: i32, y: i32) {
+        unsafe { self.inner.deallocate_if_present(x, z, y) };
+    }
+
+    fn is_zone_allocated(&self, x: i32, z: i32, y: i32) -> bool {
+        return unsafe { self.inner.is_zone_allocated(x, z, y) };
+    }
+
+    fn is_flagged(&self, x: i32, z: i32, y: i32, masks: u32) -> bool {
+        return unsafe { self.inner.is_flagged(x, z, y, masks) };
+    }
+
+    fn change_floor(&mut self, x: i32, z: i32, y: i32, add: bool) -> PyResult<()> {
+        return in_world(unsafe { self.inner.change_floor(x, z, y, add) });
+    }
+
+    fn change_roof(&mut self, x: i32, z: i32, y: i32, add: bool) -> PyResult<()> {
+        return in_world(unsafe { self.inner.change_roof(x, z, y, add) });
+    }
+
+    fn change_npc(&mut self, x: i32, z: i32, y: i32, size: i32, add: bool) -> PyResult<()> {
+        return in_world(unsafe { self.inner.change_npc(x, z, y, size, add) });
+    }
+
+    fn change_player(&mut self, x: i32, z: i32, y: i32, size: i32, add: bool) -> PyResult<()> {
+        return in_world(unsafe { self.inner.change_player(x, z, y, size, add) });
+    }
+
+    fn change_loc(
+        &mut self,
+        x: i32,
+        z: i32,
+        y: i32,
+        width: i32,
+        length: i32,
+        blockrange: bool,
+        breakroutefinding: bool,
+        add: bool,
+    ) -> PyResult<()> {
+        return in_world(unsafe { self.inner.change_loc(x, z, y, width, length, blockrange, breakroutefinding, add) });
+    }
+
+    fn change_loc_shape(
+        &mut self,
+        x: i32,
+        z: i32,
+        y: i32,
+        width: u8,
+        length: u8,
+        angle: u8,
+        shape: i8,
+        blockrange: bool,
+        breakroutefinding: bool,
+        add: bool,
+    ) -> PyResult<()> {
+        let shape: LocShape = loc_shape(shape)?;
+        return in_world(unsafe {
+            self.inner.change_loc_shape(x, z, y, width, length, angle & 0x3, shape, blockrange, breakroutefinding, add)
+        });
+    }
+
+    /**
+     * Flags a wall of shape 0-3, raising a `ValueError` for any other shape, diagonal walls (9) included.
+     */
+    fn change_wall(&mut self, x: i32, z: i32, y: i32, angle: u8, shape: i8, blockrange: bool, breakroutefinding: bool, add: bool) -> PyResult<()> {
+        let shape: LocShape = loc_shape(shape)?;
+        return unsafe { self.inner.change_wall(x, z, y, angle & 0x3, shape, blockrange, breakroutefinding, add) }
+            .map_err(|error| PyValueError::new_err(error.to_string()));
+    }
+
+    fn change_wall_straight(&mut self, x: i32, z: i32, y: i32, angle: u8, blockrange: bool, breakroutefinding: bool, add: bool) -> PyResult<()> {
+        let angle: u8 = loc_angle(angle)?;
+        return in_world(unsafe { self.inner.change_wall_straight(x, z, y, angle, blockrange, breakroutefinding, add) });
+    }
+
+    fn change_wall_corner(&mut self, x: i32, z: i32, y: i32, angle: u8, blockrange: bool, breakroutefinding: bool, add: bool) -> PyResult<()> {
+        let angle: u8 = loc_angle(angle)?;
+        return in_world(unsafe { self.inner.change_wall_corner(x, z, y, angle, blockrange, breakroutefinding, add) });
+    }
+
+    fn change_wall_l(&mut self, x: i32, z: i32, y: i32, angle: u8, blockrange: bool, breakroutefinding: bool, add: bool) -> PyResult<()> {
+        let angle: u8 = loc_angle(angle)?;
+        return in_world(unsafe { self.inner.change_wall_l(x, z, y, angle, blockrange, breakroutefinding, add) });
+    }
+
+    /**
+     * Replaces the zone with 64 masks indexed by `(x & 7) | ((z & 7) << 3)`.
+     */
+    fn set_zone(&mut self, x: i32, z: i32, y: i32, flags: Vec<u32>) -> PyResult<()> {
+        let zone: [u32; 64] = flags.try_into().map_err(|_| PyValueError::new_err("Zone flags must hold 64 masks"))?;
+        if !self.inner.contains(x, z, y) {
+            return Err(PyValueError::new_err(format!("Zone out of range: x={}, z={}, y={}", x, z, y)));
+        }
+        unsafe { self.inner.set_zone(x, z, y, &zone) };
+        return Ok(());
+    }
+
+    /**
+     * Returns the 64 masks of the zone, or `None` if it is not allocated.
+     */
+    fn zone(&self, x: i32, z: i32, y: i32) -> Option<PyU32Buffer> {
+        return self.inner.zone(x, z, y).map(|flags| PyU32Buffer::new(flags.to_vec()));
+    }
+
+    /**
+     * Returns the south-west tile of every allocated zone as `(x, z, y)`.
+     */
+    fn zones(&self) -> Vec<(i32, i32, i32)> {
+        return self.inner.zones().map(|(x, z, y, _)| (x, z, y)).collect();
+    }
+
+    fn zone_count(&self) -> usize {
+        return self.inner.zone_count();
+    }
+}
+
+#[pyclass(name = "PathFinder", module = "rsmod")]
+pub struct PyPathFinder {
+    inner: PathFinder,
+}
+
+#[pymethods]
+impl PyPathFinder {
+    #[new]
+    fn new() -> PyPathFinder {
+        return PyPathFinder {
+            inner: PathFinder::new(),
+        };
+    }
+
+    #[pyo3(signature = (
+        map, y, src_x, src_z, dest_x, dest_z, src_size = 1, dest_width = 1, dest_height = 1, angle = 0,
+        shape = -1, move_near = true, block_access_flags = 0, max_waypoints = 25, collision = 0
+    ))]
+    fn find_path(
+        &mut self,
+        map: &PyCollisionFlagMap,
+        y: i32,
+        src_x: i32,
+        src_z: i32,
+        dest_x: i32,
+        dest_z: i32,
+        src_size: u8,
+        dest_width: u8,
+        dest_height: u8,
+        angle: u8,
+        shape: i8,
+        move_near: bool,
+        block_access_flags: u8,
+        max_waypoints: u8,
+        collision: u8,
+    ) -> PyResult<PyU32Buffer> {
+        path_target(angle, shape)?;
+        let collision: CollisionStrategies = collision_strategy(collision)?;
+        return Ok(PyU32Buffer::new(in_world(unsafe {
+            self.inner.find_path(
+                &map.inner,
+                y,
+                src_x,
+                src_z,
+                dest_x,
+                dest_z,
+                src_size,
+                dest_width,
+                dest_height,
+                angle,
+                shape,
+                move_near,
+                block_access_flags,
+                max_waypoints,
+                &collision,
+            )
+        })?));
+    }
+
+    /**
+     * Like `find_path`, but returns the waypoints packed as wide coordinates, see `unpack_wide_coord`,
+     * for paths past 16383 in a sparse map.
+     */
+    #[pyo3(signature = (
+        map, y, src_x, src_z, dest_x, dest_z, src_size = 1, dest_width = 1, dest_height = 1, angle = 0,
+        shape = -1, move_near = true, block_access_flags = 0, max_waypoints = 25, collision = 0
+    ))]
+    fn find_path_wide(
+        &mut self,
+        map: &PyCollisionFlagMap,
+        y: i32,
+        src_x: i32,
+        src_z: i32,
+        dest_x: i32,
+        dest_z: i32,
+        src_size: u8,
+        dest_width: u8,
+        dest_height: u8,
+        angle: u8,
+        shape: i8,
+        move_near: bool,
+        block_access_flags: u8,
+        max_waypoints: u8,
+        collision: u8,
+    ) -> PyResult<Vec<u64>> {
+        path_target(angle, shape)?;
+        let collision: CollisionStrategies = collision_strategy(collision)?;
+        return in_world(unsafe {
+            self.inner.find_path_wide(
+                &map.inner,
+                y,
+                src_x,
+                src_z,
+                dest_x,
+                dest_z,
+                src_size,
+                dest_width,
+                dest_height,
+                angle,
+                shape,
+                move_near,
+                block_access_flags,
+                max_waypoints,
+                &collision,
+            )
+        });
+    }
+}
+
+#[pyclass(name = "ReachStrategy", module = "rsmod")]
+pub struct PyReachStrategy;
+
+#[pymethods]
+impl PyReachStrategy {
+    #[staticmethod]
+    #[pyo3(signature = (
+        map, y, src_x, src_z, dest_x, dest_z, dest_width = 1, dest_height = 1, src_size = 1, angle = 0,
+        shape = -1, block_access_flags = 0
+    ))]
+    fn reached(
+        map: &PyCollisionFlagMap,
+        y: i32,
+        src_x: i32,
+        src_z: i32,
+        dest_x: i32,
+        dest_z: i32,
+        dest_width: u8,
+        dest_height: u8,
+        src_size: u8,
+        angle: u8,
+        shape: i8,
+        block_access_flags: u8,
+    ) -> PyResult<bool> {
+        path_target(angle, shape)?;
+        return Ok(unsafe {
+            ReachStrategy::reached(
+                &map.inner,
+                y,
+                src_x,
+                src_z,
+                dest_x,
+                dest_z,
+                dest_width,
+                dest_height,
+                src_size,
+                angle,
+                shape,
+                block_access_flags,
+            )
+        });
+    }
+}
+
+#[pyfunction(name = "find_naive_path")]
+#[pyo3(signature = (
+    map, y, src_x, src_z, dest_x, dest_z, src_width = 1, src_height = 1, dest_width = 1, dest_height = 1,
+    extra_flag = 0, collision = 0, seed = None
+))]
+fn py_find_naive_path(
+    map: &PyCollisionFlagMap,
+    y: i32,
+    src_x: i32,
+    src_z: i32,
+    dest_x: i32,
+    dest_z: i32,
+    src_width: u8,
+    src_height: u8,
+    dest_width: u8,
+    dest_height: u8,
+    extra_flag: u32,
+    collision: u8,
+    seed: Option<u64>,
+) -> PyResult<PyU32Buffer> {
+    let collision: CollisionStrategies = collision_strategy(collision)?;
+    return Ok(PyU32Buffer::new(in_world(unsafe {
+        match seed {
+            Some(seed) => find_naive_path_with(
+                &map.inner,
+                y,
+                src_x,
+                src_z,
+                dest_x,
+                dest_z,
+                src_width,
+                src_height,
+                dest_width,
+                dest_height,
+                extra_flag,
+                &collision,
+                &mut SeededRandom::new(seed),
+            ),
+            None => find_naive_path(
+                &map.inner,
+                y,
+                src_x,
+                src_z,
+                dest_x,
+                dest_z,
+                src_width,
+                src_height,
+                dest_width,
+                dest_height,
+                extra_flag,
+                &collision,
+            ),
+        }
+    })?));
+}
+
+#[pyfunction(name = "can_travel")]
+#[pyo3(signature = (map, y, x, z, offset_x, offset_z, size = 1, extra_flag = 0, collision = 0))]
+fn py_can_travel(
+    map: &PyCollisionFlagMap,
+    y: i32,
+    x: i32,
+    z: i32,
+    offset_x: i8,
+    offset_z: i8,
+    size: u8,
+    extra_flag: u32,
+    collision: u8,
+) -> PyResult<bool> {
+    let collision: CollisionStrategies = collision_strategy(collision)?;
+    return Ok(unsafe { can_travel(&map.inner, y, x, z, offset_x, offset_z, size, extra_flag, &collision) });
+}
+
+#[pyfunction(name = "has_line_of_sight")]
+#[pyo3(signature = (
+    map, y, src_x, src_z, dest_x, dest_z, src_width = 1, src_height = 1, dest_width = 1, dest_height = 1,
+    extra_flag = 0
+))]
+fn py_has_line_of_sight(
+    map: &PyCollisionFlagMap,
+    y: i32,
+    src_x: i32,
+    src_z: i32,
+    dest_x: i32,
+    dest_z: i32,
+    src_width: u8,
+    src_height: u8,
+    dest_width: u8,
+    dest_height: u8,
+    extra_flag: u32,
+) -> bool {
+    return unsafe {
+        has_line_of_sight(&map.inner, y, src_x, src_z, dest_x, dest_z, src_width, src_height, dest_width, dest_height, extra_flag)
+    };
+}
+
+#[pyfunction(name = "has_line_of_walk")]
+#[pyo3(signature = (
+    map, y, src_x, src_z, dest_x, dest_z, src_width = 1, src_height = 1, dest_width = 1, dest_height = 1,
+    extra_flag = 0
+))]
+fn py_has_line_of_walk(
+    map: &PyCollisionFlagMap,
+    y: i32,
+    src_x: i32,
+    src_z: i32,
+    dest_x: i32,
+    dest_z: i32,
+    src_width: u8,
+    src_height: u8,
+    dest_width: u8,
+    dest_height: u8,
+    extra_flag: u32,
+) -> bool {
+    return unsafe {
+        has_line_of_walk(&map.inner, y, src_x, src_z, dest_x, dest_z, src_width, src_height, dest_width, dest_height, extra_flag)
+    };
+}
+
+#[pyfunction(name = "line_of_sight")]
+#[pyo3(signature = (
+    map, y, src_x, src_z, dest_x, dest_z, src_width = 1, src_height = 1, dest_width = 1, dest_height = 1,
+    extra_flag = 0
+))]
+fn py_line_of_sight(
+    map: &PyCollisionFlagMap,
+    y: i32,
+    src_x: i32,
+    src_z: i32,
+    dest_x: i32,
+    dest_z: i32,
+    src_width: u8,
+    src_height: u8,
+    dest_width: u8,
+    dest_height: u8,
+    extra_flag: u32,
+) -> PyResult<PyU32Buffer> {
+    return Ok(PyU32Buffer::new(in_world(unsafe {
+        line_of_sight(&map.inner, y, src_x, src_z, dest_x, dest_z, src_width, src_height, dest_width, dest_height, extra_flag)
+    })?));
+}
+
+/**
+ * Like `line_of_sight`, but returns the tiles packed as wide coordinates, see `unpack_wide_coord`.
+ */
+#[pyfunction(name = "line_of_sight_wide")]
+#[pyo3(signature = (
+    map, y, src_x, src_z, dest_x, dest_z, src_width = 1, src_height = 1, dest_width = 1, dest_height = 1,
+    extra_flag = 0
+))]
+fn py_line_of_sight_wide(
+    map: &PyCollisionFlagMap,
+    y: i32,
+    src_x: i32,
+    src_z: i32,
+    dest_x: i32,
+    dest_z: i32,
+    src_width: u8,
+    src_height: u8,
+    dest_width: u8,
+    dest_height: u8,
+    extra_flag: u32,
+) -> PyResult<Vec<u64>> {
+    return Ok(in_world(unsafe {
+        line_of_sight_wide(&map.inner, y, src_x, src_z, dest_x, dest_z, src_width, src_height, dest_width, dest_height, extra_flag)
+    })?.into_line());
+}
+
+#[pyfunction(name = "line_of_walk")]
+#[pyo3(signature = (
+    map, y, src_x, src_z, dest_x, dest_z, src_width = 1, src_height = 1, dest_width = 1, dest_height = 1,
+    extra_flag = 0
+))]
+fn py_line_of_walk(
+    map: &PyCollisionFlagMap,
+    y: i32,
+    src_x: i32,
+    src_z: i32,
+    dest_x: i32,
+    dest_z: i32,
+    src_width: u8,
+    src_height: u8,
+    dest_width: u8,
+    dest_height: u8,
+    extra_flag: u32,
+) -> PyResult<PyU32Buffer> {
+    return Ok(PyU32Buffer::new(in_world(unsafe {
+        line_of_walk(&map.inner, y, src_x, src_z, dest_x, dest_z, src_width, src_height, dest_width, dest_height, extra_flag)
+    })?));
+}
+
+/**
+ * Like `line_of_walk`, but returns the tiles packed as wide coordinates, see `unpack_wide_coord`.
+ */
+#[pyfunction(name = "line_of_walk_wide")]
+#[pyo3(signature = (
+    map, y, src_x, src_z, dest_x, dest_z, src_width = 1, src_height = 1, dest_width = 1, dest_height = 1,
+    extra_flag = 0
+))]
+fn py_line_of_walk_wide(
+    map: &PyCollisionFlagMap,
+    y: i32,
+    src_x: i32,
+    src_z: i32,
+    dest_x: i32,
+    dest_z: i32,
+    src_width: u8,
+    src_height: u8,
+    dest_width: u8,
+    dest_height: u8,
+    extra_flag: u32,
+) -> PyResult<Vec<u64>> {
+    return Ok(in_world(unsafe {
+        line_of_walk_wide(&map.inner, y, src_x, src_z, dest_x, dest_z, src_width, src_height, dest_width, dest_height, extra_flag)
+    })?.into_line());
+}
+
+/**
+ * Unpacks a coordinate returned by the path and line functions into `(y, x, z)`.
+ */
+#[pyfunction(name = "unpack_coord")]
+fn py_unpack_coord(packed: u32) -> (u32, u32, u32) {
+    let coord: CoordGrid = CoordGrid::from(packed);
+    return (coord.y(), coord.x(), coord.z());
+}
+
+/**
+ * Unpacks a coordinate returned by the `*_wide` functions into `(y, x, z)`.
+ */
+#[pyfunction(name = "unpack_wide_coord")]
+fn py_unpack_wide_coord(packed: u64) -> (i32, i32, i32) {
+    let coord: WideCoordGrid = WideCoordGrid::from(packed);
+    return (coord.y(), coord.x(), coord.z());
+}
+
+#[pymodule]
+fn rsmod(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add_class::<PyU32Buffer>()?;
+    m.add_class::<PyCollisionFlagMap>()?;
+    m.add_class::<PyPathFinder>()?;
+    m.add_class::<PyReachStrategy>()?;
+    m.add_function(wrap_pyfunction!(py_find_naive_path, m)?)?;
+    m.add_function(wrap_pyfunction!(py_can_travel, m)?)?;
+    m.add_function(wrap_pyfunction!(py_has_line_of_sight, m)?)?;
+    m.add_function(wrap_pyfunction!(py_has_line_of_walk, m)?)?;
+    m.add_function(wrap_pyfunction!(py_line_of_sight, m)?)?;
+    m.add_function(wrap_pyfunction!(py_line_of_walk, m)?)?;
+    m.add_function(wrap_pyfunction!(py_line_of_sight_wide, m)?)?;
+    m.add_function(wrap_pyfunction!(py_line_of_walk_wide, m)?)?;
+    m.add_function(wrap_pyfunction!(py_unpack_coord, m)?)?;
+    m.add_function(wrap_pyfunction!(py_unpack_wide_coord, m)?)?;
+    m.add("COLLISION_NORMAL", CollisionType::NORMAL as u8)?;
+    m.add("COLLISION_BLOCKED", CollisionType::BLOCKED as u8)?;
+    m.add("COLLISION_INDOORS", CollisionType::INDOORS as u8)?;
+    m.add("COLLISION_OUTDOORS", CollisionType::OUTDOORS as u8)?;
+    m.add("COLLISION_LINE_OF_SIGHT", CollisionType::LINE_OF_SIGHT as u8)?;
+    return Ok(());
+}
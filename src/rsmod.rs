@@ -1,29 +1,45 @@
+pub use area_of_effect::*;
 pub use collision::*;
 pub use collision_strategy::*;
 pub use coord_grid::*;
+pub use door::*;
 pub use flag::*;
+pub use footprint_line::*;
 pub use line_pathfinder::*;
 pub use line_validator::*;
 pub use loc_angle::*;
 pub use loc_layer::*;
 pub use loc_shape::*;
+pub use loc_type::*;
+pub use naive_movement::*;
 pub use naive_pathfinder::*;
 pub use pathfinder::*;
+pub use ray_result::*;
 pub use reach::*;
+pub use safe_spot::*;
 pub use step_validator::*;
 pub use utils::*;
+pub use visibility::*;
 
+pub mod area_of_effect;
 pub mod collision;
 pub mod coord_grid;
+pub mod door;
 pub mod flag;
+pub mod footprint_line;
 pub mod line;
 pub mod line_pathfinder;
 pub mod line_validator;
 pub mod loc_angle;
 pub mod loc_layer;
 pub mod loc_shape;
+pub mod loc_type;
+pub mod naive_movement;
 pub mod naive_pathfinder;
 pub mod pathfinder;
+pub mod ray_result;
 pub mod reach;
+pub mod safe_spot;
 pub mod step_validator;
 pub mod utils;
+pub mod visibility;
@@ -0,0 +1,142 @@
+use crate::rsmod::collision::collision::CollisionFlagReader;
+use crate::rsmod::coord_grid::{CoordGrid, CoordOutOfRange};
+use crate::rsmod::line_validator::has_line_of_sight;
+use crate::rsmod::reach::rectangle_boundary::collides;
+use crate::rsmod::visibility::{visibility_map, VisibilityMap};
+
+/**
+ * The shape of an area of effect around its centre tile. Distances are measured between tile centres.
+ */
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum AreaShape {
+    /** Every tile within `radius` tiles on both axes. */
+    Chebyshev { radius: i32 },
+    /** Every tile within a circle of `radius` tiles. */
+    Euclidean { radius: i32 },
+    /**
+     * Every tile within a circle of `radius` tiles whose direction from the centre is at most
+     * `half_angle` degrees away from `direction_x`, `direction_z`. The centre tile is always included.
+     */
+    Cone { radius: i32, direction_x: i32, direction_z: i32, half_angle: f64 },
+}
+
+impl AreaShape {
+    #[inline(always)]
+    pub fn radius(&self) -> i32 {
+        return match *self {
+            AreaShape::Chebyshev { radius } => radius,
+            AreaShape::Euclidean { radius } => radius,
+            AreaShape::Cone { radius, .. } => radius,
+        }.max(0);
+    }
+
+    /**
+     * Returns true if the tile `delta_x`, `delta_z` away from the centre is part of the shape.
+     */
+    #[inline(always)]
+    pub fn contains(&self, delta_x: i32, delta_z: i32) -> bool {
+        return match *self {
+            AreaShape::Chebyshev { radius } => delta_x.abs() <= radius && delta_z.abs() <= radius,
+            AreaShape::Euclidean { radius } => within_circle(delta_x, delta_z, radius),
+            AreaShape::Cone { radius, direction_x, direction_z, half_angle } => {
+                if !within_circle(delta_x, delta_z, radius) {
+                    return false;
+                }
+                if delta_x == 0 && delta_z == 0 {
+                    return true;
+                }
+                let length: f64 = ((delta_x * delta_x + delta_z * delta_z) as f64).sqrt();
+                let direction_length: f64 = ((direction_x * direction_x + direction_z * direction_z) as f64).sqrt();
+                if direction_length == 0.0 {
+                    return false;
+                }
+                let dot: f64 = (delta_x * direction_x + delta_z * direction_z) as f64;
+                // a small epsilon keeps tiles exactly on the edge of the cone inside.
+                dot >= length * direction_length * half_angle.to_radians().cos() - 1e-9
+            }
+        };
+    }
+}
+
+#[inline(always)]
+fn within_circle(delta_x: i32, delta_z: i32, radius: i32) -> bool {
+    return radius >= 0 && delta_x * delta_x + delta_z * delta_z <= radius * radius;
+}
+
+/**
+ * An entity that can be caught in an area of effect, `x`, `z` being its south-west tile.
+ */
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct AreaTarget {
+    pub x: i32,
+    pub z: i32,
+    pub width: u8,
+    pub height: u8,
+}
+
+/**
+ * Returns every tile of `shape` around `center_x`, `center_z` that the centre has line of sight to,
+ * packed like [CoordGrid], row by row from the south-west.
+ * Line of sight is the same as [has_line_of_sight] from the centre to each tile.
+ * Returns [CoordOutOfRange] if a tile can't be packed into a [CoordGrid].
+ */
+pub unsafe fn area_of_effect(
+    flags: &impl CollisionFlagReader,
+    y: i32,
+    center_x: i32,
+    center_z: i32,
+    shape: &AreaShape,
+    extra_flag: u32,
+) -> Result<Vec<u32>, CoordOutOfRange> {
+    let visible: VisibilityMap = visibility_map(flags, y, center_x, center_z, 1, shape.radius(), extra_flag);
+    return visible
+        .visible_tiles()
+        .filter(|&(x, z)| shape.contains(x - center_x, z - center_z))
+        .map(|(x, z)| CoordGrid::try_new(y, x, z).map(|coord| coord.packed))
+        .collect();
+}
+
+/**
+ * Returns the indices of the `targets` caught in `shape` around `center_x`, `center_z`.
+ * A target is caught when its rectangle overlaps a tile of the shape and the centre has
+ * line of sight to the target, as [has_line_of_sight] to the whole rectangle.
+ */
+pub unsafe fn area_of_effect_targets(
+    flags: &impl CollisionFlagReader,
+    y: i32,
+    center_x: i32,
+    center_z: i32,
+    shape: &AreaShape,
+    targets: &[AreaTarget],
+    extra_flag: u32,
+) -> Vec<usize> {
+    let radius: i32 = shape.radius();
+    let length: i32 = radius * 2 + 1;
+    let mut caught: Vec<usize> = vec![];
+    for (index, target) in targets.iter().enumerate() {
+        // quick rejection against the square around the shape, which only fits a u8 up to a radius of 127.
+        if length <= u8::MAX as i32
+            && !collides(center_x - radius, center_z - radius, target.x, target.z, length as u8, length as u8, target.width, target.height) {
+            continue;
+        }
+        if !overlaps_shape(center_x, center_z, shape, target) {
+            continue;
+        }
+        if has_line_of_sight(flags, y, center_x, center_z, target.x, target.z, 1, 1, target.width, target.height, extra_flag) {
+            caught.push(index);
+        }
+    }
+    return caught;
+}
+
+#[inline(always)]
+fn overlaps_shape(center_x: i32, center_z: i32, shape: &AreaShape, target: &AreaTarget) -> bool {
+    for z in target.z..target.z + target.height.max(1) as i32 {
+        for x in target.x..target.x + target.width.max(1) as i32 {
+            if shape.contains(x - center_x, z - center_z) {
+                return true;
+            }
+        }
+    }
+    return false;
+}
@@ -1,13 +1,60 @@
+use std::collections::HashMap;
+
+use crate::rsmod::{CoordOutOfRange, WideCoordGrid};
+use crate::rsmod::collision::collision_journal::CollisionJournal;
 use crate::rsmod::collision_flag::CollisionFlag;
 
+/**
+ * Read access to collision flags, so the pathfinder, step validation, reach strategies
+ * and line checks can run against anything that resolves a tile to its flags.
+ */
+pub trait CollisionFlagReader {
+    /**
+     * Returns the flags of the tile, or [CollisionFlag::NULL] if its zone is not allocated.
+     */
+    unsafe fn get(&self, x: i32, z: i32, y: i32) -> u32;
+
+    unsafe fn is_zone_allocated(&self, x: i32, z: i32, y: i32) -> bool;
+
+    /**
+     * Returns true if the tile has any of `masks` set. Tiles in unallocated zones are never flagged.
+     */
+    unsafe fn is_flagged(&self, x: i32, z: i32, y: i32, masks: u32) -> bool;
+}
+
+/**
+ * Where the zones of a sparse map live: every 64x64 mapsquare gets a run of
+ * [CollisionFlagMap::MAPSQUARE_ZONE_COUNT] zone slots the first time it is written.
+ */
+#[derive(Clone, Default)]
+struct SparseLayout {
+    slots: HashMap<(i32, i32), usize>,
+    // the mapsquare of every run of slots, in allocation order.
+    mapsquares: Vec<(i32, i32)>,
+}
+
 #[derive(Clone)]
 pub struct CollisionFlagMap {
     pub flags: Vec<Option<Box<[u32; 8 * 8]>>>,
+    // `None` for the dense layout, where `flags` is indexed by [CollisionFlagMap::zone_index].
+    sparse: Option<Box<SparseLayout>>,
+    // writes straight to `flags` bypass the journal.
+    journal: Option<Box<CollisionJournal>>,
+    // one bit per dense mapsquare a zone was ever allocated in, so iterating the zones skips the
+    // empty ones. Zones written straight to `flags` are only seen in mapsquares marked here.
+    dense_mapsquares: Vec<u64>,
 }
 
 impl CollisionFlagMap {
     const ZONE_TILE_COUNT: usize = 8 * 8;
     const TOTAL_ZONE_COUNT: usize = 256 * 256 * 4 * CollisionFlagMap::ZONE_TILE_COUNT;
+    const MAPSQUARE_ZONE_COUNT: usize = 8 * 8 * 4;
+    const DENSE_MAPSQUARE_WORDS: usize = 256 * 256 / 64;
+
+    /**
+     * The dense layout covers x and z in `0 until DENSE_COORD_LIMIT`, the same range as [CoordGrid].
+     */
+    pub const DENSE_COORD_LIMIT: i32 = 0x4000;
 
     #[inline(always)]
     pub fn zone_index(x: i32, z: i32, y: i32) -> usize {
@@ -19,78 +66,390 @@ impl CollisionFlagMap {
         return ((x & 0x7) | ((z & 0x7) << 3)) as usize;
     }
 
+    /**
+     * Returns a key unique to the zone for every x and z, unlike [CollisionFlagMap::zone_index]
+     * which only covers the dense layout.
+     */
+    #[inline(always)]
+    pub fn zone_key(x: i32, z: i32, y: i32) -> u64 {
+        return ((x >> 3) as u32 as u64 & 0x1fffffff)
+            | (((z >> 3) as u32 as u64 & 0x1fffffff) << 29)
+            | (((y & 0x3) as u64) << 58);
+    }
+
+    /**
+     * Creates a map over a fixed 16384x16384 area, backed by one table slot per zone.
+     * Coordinates outside of it are never allocated and can't be written.
+     */
     #[inline(always)]
     pub fn new() -> CollisionFlagMap {
         return CollisionFlagMap {
             flags: vec![None; CollisionFlagMap::TOTAL_ZONE_COUNT],
+            sparse: None,
+            journal: None,
+            dense_mapsquares: vec![0; CollisionFlagMap::DENSE_MAPSQUARE_WORDS],
+        };
+    }
+
+    /**
+     * Creates a map over any x and z a [WideCoordGrid] can hold that only takes room for the mapsquares
+     * written to, e.g. for instances at large offsets. Reads cost an extra lookup per mapsquare.
+     */
+    #[inline(always)]
+    pub fn sparse() -> CollisionFlagMap {
+        return CollisionFlagMap {
+            flags: vec![],
+            sparse: Some(Box::default()),
+            journal: None,
+            dense_mapsquares: vec![],
         };
     }
 
+    #[inline(always)]
+    pub fn is_sparse(&self) -> bool {
+        return self.sparse.is_some();
+    }
+
+    /**
+     * Returns true if the tile can be stored in this map.
+     */
+    #[inline(always)]
+    pub fn contains(&self, x: i32, z: i32, y: i32) -> bool {
+        if self.sparse.is_some() {
+            // kept to what a [WideCoordGrid] holds so the journal can report every changed zone.
+            return (WideCoordGrid::MIN_COORD..=WideCoordGrid::MAX_COORD).contains(&x)
+                && (WideCoordGrid::MIN_COORD..=WideCoordGrid::MAX_COORD).contains(&z)
+                && (y as u32) < 4;
+        }
+        return (x as u32) < CollisionFlagMap::DENSE_COORD_LIMIT as u32
+            && (z as u32) < CollisionFlagMap::DENSE_COORD_LIMIT as u32
+            && (y as u32) < 4;
+    }
+
+    /**
+     * Returns the tiles of the zone, or `None` if it is not allocated.
+     */
+    #[inline(always)]
+    pub fn zone(&self, x: i32, z: i32, y: i32) -> Option<&[u32; 64]> {
+        return self.zone_slot(x, z, y).and_then(|slot| self.flags[slot].as_deref());
+    }
+
     #[rustfmt::skip]
     #[inline(always)]
     pub unsafe fn get(&self, x: i32, z: i32, y: i32) -> u32 {
-        if let Some(ref flags) = *self.flags.as_ptr().add(CollisionFlagMap::zone_index(x, z, y)) {
-            return *flags.as_ptr().add(CollisionFlagMap::tile_index(x, z));
+        if let Some(slot) = self.zone_slot(x, z, y) {
+            if let Some(ref flags) = *self.flags.as_ptr().add(slot) {
+                return *flags.as_ptr().add(CollisionFlagMap::tile_index(x, z));
+            }
         }
         return CollisionFlag::NULL as u32
     }
 
+    /**
+     * # Panics
+     * If the tile is outside of the map, see [CollisionFlagMap::try_set].
+     */
     #[inline(always)]
     pub unsafe fn set(&mut self, x: i32, z: i32, y: i32, mask: u32) {
+        self.try_set(x, z, y, mask).unwrap();
+    }
+
+    /**
+     * # Panics
+     * If the tile is outside of the map, see [CollisionFlagMap::try_add].
+     */
+    #[inline(always)]
+    pub unsafe fn add(&mut self, x: i32, z: i32, y: i32, mask: u32) {
+        self.try_add(x, z, y, mask).unwrap();
+    }
+
+    /**
+     * # Panics
+     * If the tile is outside of the map, see [CollisionFlagMap::try_remove].
+     */
+    #[inline(always)]
+    pub unsafe fn remove(&mut self, x: i32, z: i32, y: i32, mask: u32) {
+        self.try_remove(x, z, y, mask).unwrap();
+    }
+
+    #[inline(always)]
+    pub unsafe fn try_set(&mut self, x: i32, z: i32, y: i32, mask: u32) -> Result<(), CoordOutOfRange> {
+        if self.journal.is_some() {
+            return self.write_journaled(x, z, y, |_| mask);
+        }
         *self
-            .allocate_if_absent_return(CollisionFlagMap::zone_index(x, z, y))
+            .allocate_if_absent_return(x, z, y)?
             .as_mut_ptr()
             .add(CollisionFlagMap::tile_index(x, z)) = mask;
+        return Ok(());
     }
 
     #[inline(always)]
-    pub unsafe fn add(&mut self, x: i32, z: i32, y: i32, mask: u32) {
+    pub unsafe fn try_add(&mut self, x: i32, z: i32, y: i32, mask: u32) -> Result<(), CoordOutOfRange> {
+        if self.journal.is_some() {
+            return self.write_journaled(x, z, y, |flags| flags | mask);
+        }
         *self
-            .allocate_if_absent_return(CollisionFlagMap::zone_index(x, z, y))
+            .allocate_if_absent_return(x, z, y)?
             .as_mut_ptr()
             .add(CollisionFlagMap::tile_index(x, z)) |= mask;
+        return Ok(());
     }
 
     #[inline(always)]
-    pub unsafe fn remove(&mut self, x: i32, z: i32, y: i32, mask: u32) {
+    pub unsafe fn try_remove(&mut self, x: i32, z: i32, y: i32, mask: u32) -> Result<(), CoordOutOfRange> {
+        if self.journal.is_some() {
+            return self.write_journaled(x, z, y, |flags| flags & !mask);
+        }
         *self
-            .allocate_if_absent_return(CollisionFlagMap::zone_index(x, z, y))
+            .allocate_if_absent_return(x, z, y)?
             .as_mut_ptr()
             .add(CollisionFlagMap::tile_index(x, z)) &= !mask;
+        return Ok(());
     }
 
+    /**
+     * # Panics
+     * If the tile is outside of the map, see [CollisionFlagMap::try_allocate_if_absent].
+     */
     #[inline(always)]
     pub unsafe fn allocate_if_absent(&mut self, x: i32, z: i32, y: i32) {
-        self.allocate_if_absent_return(CollisionFlagMap::zone_index(x, z, y));
+        self.try_allocate_if_absent(x, z, y).unwrap();
     }
 
     #[inline(always)]
-    unsafe fn allocate_if_absent_return(&mut self, zone_idx: usize) -> &mut [u32; 64] {
-        return (*self.flags.as_mut_ptr().add(zone_idx)).get_or_insert(Box::new(
+    pub unsafe fn try_allocate_if_absent(&mut self, x: i32, z: i32, y: i32) -> Result<(), CoordOutOfRange> {
+        let allocated: bool = self.is_zone_allocated(x, z, y);
+        self.allocate_if_absent_return(x, z, y)?;
+        if let Some(journal) = self.journal.as_deref_mut() {
+            if !allocated {
+                journal.record_zone(x, z, y, None, Some(&[CollisionFlag::OPEN as u32; 64]));
+            }
+        }
+        return Ok(());
+    }
+
+    #[inline(always)]
+    pub(crate) unsafe fn allocate_if_absent_return(
+        &mut self,
+        x: i32,
+        z: i32,
+        y: i32,
+    ) -> Result<&mut [u32; 64], CoordOutOfRange> {
+        let slot: usize = self.zone_slot_or_insert(x, z, y)?;
+        return Ok((*self.flags.as_mut_ptr().add(slot)).get_or_insert(Box::new(
             [CollisionFlag::OPEN as u32; CollisionFlagMap::ZONE_TILE_COUNT],
-        ));
+        )));
+    }
+
+    /**
+     * Replaces the zone, returning what it held before. `None` deallocates it.
+     */
+    #[inline(always)]
+    pub(crate) unsafe fn replace_zone(
+        &mut self,
+        x: i32,
+        z: i32,
+        y: i32,
+        zone: Option<Box<[u32; 64]>>,
+    ) -> Result<Option<Box<[u32; 64]>>, CoordOutOfRange> {
+        let slot: usize = match zone {
+            None => match self.zone_slot(x, z, y) {
+                None => return Ok(None),
+                Some(slot) => slot,
+            },
+            Some(_) => self.zone_slot_or_insert(x, z, y)?,
+        };
+        let before: Option<Box<[u32; 64]>> = std::mem::replace(&mut *self.flags.as_mut_ptr().add(slot), zone);
+        if let Some(journal) = self.journal.as_deref_mut() {
+            journal.record_zone(x, z, y, before.as_deref(), (*self.flags.as_ptr().add(slot)).as_deref());
+        }
+        return Ok(before);
     }
 
-    #[rustfmt::skip]
     #[inline(always)]
     pub unsafe fn deallocate_if_present(&mut self, x: i32, z: i32, y: i32) {
-        *self.flags.as_mut_ptr().add(CollisionFlagMap::zone_index(x, z, y)) = None;
+        // nothing outside of the map is ever allocated.
+        let _ = self.replace_zone(x, z, y, None);
     }
 
-    #[rustfmt::skip]
     #[inline(always)]
     pub unsafe fn is_zone_allocated(&self, x: i32, z: i32, y: i32) -> bool {
-        return (*self.flags.as_ptr().add(CollisionFlagMap::zone_index(x, z, y))).is_some();
+        return match self.zone_slot(x, z, y) {
+            None => false,
+            Some(slot) => (*self.flags.as_ptr().add(slot)).is_some(),
+        };
     }
 
     #[rustfmt::skip]
     #[inline(always)]
     pub unsafe fn is_flagged(&self, x: i32, z: i32, y: i32, masks: u32) -> bool {
-        return match &*self.flags.as_ptr().add(CollisionFlagMap::zone_index(x, z, y)) {
+        return match self.zone_slot(x, z, y) {
             None => false,
-            Some(flags) => {
-                *flags.as_ptr().add(CollisionFlagMap::tile_index(x, z)) & masks != CollisionFlag::OPEN as u32
+            Some(slot) => match &*self.flags.as_ptr().add(slot) {
+                None => false,
+                Some(flags) => {
+                    *flags.as_ptr().add(CollisionFlagMap::tile_index(x, z)) & masks != CollisionFlag::OPEN as u32
+                }
+            },
+        };
+    }
+
+    /**
+     * Returns the coords of the south-west tile of the zone stored at `slot` of `flags`.
+     */
+    #[inline(always)]
+    pub fn slot_coord(&self, slot: usize) -> (i32, i32, i32) {
+        return match &self.sparse {
+            None => CollisionFlagMap::zone_coord(slot),
+            Some(sparse) => {
+                let (mapsquare_x, mapsquare_z): (i32, i32) = sparse.mapsquares[slot / CollisionFlagMap::MAPSQUARE_ZONE_COUNT];
+                let local: i32 = (slot % CollisionFlagMap::MAPSQUARE_ZONE_COUNT) as i32;
+                ((mapsquare_x << 6) | ((local & 0x7) << 3), (mapsquare_z << 6) | (((local >> 3) & 0x7) << 3), local >> 6)
             }
         };
     }
+
+    #[inline(always)]
+    fn zone_slot(&self, x: i32, z: i32, y: i32) -> Option<usize> {
+        if !self.contains(x, z, y) {
+            return None;
+        }
+        return match &self.sparse {
+            None => Some(CollisionFlagMap::zone_index(x, z, y)),
+            Some(sparse) => sparse
+                .slots
+                .get(&(x >> 6, z >> 6))
+                .map(|&first| first + CollisionFlagMap::mapsquare_zone_index(x, z, y)),
+        };
+    }
+
+    #[inline(always)]
+    fn zone_slot_or_insert(&mut self, x: i32, z: i32, y: i32) -> Result<usize, CoordOutOfRange> {
+        if !self.contains(x, z, y) {
+            return Err(CoordOutOfRange { x, z, y });
+        }
+        return Ok(match &mut self.sparse {
+            None => {
+                let mapsquare: usize = ((x >> 6) | ((z >> 6) << 8)) as usize;
+                self.dense_mapsquares[mapsquare >> 6] |= 1 << (mapsquare & 0x3f);
+                CollisionFlagMap::zone_index(x, z, y)
+            }
+            Some(sparse) => {
+                let flags: &mut Vec<Option<Box<[u32; 64]>>> = &mut self.flags;
+                let first: usize = *sparse.slots.entry((x >> 6, z >> 6)).or_insert_with(|| {
+                    sparse.mapsquares.push((x >> 6, z >> 6));
+                    let first: usize = flags.len();
+                    flags.resize(first + CollisionFlagMap::MAPSQUARE_ZONE_COUNT, None);
+                    return first;
+                });
+                first + CollisionFlagMap::mapsquare_zone_index(x, z, y)
+            }
+        });
+    }
+
+    /**
+     * Returns every slot that can hold an allocated zone, in slot order. A dense map only walks
+     * the mapsquares it ever allocated a zone in rather than all of its 16.7M slots.
+     */
+    pub(crate) fn allocated_slots(&self) -> Box<dyn Iterator<Item = usize> + '_> {
+        if self.sparse.is_some() {
+            return Box::new(0..self.flags.len());
+        }
+        return Box::new((0..4usize).flat_map(move |y| {
+            return (0..2048usize).flat_map(move |zone_z| {
+                // the 256 mapsquares of a row of zones span 4 words.
+                let row: &[u64] = &self.dense_mapsquares[(zone_z >> 3) * 4..(zone_z >> 3) * 4 + 4];
+                return row
+                    .iter()
+                    .enumerate()
+                    .flat_map(|(word, &bits)| {
+                        return std::iter::successors(Some(bits), |&bits| Some(bits & bits.wrapping_sub(1)))
+                            .take_while(|&bits| bits != 0)
+                            .map(move |bits| (word << 6) | bits.trailing_zeros() as usize);
+                    })
+                    .flat_map(move |mapsquare_x| {
+                        return (mapsquare_x << 3..(mapsquare_x << 3) + 8)
+                            .map(move |zone_x| zone_x | (zone_z << 11) | (y << 22));
+                    });
+            });
+        }));
+    }
+
+    #[inline(always)]
+    fn mapsquare_zone_index(x: i32, z: i32, y: i32) -> usize {
+        return (((x >> 3) & 0x7) | (((z >> 3) & 0x7) << 3) | ((y & 0x3) << 6)) as usize;
+    }
+
+    /**
+     * Starts journaling changes, see [CollisionJournal]. `record_tiles` also keeps every changed
+     * tile with its masks instead of only the changed zones. Does nothing if already enabled.
+     */
+    #[inline(always)]
+    pub fn enable_journal(&mut self, record_tiles: bool) {
+        if self.journal.is_none() {
+            self.journal = Some(Box::new(CollisionJournal::new(record_tiles)));
+        }
+    }
+
+    #[inline(always)]
+    pub fn disable_journal(&mut self) {
+        self.journal = None;
+    }
+
+    #[inline(always)]
+    pub fn journal(&self) -> Option<&CollisionJournal> {
+        return self.journal.as_deref();
+    }
+
+    #[inline(always)]
+    pub fn journal_mut(&mut self) -> Option<&mut CollisionJournal> {
+        return self.journal.as_deref_mut();
+    }
+
+    /**
+     * Returns the version of the zone, or 0 if it never changed or the journal is disabled.
+     */
+    #[inline(always)]
+    pub fn zone_version(&self, x: i32, z: i32, y: i32) -> u32 {
+        return self.journal.as_ref().map_or(0, |journal| journal.zone_version(x, z, y));
+    }
+
+    #[inline(always)]
+    unsafe fn write_journaled(
+        &mut self,
+        x: i32,
+        z: i32,
+        y: i32,
+        write: impl Fn(u32) -> u32,
+    ) -> Result<(), CoordOutOfRange> {
+        self.try_allocate_if_absent(x, z, y)?;
+        let tile: *mut u32 = self
+            .allocate_if_absent_return(x, z, y)?
+            .as_mut_ptr()
+            .add(CollisionFlagMap::tile_index(x, z));
+        let before: u32 = *tile;
+        *tile = write(before);
+        if let Some(journal) = self.journal.as_deref_mut() {
+            journal.record_tile(x, z, y, before, *tile);
+        }
+        return Ok(());
+    }
+}
+
+impl CollisionFlagReader for CollisionFlagMap {
+    #[inline(always)]
+    unsafe fn get(&self, x: i32, z: i32, y: i32) -> u32 {
+        return CollisionFlagMap::get(self, x, z, y);
+    }
+
+    #[inline(always)]
+    unsafe fn is_zone_allocated(&self, x: i32, z: i32, y: i32) -> bool {
+        return CollisionFlagMap::is_zone_allocated(self, x, z, y);
+    }
+
+    #[inline(always)]
+    unsafe fn is_flagged(&self, x: i32, z: i32, y: i32, masks: u32) -> bool {
+        return CollisionFlagMap::is_flagged(self, x, z, y, masks);
+    }
 }
@@ -0,0 +1,421 @@
+use crate::rsmod::{CoordOutOfRange, LocAngle, LocLayer, LocShape};
+use crate::rsmod::collision::collision::CollisionFlagMap;
+use crate::rsmod::collision_flag::CollisionFlag;
+use crate::rsmod::rotation::rotate;
+
+/**
+ * Why [CollisionFlagWriter::change_wall] refused a change. Nothing is changed either way.
+ */
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum ChangeWallError {
+    OutOfRange(CoordOutOfRange),
+    /** The shape is outside of the wall layer. */
+    NotAWall(LocShape),
+}
+
+impl From<CoordOutOfRange> for ChangeWallError {
+    fn from(error: CoordOutOfRange) -> ChangeWallError {
+        return ChangeWallError::OutOfRange(error);
+    }
+}
+
+impl std::fmt::Display for ChangeWallError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        return match self {
+            ChangeWallError::OutOfRange(error) => error.fmt(f),
+            ChangeWallError::NotAWall(shape) => write!(f, "not a wall shape: {}", *shape as i8),
+        };
+    }
+}
+
+impl std::error::Error for ChangeWallError {}
+
+/**
+ * A collision map the change helpers can write to. Each helper either applies fully or,
+ * when any of its tiles is out of range, changes nothing.
+ */
+pub trait CollisionFlagWriter {
+    fn contains(&self, x: i32, z: i32, y: i32) -> bool;
+
+    unsafe fn try_add(&mut self, x: i32, z: i32, y: i32, mask: u32) -> Result<(), CoordOutOfRange>;
+
+    unsafe fn try_remove(&mut self, x: i32, z: i32, y: i32, mask: u32) -> Result<(), CoordOutOfRange>;
+
+    #[inline(always)]
+    unsafe fn change_floor(&mut self, x: i32, z: i32, y: i32, add: bool) -> Result<(), CoordOutOfRange> {
+        return change(self, x, z, y, CollisionFlag::FLOOR as u32, add);
+    }
+
+    #[inline(always)]
+    unsafe fn change_roof(&mut self, x: i32, z: i32, y: i32, add: bool) -> Result<(), CoordOutOfRange> {
+        return change(self, x, z, y, CollisionFlag::ROOF as u32, add);
+    }
+
+    #[inline(always)]
+    unsafe fn change_npc(&mut self, x: i32, z: i32, y: i32, size: i32, add: bool) -> Result<(), CoordOutOfRange> {
+        return change_area(self, x, z, y, size, size, CollisionFlag::NPC as u32, add);
+    }
+
+    #[inline(always)]
+    unsafe fn change_player(&mut self, x: i32, z: i32, y: i32, size: i32, add: bool) -> Result<(), CoordOutOfRange> {
+        return change_area(self, x, z, y, size, size, CollisionFlag::PLAYER as u32, add);
+    }
+
+    /**
+     * Flags the (already rotated) `width` x `length` footprint of a ground loc.
+     */
+    #[inline(always)]
+    unsafe fn change_loc(
+        &mut self,
+        x: i32,
+        z: i32,
+        y: i32,
+        width: i32,
+        length: i32,
+        blockrange: bool,
+        breakroutefinding: bool,
+        add: bool,
+    ) -> Result<(), CoordOutOfRange> {
+        let mut mask: u32 = CollisionFlag::LOC as u32;
+        if blockrange {
+            mask |= CollisionFlag::LOC_PROJ_BLOCKER as u32;
+        }
+        if breakroutefinding {
+            mask |= CollisionFlag::LOC_ROUTE_BLOCKER as u32;
+        }
+        return change_area(self, x, z, y, width, length, mask, add);
+    }
+
+    /**
+     * Applies the collision of a loc by its shape, picking the placement from the layer
+     * the shape belongs to. `width` and `length` are the unrotated loc dimensions.
+     * Wall decoration does not block movement so it is a no-op.
+     */
+    #[inline(always)]
+    unsafe fn change_loc_shape(
+        &mut self,
+        x: i32,
+        z: i32,
+        y: i32,
+        width: u8,
+        length: u8,
+        angle: u8,
+        shape: LocShape,
+        blockrange: bool,
+        breakroutefinding: bool,
+        add: bool,
+    ) -> Result<(), CoordOutOfRange> {
+        return match shape.layer() {
+            LocLayer::WALL => {
+                change_wall_layer(self, x, z, y, angle, shape, blockrange, breakroutefinding, add)
+            }
+            LocLayer::WALL_DECOR => Ok(()),
+            LocLayer::GROUND => self.change_loc(
+                x,
+                z,
+                y,
+                rotate(angle, width, length) as i32,
+                rotate(angle, length, width) as i32,
+                blockrange,
+                breakroutefinding,
+                add,
+            ),
+            LocLayer::GROUND_DECOR => {
+                change(self, x, z, y, CollisionFlag::FLOOR_DECORATION as u32, add)
+            }
+        };
+    }
+
+    /**
+     * Applies the collision of a wall layer loc, refusing shapes outside of the wall layer.
+     */
+    #[inline(always)]
+    unsafe fn change_wall(
+        &mut self,
+        x: i32,
+        z: i32,
+        y: i32,
+        angle: u8,
+        shape: LocShape,
+        blockrange: bool,
+        breakroutefinding: bool,
+        add: bool,
+    ) -> Result<(), ChangeWallError> {
+        if shape.layer() != LocLayer::WALL {
+            return Err(ChangeWallError::NotAWall(shape));
+        }
+        change_wall_layer(self, x, z, y, angle, shape, blockrange, breakroutefinding, add)?;
+        return Ok(());
+    }
+
+    #[inline(always)]
+    unsafe fn change_wall_straight(
+        &mut self,
+        x: i32,
+        z: i32,
+        y: i32,
+        angle: u8,
+        blockrange: bool,
+        breakroutefinding: bool,
+        add: bool,
+    ) -> Result<(), CoordOutOfRange> {
+        let west: u32 = wall_flag(
+            CollisionFlag::WALL_WEST,
+            CollisionFlag::WALL_WEST_PROJ_BLOCKER,
+            CollisionFlag::WALL_WEST_ROUTE_BLOCKER,
+            blockrange,
+            breakroutefinding,
+        );
+        let east: u32 = wall_flag(
+            CollisionFlag::WALL_EAST,
+            CollisionFlag::WALL_EAST_PROJ_BLOCKER,
+            CollisionFlag::WALL_EAST_ROUTE_BLOCKER,
+            blockrange,
+            breakroutefinding,
+        );
+        let north: u32 = wall_flag(
+            CollisionFlag::WALL_NORTH,
+            CollisionFlag::WALL_NORTH_PROJ_BLOCKER,
+            CollisionFlag::WALL_NORTH_ROUTE_BLOCKER,
+            blockrange,
+            breakroutefinding,
+        );
+        let south: u32 = wall_flag(
+            CollisionFlag::WALL_SOUTH,
+            CollisionFlag::WALL_SOUTH_PROJ_BLOCKER,
+            CollisionFlag::WALL_SOUTH_ROUTE_BLOCKER,
+            blockrange,
+            breakroutefinding,
+        );
+
+        return match LocAngle::from(angle) {
+            LocAngle::WEST => change_tiles(self, y, &[(x, z, west), (x - 1, z, east)], add),
+            LocAngle::NORTH => change_tiles(self, y, &[(x, z, north), (x, z + 1, south)], add),
+            LocAngle::EAST => change_tiles(self, y, &[(x, z, east), (x + 1, z, west)], add),
+            LocAngle::SOUTH => change_tiles(self, y, &[(x, z, south), (x, z - 1, north)], add),
+        };
+    }
+
+    #[inline(always)]
+    unsafe fn change_wall_corner(
+        &mut self,
+        x: i32,
+        z: i32,
+        y: i32,
+        angle: u8,
+        blockrange: bool,
+        breakroutefinding: bool,
+        add: bool,
+    ) -> Result<(), CoordOutOfRange> {
+        let north_west: u32 = wall_flag(
+            CollisionFlag::WALL_NORTH_WEST,
+            CollisionFlag::WALL_NORTH_WEST_PROJ_BLOCKER,
+            CollisionFlag::WALL_NORTH_WEST_ROUTE_BLOCKER,
+            blockrange,
+            breakroutefinding,
+        );
+        let south_east: u32 = wall_flag(
+            CollisionFlag::WALL_SOUTH_EAST,
+            CollisionFlag::WALL_SOUTH_EAST_PROJ_BLOCKER,
+            CollisionFlag::WALL_SOUTH_EAST_ROUTE_BLOCKER,
+            blockrange,
+            breakroutefinding,
+        );
+        let north_east: u32 = wall_flag(
+            CollisionFlag::WALL_NORTH_EAST,
+            CollisionFlag::WALL_NORTH_EAST_PROJ_BLOCKER,
+            CollisionFlag::WALL_NORTH_EAST_ROUTE_BLOCKER,
+            blockrange,
+            breakroutefinding,
+        );
+        let south_west: u32 = wall_flag(
+            CollisionFlag::WALL_SOUTH_WEST,
+            CollisionFlag::WALL_SOUTH_WEST_PROJ_BLOCKER,
+            CollisionFlag::WALL_SOUTH_WEST_ROUTE_BLOCKER,
+            blockrange,
+            breakroutefinding,
+        );
+
+        return match LocAngle::from(angle) {
+            LocAngle::WEST => change_tiles(self, y, &[(x, z, north_west), (x - 1, z + 1, south_east)], add),
+            LocAngle::NORTH => change_tiles(self, y, &[(x, z, north_east), (x + 1, z + 1, south_west)], add),
+            LocAngle::EAST => change_tiles(self, y, &[(x, z, south_east), (x + 1, z - 1, north_west)], add),
+            LocAngle::SOUTH => change_tiles(self, y, &[(x, z, south_west), (x - 1, z - 1, north_east)], add),
+        };
+    }
+
+    #[inline(always)]
+    unsafe fn change_wall_l(
+        &mut self,
+        x: i32,
+        z: i32,
+        y: i32,
+        angle: u8,
+        blockrange: bool,
+        breakroutefinding: bool,
+        add: bool,
+    ) -> Result<(), CoordOutOfRange> {
+        let west: u32 = wall_flag(
+            CollisionFlag::WALL_WEST,
+            CollisionFlag::WALL_WEST_PROJ_BLOCKER,
+            CollisionFlag::WALL_WEST_ROUTE_BLOCKER,
+            blockrange,
+            breakroutefinding,
+        );
+        let east: u32 = wall_flag(
+            CollisionFlag::WALL_EAST,
+            CollisionFlag::WALL_EAST_PROJ_BLOCKER,
+            CollisionFlag::WALL_EAST_ROUTE_BLOCKER,
+            blockrange,
+            breakroutefinding,
+        );
+        let north: u32 = wall_flag(
+            CollisionFlag::WALL_NORTH,
+            CollisionFlag::WALL_NORTH_PROJ_BLOCKER,
+            CollisionFlag::WALL_NORTH_ROUTE_BLOCKER,
+            blockrange,
+            breakroutefinding,
+        );
+        let south: u32 = wall_flag(
+            CollisionFlag::WALL_SOUTH,
+            CollisionFlag::WALL_SOUTH_PROJ_BLOCKER,
+            CollisionFlag::WALL_SOUTH_ROUTE_BLOCKER,
+            blockrange,
+            breakroutefinding,
+        );
+
+        return match LocAngle::from(angle) {
+            LocAngle::WEST => change_tiles(self, y, &[(x, z, north | west), (x - 1, z, east), (x, z + 1, south)], add),
+            LocAngle::NORTH => change_tiles(self, y, &[(x, z, north | east), (x, z + 1, south), (x + 1, z, west)], add),
+            LocAngle::EAST => change_tiles(self, y, &[(x, z, south | east), (x + 1, z, west), (x, z - 1, north)], add),
+            LocAngle::SOUTH => change_tiles(self, y, &[(x, z, south | west), (x, z - 1, north), (x - 1, z, east)], add),
+        };
+    }
+}
+
+impl CollisionFlagWriter for CollisionFlagMap {
+    #[inline(always)]
+    fn contains(&self, x: i32, z: i32, y: i32) -> bool {
+        return CollisionFlagMap::contains(self, x, z, y);
+    }
+
+    #[inline(always)]
+    unsafe fn try_add(&mut self, x: i32, z: i32, y: i32, mask: u32) -> Result<(), CoordOutOfRange> {
+        return CollisionFlagMap::try_add(self, x, z, y, mask);
+    }
+
+    #[inline(always)]
+    unsafe fn try_remove(&mut self, x: i32, z: i32, y: i32, mask: u32) -> Result<(), CoordOutOfRange> {
+        return CollisionFlagMap::try_remove(self, x, z, y, mask);
+    }
+}
+
+#[inline(always)]
+unsafe fn change_wall_layer<W: CollisionFlagWriter + ?Sized>(
+    map: &mut W,
+    x: i32,
+    z: i32,
+    y: i32,
+    angle: u8,
+    shape: LocShape,
+    blockrange: bool,
+    breakroutefinding: bool,
+    add: bool,
+) -> Result<(), CoordOutOfRange> {
+    return match shape {
+        LocShape::WALL_STRAIGHT => {
+            map.change_wall_straight(x, z, y, angle, blockrange, breakroutefinding, add)
+        }
+        LocShape::WALL_DIAGONAL_CORNER | LocShape::WALL_SQUARE_CORNER => {
+            map.change_wall_corner(x, z, y, angle, blockrange, breakroutefinding, add)
+        }
+        LocShape::WALL_L => {
+            map.change_wall_l(x, z, y, angle, blockrange, breakroutefinding, add)
+        }
+        // the wall layer holds no other shapes.
+        _ => Ok(()),
+    };
+}
+
+#[inline(always)]
+unsafe fn change<W: CollisionFlagWriter + ?Sized>(
+    map: &mut W,
+    x: i32,
+    z: i32,
+    y: i32,
+    mask: u32,
+    add: bool,
+) -> Result<(), CoordOutOfRange> {
+    if add {
+        return map.try_add(x, z, y, mask);
+    }
+    return map.try_remove(x, z, y, mask);
+}
+
+/**
+ * Changes every `(x, z, mask)` tile, checking all of them first so a wall is never half applied.
+ */
+#[inline(always)]
+unsafe fn change_tiles<W: CollisionFlagWriter + ?Sized>(
+    map: &mut W,
+    y: i32,
+    tiles: &[(i32, i32, u32)],
+    add: bool,
+) -> Result<(), CoordOutOfRange> {
+    if let Some(&(x, z, _)) = tiles.iter().find(|&&(x, z, _)| !map.contains(x, z, y)) {
+        return Err(CoordOutOfRange { x, z, y });
+    }
+    for &(x, z, mask) in tiles {
+        change(map, x, z, y, mask, add)?;
+    }
+    return Ok(());
+}
+
+#[inline(always)]
+unsafe fn change_area<W: CollisionFlagWriter + ?Sized>(
+    map: &mut W,
+    x: i32,
+    z: i32,
+    y: i32,
+    width: i32,
+    length: i32,
+    mask: u32,
+    add: bool,
+) -> Result<(), CoordOutOfRange> {
+    if width <= 0 || length <= 0 {
+        return Ok(());
+    }
+    // the map holds a box of tiles, so holding both corners of the area means holding all of it.
+    for (corner_x, corner_z) in [(x, z), (x + width - 1, z + length - 1)] {
+        if !map.contains(corner_x, corner_z, y) {
+            return Err(CoordOutOfRange { x: corner_x, z: corner_z, y });
+        }
+    }
+    let area: i32 = width * length;
+    for index in 0..area {
+        change(map, x + (index % width), z + (index / width), y, mask, add)?;
+    }
+    return Ok(());
+}
+
+/**
+ * A wall blocks movement on its own, projectiles too when `blockrange`,
+ * and client route finding too when `breakroutefinding`.
+ */
+#[inline(always)]
+fn wall_flag(
+    flag: CollisionFlag,
+    proj: CollisionFlag,
+    route: CollisionFlag,
+    blockrange: bool,
+    breakroutefinding: bool,
+) -> u32 {
+    let mut mask: u32 = flag as u32;
+    if blockrange {
+        mask |= proj as u32;
+    }
+    if breakroutefinding {
+        mask |= route as u32;
+    }
+    return mask;
+}
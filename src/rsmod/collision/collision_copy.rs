@@ -0,0 +1,74 @@
+use crate::rsmod::CoordOutOfRange;
+use crate::rsmod::collision::collision::CollisionFlagMap;
+use crate::rsmod::rotation::rotate_collision_flags;
+
+impl CollisionFlagMap {
+    /**
+     * Copies the zone containing `src_x`, `src_z` onto the zone containing `dst_x`, `dst_z`,
+     * turned clockwise by `rotation` quarter turns. Tiles move with the zone and their wall
+     * flags are rotated with [rotate_collision_flags]. An unallocated source zone
+     * leaves the destination unallocated.
+     *
+     * Walls on the edge of a zone also flag the neighbouring zone, so whole regions
+     * should be copied for those to line up.
+     *
+     * # Panics
+     * If either zone is outside of the map, see [CollisionFlagMap::try_copy_zone].
+     */
+    #[inline(always)]
+    pub unsafe fn copy_zone(
+        &mut self,
+        src_x: i32,
+        src_z: i32,
+        src_y: i32,
+        dst_x: i32,
+        dst_z: i32,
+        dst_y: i32,
+        rotation: u8,
+    ) {
+        self.try_copy_zone(src_x, src_z, src_y, dst_x, dst_z, dst_y, rotation).unwrap();
+    }
+
+    /**
+     * Like [CollisionFlagMap::copy_zone], but leaves the map untouched if either zone is outside of it.
+     */
+    #[inline(always)]
+    pub unsafe fn try_copy_zone(
+        &mut self,
+        src_x: i32,
+        src_z: i32,
+        src_y: i32,
+        dst_x: i32,
+        dst_z: i32,
+        dst_y: i32,
+        rotation: u8,
+    ) -> Result<(), CoordOutOfRange> {
+        if !self.contains(src_x, src_z, src_y) {
+            return Err(CoordOutOfRange { x: src_x, z: src_z, y: src_y });
+        }
+        if !self.contains(dst_x, dst_z, dst_y) {
+            return Err(CoordOutOfRange { x: dst_x, z: dst_z, y: dst_y });
+        }
+        let src: [u32; 64] = match self.zone(src_x, src_z, src_y) {
+            None => {
+                self.deallocate_if_present(dst_x, dst_z, dst_y);
+                return Ok(());
+            }
+            Some(flags) => *flags,
+        };
+        let mut dst: [u32; 64] = [0; 64];
+        for x in 0..8 {
+            for z in 0..8 {
+                let (rotated_x, rotated_z): (i32, i32) = match rotation & 0x3 {
+                    0 => (x, z),
+                    1 => (z, 7 - x),
+                    2 => (7 - x, 7 - z),
+                    _ => (7 - z, x),
+                };
+                dst[CollisionFlagMap::tile_index(rotated_x, rotated_z)] =
+                    rotate_collision_flags(rotation, src[CollisionFlagMap::tile_index(x, z)]);
+            }
+        }
+        return self.try_set_zone(dst_x, dst_z, dst_y, &dst);
+    }
+}
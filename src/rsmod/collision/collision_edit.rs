@@ -0,0 +1,119 @@
+#![allow(non_camel_case_types)]
+
+use crate::rsmod::CoordOutOfRange;
+use crate::rsmod::collision::collision::CollisionFlagMap;
+
+#[repr(u8)]
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum CollisionOp {
+    SET = 0,
+    ADD = 1,
+    REMOVE = 2,
+}
+
+impl CollisionOp {
+    #[inline(always)]
+    pub fn try_from_u8(value: u8) -> Option<CollisionOp> {
+        return match value {
+            0 => Some(CollisionOp::SET),
+            1 => Some(CollisionOp::ADD),
+            2 => Some(CollisionOp::REMOVE),
+            _ => None,
+        };
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct CollisionEdit {
+    pub x: i32,
+    pub z: i32,
+    pub y: i32,
+    pub mask: u32,
+    pub op: CollisionOp,
+}
+
+impl CollisionEdit {
+    #[inline(always)]
+    pub fn new(x: i32, z: i32, y: i32, mask: u32, op: CollisionOp) -> CollisionEdit {
+        return CollisionEdit { x, z, y, mask, op };
+    }
+}
+
+impl CollisionFlagMap {
+    pub const MAPSQUARE_TILE_COUNT: usize = 4 * 64 * 64;
+
+    /**
+     * Replaces every tile of the zone containing `x`, `z` with `flags`, indexed by [CollisionFlagMap::tile_index].
+     *
+     * # Panics
+     * If the zone is outside of the map, see [CollisionFlagMap::try_set_zone].
+     */
+    #[inline(always)]
+    pub unsafe fn set_zone(&mut self, x: i32, z: i32, y: i32, flags: &[u32; 64]) {
+        self.try_set_zone(x, z, y, flags).unwrap();
+    }
+
+    #[inline(always)]
+    pub unsafe fn try_set_zone(&mut self, x: i32, z: i32, y: i32, flags: &[u32; 64]) -> Result<(), CoordOutOfRange> {
+        self.replace_zone(x, z, y, Some(Box::new(*flags)))?;
+        return Ok(());
+    }
+
+    /**
+     * # Panics
+     * If an edit is outside of the map, see [CollisionFlagMap::try_apply].
+     */
+    #[inline(always)]
+    pub unsafe fn apply(&mut self, edits: &[CollisionEdit]) {
+        self.try_apply(edits).unwrap();
+    }
+
+    /**
+     * Applies the edits in order, or none of them if any is outside of the map.
+     */
+    #[inline(always)]
+    pub unsafe fn try_apply(&mut self, edits: &[CollisionEdit]) -> Result<(), CoordOutOfRange> {
+        if let Some(edit) = edits.iter().find(|edit| !self.contains(edit.x, edit.z, edit.y)) {
+            return Err(CoordOutOfRange { x: edit.x, z: edit.z, y: edit.y });
+        }
+        for edit in edits {
+            match edit.op {
+                CollisionOp::SET => self.try_set(edit.x, edit.z, edit.y, edit.mask)?,
+                CollisionOp::ADD => self.try_add(edit.x, edit.z, edit.y, edit.mask)?,
+                CollisionOp::REMOVE => self.try_remove(edit.x, edit.z, edit.y, edit.mask)?,
+            }
+        }
+        return Ok(());
+    }
+
+    /**
+     * Replaces all 4 levels of a 64x64 mapsquare.
+     * `flags` holds [CollisionFlagMap::MAPSQUARE_TILE_COUNT] masks, zone by zone so every
+     * 64 masks form one zone: `(y << 12) | (zone_z << 9) | (zone_x << 6) | tile_index`.
+     * Returns false without changing anything if `flags` has the wrong length
+     * or the mapsquare is outside of the map.
+     */
+    #[inline(always)]
+    pub unsafe fn load_mapsquare(&mut self, mapsquare_x: i32, mapsquare_z: i32, flags: &[u32]) -> bool {
+        if flags.len() != CollisionFlagMap::MAPSQUARE_TILE_COUNT {
+            return false;
+        }
+        let base_x: i32 = mapsquare_x.wrapping_shl(6);
+        let base_z: i32 = mapsquare_z.wrapping_shl(6);
+        if base_x >> 6 != mapsquare_x
+            || base_z >> 6 != mapsquare_z
+            || !self.contains(base_x, base_z, 0)
+            || !self.contains(base_x + 63, base_z + 63, 3)
+        {
+            return false;
+        }
+        for (index, zone) in flags.chunks_exact(64).enumerate() {
+            let index: i32 = index as i32;
+            let zone_x: i32 = base_x + ((index & 0x7) << 3);
+            let zone_z: i32 = base_z + (((index >> 3) & 0x7) << 3);
+            let y: i32 = index >> 6;
+            self.set_zone(zone_x, zone_z, y, zone.try_into().unwrap());
+        }
+        return true;
+    }
+}
@@ -0,0 +1,129 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::rsmod::WideCoordGrid;
+use crate::rsmod::collision::collision::CollisionFlagMap;
+use crate::rsmod::collision_flag::CollisionFlag;
+
+/**
+ * One tile whose flags changed, `before` and `after` being [CollisionFlag::NULL] while its zone is unallocated.
+ */
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct CollisionChange {
+    pub x: i32,
+    pub z: i32,
+    pub y: i32,
+    pub before: u32,
+    pub after: u32,
+}
+
+/**
+ * Records which zones of a [CollisionFlagMap] changed so anything cached on top of the map
+ * (paths, flow fields, connectivity) can tell when it went stale.
+ * Every zone has a version that goes up each time a write changes the value of one of its tiles,
+ * so a cache only has to remember the versions of the zones it read.
+ */
+#[derive(Clone, Default)]
+pub struct CollisionJournal {
+    // zones that never changed are at version 0.
+    versions: HashMap<u64, u32>,
+    changed_zones: Vec<WideCoordGrid>,
+    pending: HashSet<u64>,
+    changes: Vec<CollisionChange>,
+    record_tiles: bool,
+}
+
+impl CollisionJournal {
+    #[inline(always)]
+    pub fn new(record_tiles: bool) -> CollisionJournal {
+        return CollisionJournal {
+            versions: HashMap::new(),
+            changed_zones: vec![],
+            pending: HashSet::new(),
+            changes: vec![],
+            record_tiles,
+        };
+    }
+
+    #[inline(always)]
+    pub fn zone_version(&self, x: i32, z: i32, y: i32) -> u32 {
+        return *self.versions.get(&CollisionFlagMap::zone_key(x, z, y)).unwrap_or(&0);
+    }
+
+    /**
+     * Returns the zones [CollisionJournal::drain_changed_zones] would, without draining them.
+     */
+    #[inline(always)]
+    pub fn changed_zones(&self) -> &[WideCoordGrid] {
+        return &self.changed_zones;
+    }
+
+    /**
+     * Returns the south-west tile of every zone that changed since the last drain,
+     * each zone once, in the order they first changed.
+     */
+    #[inline(always)]
+    pub fn drain_changed_zones(&mut self) -> Vec<WideCoordGrid> {
+        self.pending.clear();
+        return std::mem::take(&mut self.changed_zones);
+    }
+
+    /**
+     * Returns every tile change since the last drain. Always empty unless tiles are recorded.
+     */
+    #[inline(always)]
+    pub fn drain_changes(&mut self) -> Vec<CollisionChange> {
+        return std::mem::take(&mut self.changes);
+    }
+
+    #[inline(always)]
+    pub(crate) fn record_tile(&mut self, x: i32, z: i32, y: i32, before: u32, after: u32) {
+        if before == after {
+            return;
+        }
+        self.bump(x, z, y);
+        if self.record_tiles {
+            self.changes.push(CollisionChange { x, z, y, before, after });
+        }
+    }
+
+    #[inline(always)]
+    pub(crate) fn record_zone(
+        &mut self,
+        x: i32,
+        z: i32,
+        y: i32,
+        before: Option<&[u32; 64]>,
+        after: Option<&[u32; 64]>,
+    ) {
+        let base_x: i32 = x & !0x7;
+        let base_z: i32 = z & !0x7;
+        let mut changed: bool = false;
+        for index in 0..64 {
+            let before: u32 = before.map_or(CollisionFlag::NULL as u32, |flags| flags[index]);
+            let after: u32 = after.map_or(CollisionFlag::NULL as u32, |flags| flags[index]);
+            if before == after {
+                continue;
+            }
+            changed = true;
+            if self.record_tiles {
+                let tile_x: i32 = base_x + (index as i32 & 0x7);
+                let tile_z: i32 = base_z + (index as i32 >> 3);
+                self.changes.push(CollisionChange { x: tile_x, z: tile_z, y, before, after });
+            }
+        }
+        if changed {
+            self.bump(x, z, y);
+        }
+    }
+
+    #[inline(always)]
+    fn bump(&mut self, x: i32, z: i32, y: i32) {
+        let zone_key: u64 = CollisionFlagMap::zone_key(x, z, y);
+        let version: &mut u32 = self.versions.entry(zone_key).or_insert(0);
+        *version = version.wrapping_add(1);
+        if self.pending.insert(zone_key) {
+            // every zone a map holds has a coordinate that fits, see [CollisionFlagMap::contains].
+            self.changed_zones.push(WideCoordGrid::try_new(y, x & !0x7, z & !0x7).unwrap());
+        }
+    }
+}
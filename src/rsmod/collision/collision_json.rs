@@ -0,0 +1,19 @@
+use crate::rsmod::collision::collision::CollisionFlagMap;
+
+impl CollisionFlagMap {
+    /**
+     * Replaces all 4 levels of a 64x64 mapsquare from a JSON array of masks,
+     * laid out like [CollisionFlagMap::load_mapsquare].
+     * Returns `Ok(false)` without changing anything if the array has the wrong length.
+     */
+    #[inline(always)]
+    pub unsafe fn load_mapsquare_json(
+        &mut self,
+        mapsquare_x: i32,
+        mapsquare_z: i32,
+        json: &str,
+    ) -> Result<bool, serde_json::Error> {
+        let flags: Vec<u32> = serde_json::from_str(json)?;
+        return Ok(self.load_mapsquare(mapsquare_x, mapsquare_z, &flags));
+    }
+}
@@ -0,0 +1,290 @@
+use std::collections::HashMap;
+
+use crate::rsmod::CoordOutOfRange;
+use crate::rsmod::collision::collision::{CollisionFlagMap, CollisionFlagReader};
+use crate::rsmod::collision::collision_change::CollisionFlagWriter;
+use crate::rsmod::collision_flag::CollisionFlag;
+
+/**
+ * Private copy-on-write collision on top of a shared [CollisionFlagMap], e.g. for instances.
+ * A zone is copied out of the base the first time it is written, untouched zones read through.
+ * The base is passed in on every access so it can keep changing while the overlay exists.
+ */
+#[derive(Clone, Default)]
+pub struct CollisionOverlay {
+    // a `None` zone was deallocated in the overlay and hides the base zone.
+    zones: HashMap<u64, Option<Box<[u32; 64]>>>,
+}
+
+/**
+ * An overlay paired with its base, readable like any other collision map.
+ */
+pub struct CollisionOverlayView<'a> {
+    base: &'a CollisionFlagMap,
+    overlay: &'a CollisionOverlay,
+}
+
+/**
+ * An overlay paired with its base for writing, see [CollisionOverlay::edit].
+ */
+pub struct CollisionOverlayEdit<'a> {
+    base: &'a CollisionFlagMap,
+    overlay: &'a mut CollisionOverlay,
+}
+
+impl CollisionOverlay {
+    #[inline(always)]
+    pub fn new() -> CollisionOverlay {
+        return CollisionOverlay {
+            zones: HashMap::new(),
+        };
+    }
+
+    #[inline(always)]
+    pub fn view<'a>(&'a self, base: &'a CollisionFlagMap) -> CollisionOverlayView<'a> {
+        return CollisionOverlayView {
+            base,
+            overlay: self,
+        };
+    }
+
+    /**
+     * Pairs the overlay with its base so the [CollisionFlagWriter] helpers can write to it.
+     */
+    #[inline(always)]
+    pub fn edit<'a>(&'a mut self, base: &'a CollisionFlagMap) -> CollisionOverlayEdit<'a> {
+        return CollisionOverlayEdit {
+            base,
+            overlay: self,
+        };
+    }
+
+    /**
+     * # Panics
+     * If the tile is outside of the base, see [CollisionOverlay::try_set].
+     */
+    #[inline(always)]
+    pub unsafe fn set(&mut self, base: &CollisionFlagMap, x: i32, z: i32, y: i32, mask: u32) {
+        self.try_set(base, x, z, y, mask).unwrap();
+    }
+
+    /**
+     * # Panics
+     * If the tile is outside of the base, see [CollisionOverlay::try_add].
+     */
+    #[inline(always)]
+    pub unsafe fn add(&mut self, base: &CollisionFlagMap, x: i32, z: i32, y: i32, mask: u32) {
+        self.try_add(base, x, z, y, mask).unwrap();
+    }
+
+    /**
+     * # Panics
+     * If the tile is outside of the base, see [CollisionOverlay::try_remove].
+     */
+    #[inline(always)]
+    pub unsafe fn remove(&mut self, base: &CollisionFlagMap, x: i32, z: i32, y: i32, mask: u32) {
+        self.try_remove(base, x, z, y, mask).unwrap();
+    }
+
+    #[inline(always)]
+    pub unsafe fn try_set(
+        &mut self,
+        base: &CollisionFlagMap,
+        x: i32,
+        z: i32,
+        y: i32,
+        mask: u32,
+    ) -> Result<(), CoordOutOfRange> {
+        self.materialize(base, x, z, y)?[CollisionFlagMap::tile_index(x, z)] = mask;
+        return Ok(());
+    }
+
+    #[inline(always)]
+    pub unsafe fn try_add(
+        &mut self,
+        base: &CollisionFlagMap,
+        x: i32,
+        z: i32,
+        y: i32,
+        mask: u32,
+    ) -> Result<(), CoordOutOfRange> {
+        self.materialize(base, x, z, y)?[CollisionFlagMap::tile_index(x, z)] |= mask;
+        return Ok(());
+    }
+
+    #[inline(always)]
+    pub unsafe fn try_remove(
+        &mut self,
+        base: &CollisionFlagMap,
+        x: i32,
+        z: i32,
+        y: i32,
+        mask: u32,
+    ) -> Result<(), CoordOutOfRange> {
+        self.materialize(base, x, z, y)?[CollisionFlagMap::tile_index(x, z)] &= !mask;
+        return Ok(());
+    }
+
+    /**
+     * # Panics
+     * If the zone is outside of the base, see [CollisionOverlay::try_set_zone].
+     */
+    #[inline(always)]
+    pub unsafe fn set_zone(&mut self, base: &CollisionFlagMap, x: i32, z: i32, y: i32, flags: &[u32; 64]) {
+        self.try_set_zone(base, x, z, y, flags).unwrap();
+    }
+
+    #[inline(always)]
+    pub unsafe fn try_set_zone(
+        &mut self,
+        base: &CollisionFlagMap,
+        x: i32,
+        z: i32,
+        y: i32,
+        flags: &[u32; 64],
+    ) -> Result<(), CoordOutOfRange> {
+        if !base.contains(x, z, y) {
+            return Err(CoordOutOfRange { x, z, y });
+        }
+        self.zones.insert(CollisionFlagMap::zone_key(x, z, y), Some(Box::new(*flags)));
+        return Ok(());
+    }
+
+    /**
+     * # Panics
+     * If the zone is outside of the base, see [CollisionOverlay::try_allocate_if_absent].
+     */
+    #[inline(always)]
+    pub unsafe fn allocate_if_absent(&mut self, base: &CollisionFlagMap, x: i32, z: i32, y: i32) {
+        self.try_allocate_if_absent(base, x, z, y).unwrap();
+    }
+
+    #[inline(always)]
+    pub unsafe fn try_allocate_if_absent(
+        &mut self,
+        base: &CollisionFlagMap,
+        x: i32,
+        z: i32,
+        y: i32,
+    ) -> Result<(), CoordOutOfRange> {
+        self.materialize(base, x, z, y)?;
+        return Ok(());
+    }
+
+    #[inline(always)]
+    pub unsafe fn deallocate_if_present(&mut self, base: &CollisionFlagMap, x: i32, z: i32, y: i32) {
+        // nothing outside of the base is ever allocated.
+        if base.contains(x, z, y) {
+            self.zones.insert(CollisionFlagMap::zone_key(x, z, y), None);
+        }
+    }
+
+    /**
+     * Drops the private copy of a zone so it reads through to the base again.
+     */
+    #[inline(always)]
+    pub fn revert_zone(&mut self, x: i32, z: i32, y: i32) {
+        if let Some(key) = CollisionOverlay::zone_key(x, z, y) {
+            self.zones.remove(&key);
+        }
+    }
+
+    #[inline(always)]
+    pub fn is_zone_touched(&self, x: i32, z: i32, y: i32) -> bool {
+        return CollisionOverlay::zone_key(x, z, y).is_some_and(|key| self.zones.contains_key(&key));
+    }
+
+    #[inline(always)]
+    pub fn touched_zone_count(&self) -> usize {
+        return self.zones.len();
+    }
+
+    /**
+     * Discards every private zone, in O(touched zones).
+     */
+    #[inline(always)]
+    pub fn clear(&mut self) {
+        self.zones.clear();
+    }
+
+    /**
+     * Keys the zone like [CollisionFlagMap::zone_key], which only tells apart the levels of the map.
+     */
+    #[inline(always)]
+    fn zone_key(x: i32, z: i32, y: i32) -> Option<u64> {
+        if (y as u32) >= 4 {
+            return None;
+        }
+        return Some(CollisionFlagMap::zone_key(x, z, y));
+    }
+
+    #[inline(always)]
+    fn zone(&self, x: i32, z: i32, y: i32) -> Option<&Option<Box<[u32; 64]>>> {
+        return CollisionOverlay::zone_key(x, z, y).and_then(|key| self.zones.get(&key));
+    }
+
+    #[inline(always)]
+    unsafe fn materialize(
+        &mut self,
+        base: &CollisionFlagMap,
+        x: i32,
+        z: i32,
+        y: i32,
+    ) -> Result<&mut [u32; 64], CoordOutOfRange> {
+        if !base.contains(x, z, y) {
+            return Err(CoordOutOfRange { x, z, y });
+        }
+        return Ok(self
+            .zones
+            .entry(CollisionFlagMap::zone_key(x, z, y))
+            .or_insert_with(|| base.zone(x, z, y).map(|flags| Box::new(*flags)))
+            .get_or_insert_with(|| Box::new([CollisionFlag::OPEN as u32; 64])));
+    }
+}
+
+impl CollisionFlagWriter for CollisionOverlayEdit<'_> {
+    #[inline(always)]
+    fn contains(&self, x: i32, z: i32, y: i32) -> bool {
+        return self.base.contains(x, z, y);
+    }
+
+    #[inline(always)]
+    unsafe fn try_add(&mut self, x: i32, z: i32, y: i32, mask: u32) -> Result<(), CoordOutOfRange> {
+        return self.overlay.try_add(self.base, x, z, y, mask);
+    }
+
+    #[inline(always)]
+    unsafe fn try_remove(&mut self, x: i32, z: i32, y: i32, mask: u32) -> Result<(), CoordOutOfRange> {
+        return self.overlay.try_remove(self.base, x, z, y, mask);
+    }
+}
+
+impl CollisionFlagReader for CollisionOverlayView<'_> {
+    #[inline(always)]
+    unsafe fn get(&self, x: i32, z: i32, y: i32) -> u32 {
+        return match self.overlay.zone(x, z, y) {
+            None => self.base.get(x, z, y),
+            Some(None) => CollisionFlag::NULL as u32,
+            Some(Some(flags)) => flags[CollisionFlagMap::tile_index(x, z)],
+        };
+    }
+
+    #[inline(always)]
+    unsafe fn is_zone_allocated(&self, x: i32, z: i32, y: i32) -> bool {
+        return match self.overlay.zone(x, z, y) {
+            None => self.base.is_zone_allocated(x, z, y),
+            Some(zone) => zone.is_some(),
+        };
+    }
+
+    #[inline(always)]
+    unsafe fn is_flagged(&self, x: i32, z: i32, y: i32, masks: u32) -> bool {
+        return match self.overlay.zone(x, z, y) {
+            None => self.base.is_flagged(x, z, y, masks),
+            Some(None) => false,
+            Some(Some(flags)) => {
+                flags[CollisionFlagMap::tile_index(x, z)] & masks != CollisionFlag::OPEN as u32
+            }
+        };
+    }
+}
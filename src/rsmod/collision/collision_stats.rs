@@ -0,0 +1,168 @@
+use crate::rsmod::collision::collision::CollisionFlagMap;
+use crate::rsmod::collision_flag::CollisionFlag;
+
+/**
+ * A tile whose flags or zone allocation differ between two maps, see [diff].
+ * A tile in a zone unallocated in a map reads as [CollisionFlag::OPEN] there,
+ * with the allocation kept apart in `left_allocated` and `right_allocated`.
+ */
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct CollisionDiff {
+    pub x: i32,
+    pub z: i32,
+    pub y: i32,
+    pub left: u32,
+    pub right: u32,
+    pub left_allocated: bool,
+    pub right_allocated: bool,
+}
+
+impl CollisionDiff {
+    /**
+     * Returns if only the zone allocation differs, the flags being the same on both sides.
+     */
+    #[inline(always)]
+    pub fn is_allocation_only(&self) -> bool {
+        return self.left == self.right;
+    }
+
+    /**
+     * Flags set on the right but not on the left.
+     */
+    #[inline(always)]
+    pub fn added(&self) -> Vec<CollisionFlag> {
+        return CollisionFlag::split(self.right & !self.left);
+    }
+
+    /**
+     * Flags set on the left but not on the right.
+     */
+    #[inline(always)]
+    pub fn removed(&self) -> Vec<CollisionFlag> {
+        return CollisionFlag::split(self.left & !self.right);
+    }
+}
+
+impl CollisionFlagMap {
+    /**
+     * Returns the coords of the south-west tile of a zone from its [CollisionFlagMap::zone_index].
+     */
+    #[inline(always)]
+    pub fn zone_coord(zone_idx: usize) -> (i32, i32, i32) {
+        let zone_idx: i32 = zone_idx as i32;
+        return ((zone_idx & 0x7ff) << 3, ((zone_idx >> 11) & 0x7ff) << 3, (zone_idx >> 22) & 0x3);
+    }
+
+    /**
+     * Iterates the allocated zones as the coords of their south-west tile and their tiles.
+     * This costs O(mapsquares ever allocated in), not O(slots in the zone table).
+     */
+    pub fn zones(&self) -> impl Iterator<Item = (i32, i32, i32, &[u32; 64])> + '_ {
+        return self.allocated_slots().filter_map(|slot| {
+            return self.flags[slot].as_deref().map(|flags| {
+                let (x, z, y): (i32, i32, i32) = self.slot_coord(slot);
+                return (x, z, y, flags);
+            });
+        });
+    }
+
+    /**
+     * Iterates every tile in an allocated zone that has any flag set.
+     */
+    pub fn tiles(&self) -> impl Iterator<Item = (i32, i32, i32, u32)> + '_ {
+        return self.zones().flat_map(|(x, z, y, flags)| {
+            return flags
+                .iter()
+                .enumerate()
+                .filter(|&(_, &mask)| mask != CollisionFlag::OPEN as u32)
+                .map(move |(index, &mask)| (x + (index as i32 & 0x7), z + (index as i32 >> 3), y, mask));
+        });
+    }
+
+    #[inline(always)]
+    pub fn zone_count(&self) -> usize {
+        return self.zones().count();
+    }
+
+    /**
+     * Returns the bytes held by the zone table and the allocated zones.
+     */
+    #[inline(always)]
+    pub fn memory_usage(&self) -> usize {
+        return self.flags.capacity() * std::mem::size_of::<Option<Box<[u32; 64]>>>()
+            + self.zone_count() * std::mem::size_of::<[u32; 64]>();
+    }
+
+    /**
+     * Counts the tiles of allocated zones that have each flag set, indexed like [CollisionFlag::SINGLE].
+     */
+    pub fn flag_histogram(&self) -> [usize; 32] {
+        let mut histogram: [usize; 32] = [0; 32];
+        for (_, _, _, mask) in self.tiles() {
+            for (bit, count) in histogram.iter_mut().enumerate() {
+                if mask & (1 << bit) != 0 {
+                    *count += 1;
+                }
+            }
+        }
+        return histogram;
+    }
+}
+
+/**
+ * Returns every tile whose flags or zone allocation differ between `left` and `right`, zone by zone.
+ * An unallocated zone only matches another unallocated zone. The maps don't need to share a layout.
+ */
+pub fn diff(left: &CollisionFlagMap, right: &CollisionFlagMap) -> Vec<CollisionDiff> {
+    let mut diffs: Vec<CollisionDiff> = vec![];
+    for (x, z, y, left_zone) in left.zones() {
+        diff_zone(&mut diffs, x, z, y, Some(left_zone), right.zone(x, z, y));
+    }
+    for (x, z, y, right_zone) in right.zones() {
+        if left.zone(x, z, y).is_none() {
+            diff_zone(&mut diffs, x, z, y, None, Some(right_zone));
+        }
+    }
+    return diffs;
+}
+
+fn diff_zone(
+    diffs: &mut Vec<CollisionDiff>,
+    x: i32,
+    z: i32,
+    y: i32,
+    left_zone: Option<&[u32; 64]>,
+    right_zone: Option<&[u32; 64]>,
+) {
+    for index in 0..64 {
+        let left: u32 = left_zone.map_or(CollisionFlag::OPEN as u32, |flags| flags[index]);
+        let right: u32 = right_zone.map_or(CollisionFlag::OPEN as u32, |flags| flags[index]);
+        if left != right || left_zone.is_some() != right_zone.is_some() {
+            diffs.push(CollisionDiff {
+                x: x + (index as i32 & 0x7),
+                z: z + (index as i32 >> 3),
+                y,
+                left,
+                right,
+                left_allocated: left_zone.is_some(),
+                right_allocated: right_zone.is_some(),
+            });
+        }
+    }
+}
+
+/**
+ * Counts how many of `diffs` differ in each flag, indexed like [CollisionFlag::SINGLE].
+ * Diffs that are only a zone allocation count towards no flag.
+ */
+pub fn diff_histogram(diffs: &[CollisionDiff]) -> [usize; 32] {
+    let mut histogram: [usize; 32] = [0; 32];
+    for diff in diffs {
+        for (bit, count) in histogram.iter_mut().enumerate() {
+            if (diff.left ^ diff.right) & (1 << bit) != 0 {
+                *count += 1;
+            }
+        }
+    }
+    return histogram;
+}
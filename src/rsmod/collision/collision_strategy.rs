@@ -2,6 +2,7 @@
 
 use crate::rsmod::collision_flag::CollisionFlag;
 
+#[cfg_attr(feature = "wasm", wasm_bindgen::prelude::wasm_bindgen)]
 #[repr(u8)]
 pub enum CollisionType {
     NORMAL = 0,
@@ -110,3 +111,16 @@ impl CollisionStrategy for CollisionStrategies {
         }
     }
 }
+
+#[inline(always)]
+pub fn get_collision_strategy(
+    collision: CollisionType
+) -> CollisionStrategies {
+    match collision {
+        CollisionType::NORMAL => CollisionStrategies::Normal(Normal),
+        CollisionType::BLOCKED => CollisionStrategies::Blocked(Blocked),
+        CollisionType::INDOORS => CollisionStrategies::Indoors(Indoors),
+        CollisionType::OUTDOORS => CollisionStrategies::Outdoors(Outdoors),
+        CollisionType::LINE_OF_SIGHT => CollisionStrategies::LineOfSight(LineOfSight),
+    }
+}
@@ -1,2 +1,10 @@
 pub mod collision;
+pub mod collision_change;
+pub mod collision_copy;
+pub mod collision_edit;
+pub mod collision_journal;
+#[cfg(feature = "json")]
+pub mod collision_json;
+pub mod collision_overlay;
+pub mod collision_stats;
 pub mod collision_strategy;
@@ -30,3 +30,105 @@ impl CoordGrid {
         return self.packed & 0x3fff;
     }
 }
+
+/**
+ * A coordinate that doesn't fit the map or packed type it was given to.
+ */
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct CoordOutOfRange {
+    pub x: i32,
+    pub z: i32,
+    pub y: i32,
+}
+
+impl std::fmt::Display for CoordOutOfRange {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        return write!(f, "coordinate out of range: x={}, z={}, y={}", self.x, self.z, self.y);
+    }
+}
+
+impl std::error::Error for CoordOutOfRange {}
+
+impl CoordGrid {
+    /**
+     * Like [CoordGrid::new], but refuses coordinates that would not survive packing
+     * instead of wrapping them onto other tiles.
+     */
+    #[inline(always)]
+    pub fn try_new(y: i32, x: i32, z: i32) -> Result<CoordGrid, CoordOutOfRange> {
+        if (x as u32) > 0x3fff || (z as u32) > 0x3fff || (y as u32) > 0x3 {
+            return Err(CoordOutOfRange { x, z, y });
+        }
+        return Ok(CoordGrid::new(y, x, z));
+    }
+}
+
+/**
+ * A packed coordinate for layouts past [CoordGrid]'s 14 bits, e.g. in a sparse map.
+ * x and z are signed 31 bit, y takes the top 2 bits.
+ */
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct WideCoordGrid {
+    pub packed: u64,
+}
+
+impl WideCoordGrid {
+    pub const MIN_COORD: i32 = -0x40000000;
+    pub const MAX_COORD: i32 = 0x3fffffff;
+
+    #[inline(always)]
+    pub fn try_new(y: i32, x: i32, z: i32) -> Result<WideCoordGrid, CoordOutOfRange> {
+        if !(WideCoordGrid::MIN_COORD..=WideCoordGrid::MAX_COORD).contains(&x)
+            || !(WideCoordGrid::MIN_COORD..=WideCoordGrid::MAX_COORD).contains(&z)
+            || (y as u32) > 0x3
+        {
+            return Err(CoordOutOfRange { x, z, y });
+        }
+        return Ok(WideCoordGrid {
+            packed: (z as u64 & 0x7fffffff) | ((x as u64 & 0x7fffffff) << 31) | ((y as u64) << 62),
+        });
+    }
+
+    #[inline(always)]
+    pub fn from(packed: u64) -> WideCoordGrid {
+        return WideCoordGrid { packed };
+    }
+
+    #[inline(always)]
+    pub fn y(&self) -> i32 {
+        return (self.packed >> 62) as i32;
+    }
+
+    #[inline(always)]
+    pub fn x(&self) -> i32 {
+        // shifted up to the sign bit and back to sign extend.
+        return (((self.packed >> 31) as u32) << 1) as i32 >> 1;
+    }
+
+    #[inline(always)]
+    pub fn z(&self) -> i32 {
+        return ((self.packed as u32) << 1) as i32 >> 1;
+    }
+}
+
+/**
+ * The packed form paths and lines return their tiles in: a [CoordGrid] in a u32, or a
+ * [WideCoordGrid] in a u64 for tiles past [CoordGrid]'s 14 bits.
+ */
+pub trait PackedCoord: Copy {
+    fn pack(y: i32, x: i32, z: i32) -> Result<Self, CoordOutOfRange>;
+}
+
+impl PackedCoord for u32 {
+    #[inline(always)]
+    fn pack(y: i32, x: i32, z: i32) -> Result<u32, CoordOutOfRange> {
+        return CoordGrid::try_new(y, x, z).map(|coord| coord.packed);
+    }
+}
+
+impl PackedCoord for u64 {
+    #[inline(always)]
+    fn pack(y: i32, x: i32, z: i32) -> Result<u64, CoordOutOfRange> {
+        return WideCoordGrid::try_new(y, x, z).map(|coord| coord.packed);
+    }
+}
@@ -0,0 +1,151 @@
+use crate::rsmod::{CoordGrid, CoordOutOfRange, LocShape};
+use crate::rsmod::collision::collision::CollisionFlagMap;
+use crate::rsmod::collision::collision_change::CollisionFlagWriter;
+
+/**
+ * The shape, angle and offset of one side of a door, relative to the closed door's coordinate.
+ */
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct DoorState {
+    pub shape: LocShape,
+    pub angle: u8,
+    pub offset_x: i32,
+    pub offset_z: i32,
+}
+
+impl DoorState {
+    #[inline(always)]
+    pub fn new(shape: LocShape, angle: u8, offset_x: i32, offset_z: i32) -> DoorState {
+        return DoorState {
+            shape,
+            angle,
+            offset_x,
+            offset_z,
+        };
+    }
+}
+
+/**
+ * A door or gate that swaps between a closed and an open wall.
+ * Both sides share the same blockrange and breakroutefinding so toggling never leaves stale flags.
+ */
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct Door {
+    pub closed: DoorState,
+    pub open: DoorState,
+    pub blockrange: bool,
+    pub breakroutefinding: bool,
+}
+
+impl Door {
+    #[inline(always)]
+    pub fn new(
+        closed: DoorState,
+        open: DoorState,
+        blockrange: bool,
+        breakroutefinding: bool,
+    ) -> Door {
+        return Door {
+            closed,
+            open,
+            blockrange,
+            breakroutefinding,
+        };
+    }
+
+    /**
+     * Opens the door closed at `x`, `z`.
+     * Returns the packed coords of every tile whose flags changed.
+     */
+    #[inline(always)]
+    pub unsafe fn open(&self, flags: &mut CollisionFlagMap, x: i32, z: i32, y: i32) -> Result<Vec<u32>, CoordOutOfRange> {
+        return self.swap(flags, x, z, y, &self.closed, &self.open);
+    }
+
+    /**
+     * Closes the door that was closed at `x`, `z` and is currently open.
+     * Returns the packed coords of every tile whose flags changed.
+     */
+    #[inline(always)]
+    pub unsafe fn close(&self, flags: &mut CollisionFlagMap, x: i32, z: i32, y: i32) -> Result<Vec<u32>, CoordOutOfRange> {
+        return self.swap(flags, x, z, y, &self.open, &self.closed);
+    }
+
+    /**
+     * Applies the collision of the door in the given state, e.g. when the map is first loaded.
+     */
+    #[inline(always)]
+    pub unsafe fn add(&self, flags: &mut CollisionFlagMap, x: i32, z: i32, y: i32, open: bool) -> Result<(), CoordOutOfRange> {
+        let state: &DoorState = if open { &self.open } else { &self.closed };
+        return self.change(flags, x, z, y, state, true);
+    }
+
+    #[inline(always)]
+    unsafe fn swap(
+        &self,
+        flags: &mut CollisionFlagMap,
+        x: i32,
+        z: i32,
+        y: i32,
+        from: &DoorState,
+        to: &DoorState,
+    ) -> Result<Vec<u32>, CoordOutOfRange> {
+        // a wall only ever touches its own tile and the neighbouring ones.
+        let mut tiles: Vec<(i32, i32, u32)> = Vec::with_capacity(18);
+        for state in [from, to] {
+            // checked up front so the door is never left removed without the other side added,
+            // or changed without the changed tiles fitting in a [CoordGrid].
+            for (dx, dz) in [(-1, -1), (1, 1)] {
+                let (tile_x, tile_z): (i32, i32) = (x + state.offset_x + dx, z + state.offset_z + dz);
+                if !flags.contains(tile_x, tile_z, y) {
+                    return Err(CoordOutOfRange { x: tile_x, z: tile_z, y });
+                }
+                CoordGrid::try_new(y, tile_x, tile_z)?;
+            }
+            for dx in -1..=1 {
+                for dz in -1..=1 {
+                    let tile_x: i32 = x + state.offset_x + dx;
+                    let tile_z: i32 = z + state.offset_z + dz;
+                    if !tiles.iter().any(|&(tx, tz, _)| tx == tile_x && tz == tile_z) {
+                        tiles.push((tile_x, tile_z, flags.get(tile_x, tile_z, y)));
+                    }
+                }
+            }
+        }
+
+        self.change(flags, x, z, y, from, false)?;
+        self.change(flags, x, z, y, to, true)?;
+
+        let mut changed: Vec<u32> = vec![];
+        for (tile_x, tile_z, before) in tiles {
+            if flags.get(tile_x, tile_z, y) != before {
+                changed.push(CoordGrid::try_new(y, tile_x, tile_z)?.packed);
+            }
+        }
+        return Ok(changed);
+    }
+
+    #[inline(always)]
+    unsafe fn change(
+        &self,
+        flags: &mut CollisionFlagMap,
+        x: i32,
+        z: i32,
+        y: i32,
+        state: &DoorState,
+        add: bool,
+    ) -> Result<(), CoordOutOfRange> {
+        return flags.change_loc_shape(
+            x + state.offset_x,
+            z + state.offset_z,
+            y,
+            1,
+            1,
+            state.angle,
+            state.shape,
+            self.blockrange,
+            self.breakroutefinding,
+            add,
+        );
+    }
+}
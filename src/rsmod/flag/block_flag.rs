@@ -2,6 +2,7 @@
 
 use std::ops::BitAnd;
 
+#[cfg_attr(feature = "wasm", wasm_bindgen::prelude::wasm_bindgen)]
 #[repr(u8)]
 #[derive(Debug)]
 pub enum BlockAccessFlag {
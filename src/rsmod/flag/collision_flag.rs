@@ -1,5 +1,6 @@
 #![allow(non_camel_case_types)]
 
+#[cfg_attr(feature = "wasm", wasm_bindgen::prelude::wasm_bindgen)]
 #[repr(u32)]
 #[derive(Clone, PartialEq, Copy, Debug)]
 pub enum CollisionFlag {
@@ -94,3 +95,54 @@ pub enum CollisionFlag {
 
     NULL = 0x7FFFFFFF,
 }
+
+impl CollisionFlag {
+    /**
+     * Every flag that is a single bit, indexed by its bit.
+     */
+    pub const SINGLE: [CollisionFlag; 32] = [
+        CollisionFlag::WALL_NORTH_WEST,
+        CollisionFlag::WALL_NORTH,
+        CollisionFlag::WALL_NORTH_EAST,
+        CollisionFlag::WALL_EAST,
+        CollisionFlag::WALL_SOUTH_EAST,
+        CollisionFlag::WALL_SOUTH,
+        CollisionFlag::WALL_SOUTH_WEST,
+        CollisionFlag::WALL_WEST,
+        CollisionFlag::LOC,
+        CollisionFlag::WALL_NORTH_WEST_PROJ_BLOCKER,
+        CollisionFlag::WALL_NORTH_PROJ_BLOCKER,
+        CollisionFlag::WALL_NORTH_EAST_PROJ_BLOCKER,
+        CollisionFlag::WALL_EAST_PROJ_BLOCKER,
+        CollisionFlag::WALL_SOUTH_EAST_PROJ_BLOCKER,
+        CollisionFlag::WALL_SOUTH_PROJ_BLOCKER,
+        CollisionFlag::WALL_SOUTH_WEST_PROJ_BLOCKER,
+        CollisionFlag::WALL_WEST_PROJ_BLOCKER,
+        CollisionFlag::LOC_PROJ_BLOCKER,
+        CollisionFlag::FLOOR_DECORATION,
+        CollisionFlag::NPC,
+        CollisionFlag::PLAYER,
+        CollisionFlag::FLOOR,
+        CollisionFlag::WALL_NORTH_WEST_ROUTE_BLOCKER,
+        CollisionFlag::WALL_NORTH_ROUTE_BLOCKER,
+        CollisionFlag::WALL_NORTH_EAST_ROUTE_BLOCKER,
+        CollisionFlag::WALL_EAST_ROUTE_BLOCKER,
+        CollisionFlag::WALL_SOUTH_EAST_ROUTE_BLOCKER,
+        CollisionFlag::WALL_SOUTH_ROUTE_BLOCKER,
+        CollisionFlag::WALL_SOUTH_WEST_ROUTE_BLOCKER,
+        CollisionFlag::WALL_WEST_ROUTE_BLOCKER,
+        CollisionFlag::LOC_ROUTE_BLOCKER,
+        CollisionFlag::ROOF,
+    ];
+
+    /**
+     * Splits a mask into its single-bit flags, lowest bit first.
+     */
+    #[inline(always)]
+    pub fn split(mask: u32) -> Vec<CollisionFlag> {
+        return (0..32)
+            .filter(|bit| mask & (1 << bit) != 0)
+            .map(|bit| CollisionFlag::SINGLE[bit])
+            .collect();
+    }
+}
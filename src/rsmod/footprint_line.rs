@@ -0,0 +1,187 @@
+use crate::rsmod::collision::collision::CollisionFlagReader;
+use crate::rsmod::collision_strategy::CollisionStrategies;
+use crate::rsmod::coord_grid::{CoordGrid, CoordOutOfRange};
+use crate::rsmod::line::Line;
+use crate::rsmod::step_validator::can_travel;
+
+/**
+ * [crate::rsmod::has_line_of_walk] for a `size` x `size` mover. The line is traced from the tile of the
+ * mover closest to the destination like the 1x1 line, but the whole footprint is moved along it one
+ * step at a time with the same rules as [can_travel], so a wide mover can't walk a line through a narrow gap.
+ * Unlike the 1x1 line, tiles of unallocated zones block as they do for [can_travel].
+ */
+#[inline(always)]
+pub unsafe fn has_line_of_walk_footprint(
+    flags: &impl CollisionFlagReader,
+    y: i32,
+    src_x: i32,
+    src_z: i32,
+    dest_x: i32,
+    dest_z: i32,
+    size: u8,
+    dest_width: u8,
+    dest_height: u8,
+    extra_flag: u32,
+    collision: &CollisionStrategies,
+) -> bool {
+    return walkFootprint(
+        flags,
+        y,
+        src_x,
+        src_z,
+        dest_x,
+        dest_z,
+        size,
+        dest_width,
+        dest_height,
+        extra_flag,
+        collision,
+        None,
+    ) == Ok(true);
+}
+
+/**
+ * Returns the south-west tile of the mover after every step of [has_line_of_walk_footprint],
+ * or nothing if the line is blocked. Returns [CoordOutOfRange] if a tile can't be packed into a [CoordGrid].
+ */
+#[inline(always)]
+pub unsafe fn line_of_walk_footprint(
+    flags: &impl CollisionFlagReader,
+    y: i32,
+    src_x: i32,
+    src_z: i32,
+    dest_x: i32,
+    dest_z: i32,
+    size: u8,
+    dest_width: u8,
+    dest_height: u8,
+    extra_flag: u32,
+    collision: &CollisionStrategies,
+) -> Result<Vec<u32>, CoordOutOfRange> {
+    let mut coordinates: Vec<u32> = vec![];
+    if !walkFootprint(
+        flags,
+        y,
+        src_x,
+        src_z,
+        dest_x,
+        dest_z,
+        size,
+        dest_width,
+        dest_height,
+        extra_flag,
+        collision,
+        Some(&mut coordinates),
+    )? {
+        return Ok(vec![]);
+    }
+    return Ok(coordinates);
+}
+
+#[inline(always)]
+unsafe fn walkFootprint(
+    flags: &impl CollisionFlagReader,
+    y: i32,
+    src_x: i32,
+    src_z: i32,
+    dest_x: i32,
+    dest_z: i32,
+    size: u8,
+    dest_width: u8,
+    dest_height: u8,
+    extra_flag: u32,
+    collision: &CollisionStrategies,
+    mut coordinates: Option<&mut Vec<u32>>,
+) -> Result<bool, CoordOutOfRange> {
+    let size: u8 = size.max(1);
+    let start_x: i32 = Line::coordinate(src_x, dest_x, size);
+    let start_z: i32 = Line::coordinate(src_z, dest_z, size);
+
+    let end_x: i32 = Line::coordinate(dest_x, src_x, dest_width);
+    let end_z: i32 = Line::coordinate(dest_z, src_z, dest_height);
+
+    if start_x == end_x && start_z == end_z {
+        return Ok(true);
+    }
+
+    let delta_x: i32 = end_x - start_x;
+    let delta_z: i32 = end_z - start_z;
+    let absolute_delta_x: i32 = delta_x.abs();
+    let absolute_delta_z: i32 = delta_z.abs();
+
+    let travel_east: bool = delta_x >= 0;
+    let travel_north: bool = delta_z >= 0;
+
+    // the mover's south-west tile, moved along with the tile the line is traced from.
+    let mut anchor_x: i32 = src_x;
+    let mut anchor_z: i32 = src_z;
+
+    if absolute_delta_x > absolute_delta_z {
+        let offset_x: i32 = if travel_east { 1 } else { -1 };
+        let offset_z: i32 = if travel_north { 0 } else { -1 };
+
+        let mut scaled_z: i64 = Line::scale_up(start_z) + Line::HALF_TILE + offset_z as i64;
+        let tangent: i64 = Line::scale_up(delta_z) / absolute_delta_x as i64;
+
+        let mut curr_x: i32 = start_x;
+        while curr_x != end_x {
+            curr_x += offset_x;
+            let curr_z: i32 = Line::scale_down(scaled_z);
+            if !can_travel(flags, y, anchor_x, anchor_z, offset_x as i8, 0, size, extra_flag, collision) {
+                return Ok(false);
+            }
+            anchor_x += offset_x;
+            if let Some(coordinates) = coordinates.as_deref_mut() {
+                coordinates.push(CoordGrid::try_new(y, anchor_x, anchor_z)?.packed);
+            }
+
+            scaled_z += tangent;
+
+            let next_z: i32 = Line::scale_down(scaled_z);
+            if next_z != curr_z {
+                let step_z: i32 = next_z - curr_z;
+                if !can_travel(flags, y, anchor_x, anchor_z, 0, step_z as i8, size, extra_flag, collision) {
+                    return Ok(false);
+                }
+                anchor_z += step_z;
+                if let Some(coordinates) = coordinates.as_deref_mut() {
+                    coordinates.push(CoordGrid::try_new(y, anchor_x, anchor_z)?.packed);
+                }
+            }
+        }
+    } else {
+        let offset_x: i32 = if travel_east { 0 } else { -1 };
+        let offset_z: i32 = if travel_north { 1 } else { -1 };
+
+        let mut scaled_x: i64 = Line::scale_up(start_x) + Line::HALF_TILE + offset_x as i64;
+        let tangent: i64 = Line::scale_up(delta_x) / absolute_delta_z as i64;
+
+        let mut curr_z: i32 = start_z;
+        while curr_z != end_z {
+            curr_z += offset_z;
+            let curr_x: i32 = Line::scale_down(scaled_x);
+            if !can_travel(flags, y, anchor_x, anchor_z, 0, offset_z as i8, size, extra_flag, collision) {
+                return Ok(false);
+            }
+            anchor_z += offset_z;
+            if let Some(coordinates) = coordinates.as_deref_mut() {
+                coordinates.push(CoordGrid::try_new(y, anchor_x, anchor_z)?.packed);
+            }
+
+            scaled_x += tangent;
+
+            let next_x: i32 = Line::scale_down(scaled_x);
+            if next_x != curr_x {
+                let step_x: i32 = next_x - curr_x;
+                if !can_travel(flags, y, anchor_x, anchor_z, step_x as i8, 0, size, extra_flag, collision) {
+                    return Ok(false);
+                }
+                anchor_x += step_x;
+                if let Some(coordinates) = coordinates.as_deref_mut() {
+                    coordinates.push(CoordGrid::try_new(y, anchor_x, anchor_z)?.packed);
+                }
+            }
+        }
+    }
+    return Ok(true);
+}
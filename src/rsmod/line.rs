@@ -21,16 +21,20 @@ impl Line {
     pub const WALK_BLOCKED_WEST: u32 =
         CollisionFlag::WALL_WEST as u32 | CollisionFlag::WALK_BLOCKED as u32;
 
-    pub const HALF_TILE: i32 = (1 << 16) / 2;
+    pub const HALF_TILE: i64 = (1 << 16) / 2;
 
+    /**
+     * Scales tiles up into 16.16 fixed point. This is done in an i64 so the
+     * coordinates of a sparse map don't overflow past 32767.
+     */
     #[inline(always)]
-    pub fn scale_up(tiles: i32) -> i32 {
-        return tiles << 16;
+    pub fn scale_up(tiles: i32) -> i64 {
+        return (tiles as i64) << 16;
     }
 
     #[inline(always)]
-    pub fn scale_down(tiles: i32) -> i32 {
-        return tiles >> 16;
+    pub fn scale_down(tiles: i64) -> i32 {
+        return (tiles >> 16) as i32;
     }
 
     #[inline(always)]
@@ -1,11 +1,15 @@
-use crate::rsmod::collision::collision::CollisionFlagMap;
+use crate::rsmod::collision::collision::CollisionFlagReader;
 use crate::rsmod::collision_flag::CollisionFlag;
-use crate::rsmod::CoordGrid;
+use crate::rsmod::{CoordOutOfRange, PackedCoord};
 use crate::rsmod::line::Line;
+use crate::rsmod::ray_result::{RayBlock, RayBlocker, RayResult};
 
+/**
+ * Returns the packed tiles of the line, or [CoordOutOfRange] if one of them can't be packed into a [CoordGrid].
+ */
 #[inline(always)]
 pub unsafe fn line_of_sight(
-    flags: &CollisionFlagMap,
+    flags: &impl CollisionFlagReader,
     y: i32,
     src_x: i32,
     src_z: i32,
@@ -16,7 +20,39 @@ pub unsafe fn line_of_sight(
     dest_width: u8,
     dest_height: u8,
     extra_flag: u32,
-) -> Vec<u32> {
+) -> Result<Vec<u32>, CoordOutOfRange> {
+    return line_of_sight_result(
+        flags,
+        y,
+        src_x,
+        src_z,
+        dest_x,
+        dest_z,
+        src_width,
+        src_height,
+        dest_width,
+        dest_height,
+        extra_flag,
+    ).map(RayResult::into_line);
+}
+
+/**
+ * Like [line_of_sight], but keeps the tiles up to an obstruction and reports what blocked the line.
+ */
+#[inline(always)]
+pub unsafe fn line_of_sight_result(
+    flags: &impl CollisionFlagReader,
+    y: i32,
+    src_x: i32,
+    src_z: i32,
+    dest_x: i32,
+    dest_z: i32,
+    src_width: u8,
+    src_height: u8,
+    dest_width: u8,
+    dest_height: u8,
+    extra_flag: u32,
+) -> Result<RayResult, CoordOutOfRange> {
     return rayCastPath(
         flags,
         y,
@@ -38,9 +74,83 @@ pub unsafe fn line_of_sight(
     );
 }
 
+/**
+ * Like [line_of_sight_result], but packs the tiles into [crate::rsmod::WideCoordGrid]s so lines
+ * past 14 bit coordinates in a sparse map can be returned.
+ */
+#[inline(always)]
+pub unsafe fn line_of_sight_wide(
+    flags: &impl CollisionFlagReader,
+    y: i32,
+    src_x: i32,
+    src_z: i32,
+    dest_x: i32,
+    dest_z: i32,
+    src_width: u8,
+    src_height: u8,
+    dest_width: u8,
+    dest_height: u8,
+    extra_flag: u32,
+) -> Result<RayResult<u64>, CoordOutOfRange> {
+    return rayCastPath(
+        flags,
+        y,
+        src_x,
+        src_z,
+        dest_x,
+        dest_z,
+        src_width,
+        src_height,
+        dest_width,
+        dest_height,
+        Line::SIGHT_BLOCKED_WEST | extra_flag,
+        Line::SIGHT_BLOCKED_EAST | extra_flag,
+        Line::SIGHT_BLOCKED_SOUTH | extra_flag,
+        Line::SIGHT_BLOCKED_NORTH | extra_flag,
+        CollisionFlag::LOC as u32 | extra_flag,
+        CollisionFlag::LOC_PROJ_BLOCKER as u32 | extra_flag,
+        true,
+    );
+}
+
+/**
+ * Returns the packed tiles of the line, or [CoordOutOfRange] if one of them can't be packed into a [CoordGrid].
+ */
 #[inline(always)]
 pub unsafe fn line_of_walk(
-    flags: &CollisionFlagMap,
+    flags: &impl CollisionFlagReader,
+    y: i32,
+    src_x: i32,
+    src_z: i32,
+    dest_x: i32,
+    dest_z: i32,
+    src_width: u8,
+    src_height: u8,
+    dest_width: u8,
+    dest_height: u8,
+    extra_flag: u32,
+) -> Result<Vec<u32>, CoordOutOfRange> {
+    return line_of_walk_result(
+        flags,
+        y,
+        src_x,
+        src_z,
+        dest_x,
+        dest_z,
+        src_width,
+        src_height,
+        dest_width,
+        dest_height,
+        extra_flag,
+    ).map(RayResult::into_line);
+}
+
+/**
+ * Like [line_of_walk], but keeps the tiles up to an obstruction and reports what blocked the line.
+ */
+#[inline(always)]
+pub unsafe fn line_of_walk_result(
+    flags: &impl CollisionFlagReader,
     y: i32,
     src_x: i32,
     src_z: i32,
@@ -51,7 +161,7 @@ pub unsafe fn line_of_walk(
     dest_width: u8,
     dest_height: u8,
     extra_flag: u32,
-) -> Vec<u32> {
+) -> Result<RayResult, CoordOutOfRange> {
     return rayCastPath(
         flags,
         y,
@@ -73,9 +183,48 @@ pub unsafe fn line_of_walk(
     );
 }
 
+/**
+ * Like [line_of_walk_result], but packs the tiles into [crate::rsmod::WideCoordGrid]s so lines
+ * past 14 bit coordinates in a sparse map can be returned.
+ */
 #[inline(always)]
-unsafe fn rayCastPath(
-    flags: &CollisionFlagMap,
+pub unsafe fn line_of_walk_wide(
+    flags: &impl CollisionFlagReader,
+    y: i32,
+    src_x: i32,
+    src_z: i32,
+    dest_x: i32,
+    dest_z: i32,
+    src_width: u8,
+    src_height: u8,
+    dest_width: u8,
+    dest_height: u8,
+    extra_flag: u32,
+) -> Result<RayResult<u64>, CoordOutOfRange> {
+    return rayCastPath(
+        flags,
+        y,
+        src_x,
+        src_z,
+        dest_x,
+        dest_z,
+        src_width,
+        src_height,
+        dest_width,
+        dest_height,
+        Line::WALK_BLOCKED_WEST | extra_flag,
+        Line::WALK_BLOCKED_EAST | extra_flag,
+        Line::WALK_BLOCKED_SOUTH | extra_flag,
+        Line::WALK_BLOCKED_NORTH | extra_flag,
+        CollisionFlag::LOC as u32 | extra_flag,
+        CollisionFlag::LOC_PROJ_BLOCKER as u32 | extra_flag,
+        false,
+    );
+}
+
+#[inline(always)]
+unsafe fn rayCastPath<T: PackedCoord>(
+    flags: &impl CollisionFlagReader,
     y: i32,
     src_x: i32,
     src_z: i32,
@@ -92,7 +241,7 @@ unsafe fn rayCastPath(
     flag_loc: u32,
     flag_proj: u32,
     los: bool,
-) -> Vec<u32> {
+) -> Result<RayResult<T>, CoordOutOfRange> {
     let start_x: i32 = Line::coordinate(src_x, dest_x, src_width);
     let start_z: i32 = Line::coordinate(src_z, dest_z, src_height);
 
@@ -100,11 +249,11 @@ unsafe fn rayCastPath(
     let end_z: i32 = Line::coordinate(dest_z, src_z, dest_height);
 
     if start_x == end_x && start_z == end_z {
-        return vec![];
+        return Ok(RayResult { tiles: vec![], blocked: None });
     }
 
     if los && flags.is_flagged(start_x, start_z, y, flag_loc) {
-        return vec![];
+        return Ok(blocked(flags, vec![], start_x, start_z, y, flag_loc));
     }
 
     let delta_x: i32 = end_x - start_x;
@@ -118,13 +267,13 @@ unsafe fn rayCastPath(
     let mut x_flags: u32 = if travel_east { flag_west } else { flag_east };
     let mut z_flags: u32 = if travel_north { flag_south } else { flag_north };
 
-    let mut coordinates: Vec<u32> = vec![];
+    let mut coordinates: Vec<T> = vec![];
     if absolute_delta_x > absolute_delta_z {
         let offset_x: i32 = if travel_east { 1 } else { -1 };
         let offset_z: i32 = if travel_north { 0 } else { -1 };
 
-        let mut scaled_z: i32 = Line::scale_up(start_z) + Line::HALF_TILE + offset_z;
-        let tangent: i32 = Line::scale_up(delta_z) / absolute_delta_x;
+        let mut scaled_z: i64 = Line::scale_up(start_z) + Line::HALF_TILE + offset_z as i64;
+        let tangent: i64 = Line::scale_up(delta_z) / absolute_delta_x as i64;
 
         let mut curr_x: i32 = start_x;
         while curr_x != end_x {
@@ -134,9 +283,9 @@ unsafe fn rayCastPath(
                 x_flags &= !flag_proj;
             }
             if flags.is_flagged(curr_x, curr_z, y, x_flags) {
-                return vec![]; // alternative
+                return Ok(blocked(flags, coordinates, curr_x, curr_z, y, x_flags));
             }
-            coordinates.push(CoordGrid::new(y, curr_x, curr_z).packed);
+            coordinates.push(T::pack(y, curr_x, curr_z)?);
 
             scaled_z += tangent;
 
@@ -146,17 +295,17 @@ unsafe fn rayCastPath(
                     z_flags &= !flag_proj;
                 }
                 if flags.is_flagged(curr_x, nextZ, y, z_flags) {
-                    return vec![]; // alternative
+                    return Ok(blocked(flags, coordinates, curr_x, nextZ, y, z_flags));
                 }
-                coordinates.push(CoordGrid::new(y, curr_x, nextZ).packed);
+                coordinates.push(T::pack(y, curr_x, nextZ)?);
             }
         }
     } else {
         let offset_x: i32 = if travel_east { 0 } else { -1 };
         let offset_z: i32 = if travel_north { 1 } else { -1 };
 
-        let mut scaled_x: i32 = Line::scale_up(start_x) + Line::HALF_TILE + offset_x;
-        let tangent: i32 = Line::scale_up(delta_x) / absolute_delta_z;
+        let mut scaled_x: i64 = Line::scale_up(start_x) + Line::HALF_TILE + offset_x as i64;
+        let tangent: i64 = Line::scale_up(delta_x) / absolute_delta_z as i64;
 
         let mut curr_z: i32 = start_z;
         while curr_z != end_z {
@@ -166,9 +315,9 @@ unsafe fn rayCastPath(
                 z_flags &= !flag_proj;
             }
             if flags.is_flagged(curr_x, curr_z, y, z_flags) {
-                return vec![]; // alternative
+                return Ok(blocked(flags, coordinates, curr_x, curr_z, y, z_flags));
             }
-            coordinates.push(CoordGrid::new(y, curr_x, curr_z).packed);
+            coordinates.push(T::pack(y, curr_x, curr_z)?);
 
             scaled_x += tangent;
 
@@ -178,11 +327,27 @@ unsafe fn rayCastPath(
                     x_flags &= !flag_proj;
                 }
                 if flags.is_flagged(nextX, curr_z, y, x_flags) {
-                    return vec![]; // alternative
+                    return Ok(blocked(flags, coordinates, nextX, curr_z, y, x_flags));
                 }
-                coordinates.push(CoordGrid::new(y, nextX, curr_z).packed);
+                coordinates.push(T::pack(y, nextX, curr_z)?);
             }
         }
     }
-    return coordinates;
+    return Ok(RayResult { tiles: coordinates, blocked: None });
+}
+
+#[inline(always)]
+unsafe fn blocked<T>(
+    flags: &impl CollisionFlagReader,
+    tiles: Vec<T>,
+    x: i32,
+    z: i32,
+    y: i32,
+    mask: u32,
+) -> RayResult<T> {
+    let hit: u32 = flags.get(x, z, y) & mask;
+    return RayResult {
+        tiles,
+        blocked: Some(RayBlock { x, z, y, flags: hit, blocker: RayBlocker::of(hit) }),
+    };
 }
@@ -1,9 +1,9 @@
-use crate::rsmod::collision::collision::CollisionFlagMap;
+use crate::rsmod::collision::collision::CollisionFlagReader;
 use crate::rsmod::collision_flag::CollisionFlag;
 use crate::rsmod::line::Line;
 
 pub unsafe fn has_line_of_sight(
-    flags: &CollisionFlagMap,
+    flags: &impl CollisionFlagReader,
     y: i32,
     src_x: i32,
     src_z: i32,
@@ -38,7 +38,7 @@ pub unsafe fn has_line_of_sight(
 
 #[inline(always)]
 pub unsafe fn has_line_of_walk(
-    flags: &CollisionFlagMap,
+    flags: &impl CollisionFlagReader,
     y: i32,
     src_x: i32,
     src_z: i32,
@@ -73,7 +73,7 @@ pub unsafe fn has_line_of_walk(
 
 #[inline(always)]
 unsafe fn rayCastLine(
-    flags: &CollisionFlagMap,
+    flags: &impl CollisionFlagReader,
     y: i32,
     src_x: i32,
     src_z: i32,
@@ -120,8 +120,8 @@ unsafe fn rayCastLine(
         let offset_x: i32 = if travel_east { 1 } else { -1 };
         let offset_z: i32 = if travel_north { 0 } else { -1 };
 
-        let mut scaled_z: i32 = Line::scale_up(start_z) + Line::HALF_TILE + offset_z;
-        let tangent: i32 = Line::scale_up(delta_z) / absolute_delta_x;
+        let mut scaled_z: i64 = Line::scale_up(start_z) + Line::HALF_TILE + offset_z as i64;
+        let tangent: i64 = Line::scale_up(delta_z) / absolute_delta_x as i64;
 
         let mut curr_x: i32 = start_x;
         while curr_x != end_x {
@@ -148,8 +148,8 @@ unsafe fn rayCastLine(
         let offset_x: i32 = if travel_east { 0 } else { -1 };
         let offset_z: i32 = if travel_north { 1 } else { -1 };
 
-        let mut scaled_x: i32 = Line::scale_up(start_x) + Line::HALF_TILE + offset_x;
-        let tangent: i32 = Line::scale_up(delta_x) / absolute_delta_z;
+        let mut scaled_x: i64 = Line::scale_up(start_x) + Line::HALF_TILE + offset_x as i64;
+        let tangent: i64 = Line::scale_up(delta_x) / absolute_delta_z as i64;
 
         let mut curr_z: i32 = start_z;
         while curr_z != end_z {
@@ -2,6 +2,7 @@
 
 use std::process;
 
+#[cfg_attr(feature = "wasm", wasm_bindgen::prelude::wasm_bindgen)]
 #[repr(u8)]
 #[derive(PartialEq)]
 pub enum LocAngle {
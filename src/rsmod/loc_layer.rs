@@ -1,6 +1,8 @@
 #![allow(non_camel_case_types)]
 
+#[cfg_attr(feature = "wasm", wasm_bindgen::prelude::wasm_bindgen)]
 #[repr(u8)]
+#[derive(Clone, Copy, PartialEq, Debug)]
 pub enum LocLayer {
     WALL = 0,
     WALL_DECOR = 1,
@@ -3,8 +3,11 @@
 use std::cmp::PartialEq;
 use std::process;
 
+use crate::rsmod::LocLayer;
+
+#[cfg_attr(feature = "wasm", wasm_bindgen::prelude::wasm_bindgen)]
 #[repr(i8)]
-#[derive(PartialEq)]
+#[derive(Clone, Copy, PartialEq, Debug)]
 pub enum LocShape {
     WALL_STRAIGHT = 0,
     WALL_DIAGONAL_CORNER = 1,
@@ -31,34 +34,79 @@ pub enum LocShape {
     GROUND_DECOR = 22,
 }
 
+impl LocShape {
+    /**
+     * Returns the shape for the given id, or `None` if the id is not a known shape.
+     */
+    #[inline(always)]
+    pub fn try_from_i8(value: i8) -> Option<LocShape> {
+        return match value {
+            0 => Some(LocShape::WALL_STRAIGHT),
+            1 => Some(LocShape::WALL_DIAGONAL_CORNER),
+            2 => Some(LocShape::WALL_L),
+            3 => Some(LocShape::WALL_SQUARE_CORNER),
+            4 => Some(LocShape::WALLDECOR_STRAIGHT_NOOFFSET),
+            5 => Some(LocShape::WALLDECOR_STRAIGHT_OFFSET),
+            6 => Some(LocShape::WALLDECOR_DIAGONAL_OFFSET),
+            7 => Some(LocShape::WALLDECOR_DIAGONAL_NOOFFSET),
+            8 => Some(LocShape::WALLDECOR_DIAGONAL_BOTH),
+            9 => Some(LocShape::WALL_DIAGONAL),
+            10 => Some(LocShape::CENTREPIECE_STRAIGHT),
+            11 => Some(LocShape::CENTREPIECE_DIAGONAL),
+            12 => Some(LocShape::ROOF_STRAIGHT),
+            13 => Some(LocShape::ROOF_DIAGONAL_WITH_ROOFEDGE),
+            14 => Some(LocShape::ROOF_DIAGONAL),
+            15 => Some(LocShape::ROOF_L_CONCAVE),
+            16 => Some(LocShape::ROOF_L_CONVEX),
+            17 => Some(LocShape::ROOF_FLAT),
+            18 => Some(LocShape::ROOFEDGE_STRAIGHT),
+            19 => Some(LocShape::ROOFEDGE_DIAGONAL_CORNER),
+            20 => Some(LocShape::ROOFEDGE_L),
+            21 => Some(LocShape::ROOFEDGE_SQUARE_CORNER),
+            22 => Some(LocShape::GROUND_DECOR),
+            _ => None,
+        };
+    }
+
+    #[inline(always)]
+    pub fn layer(&self) -> LocLayer {
+        return match self {
+            LocShape::WALL_STRAIGHT
+            | LocShape::WALL_DIAGONAL_CORNER
+            | LocShape::WALL_L
+            | LocShape::WALL_SQUARE_CORNER => LocLayer::WALL,
+
+            LocShape::WALLDECOR_STRAIGHT_NOOFFSET
+            | LocShape::WALLDECOR_STRAIGHT_OFFSET
+            | LocShape::WALLDECOR_DIAGONAL_OFFSET
+            | LocShape::WALLDECOR_DIAGONAL_NOOFFSET
+            | LocShape::WALLDECOR_DIAGONAL_BOTH => LocLayer::WALL_DECOR,
+
+            LocShape::WALL_DIAGONAL
+            | LocShape::CENTREPIECE_STRAIGHT
+            | LocShape::CENTREPIECE_DIAGONAL
+            | LocShape::ROOF_STRAIGHT
+            | LocShape::ROOF_DIAGONAL_WITH_ROOFEDGE
+            | LocShape::ROOF_DIAGONAL
+            | LocShape::ROOF_L_CONCAVE
+            | LocShape::ROOF_L_CONVEX
+            | LocShape::ROOF_FLAT
+            | LocShape::ROOFEDGE_STRAIGHT
+            | LocShape::ROOFEDGE_DIAGONAL_CORNER
+            | LocShape::ROOFEDGE_L
+            | LocShape::ROOFEDGE_SQUARE_CORNER => LocLayer::GROUND,
+
+            LocShape::GROUND_DECOR => LocLayer::GROUND_DECOR,
+        };
+    }
+}
+
 impl From<i8> for LocShape {
     #[inline(always)]
     fn from(value: i8) -> LocShape {
-        return match value {
-            0 => LocShape::WALL_STRAIGHT,
-            1 => LocShape::WALL_DIAGONAL_CORNER,
-            2 => LocShape::WALL_L,
-            3 => LocShape::WALL_SQUARE_CORNER,
-            4 => LocShape::WALLDECOR_STRAIGHT_NOOFFSET,
-            5 => LocShape::WALLDECOR_STRAIGHT_OFFSET,
-            6 => LocShape::WALLDECOR_DIAGONAL_OFFSET,
-            7 => LocShape::WALLDECOR_DIAGONAL_NOOFFSET,
-            8 => LocShape::WALLDECOR_DIAGONAL_BOTH,
-            9 => LocShape::WALL_DIAGONAL,
-            10 => LocShape::CENTREPIECE_STRAIGHT,
-            11 => LocShape::CENTREPIECE_DIAGONAL,
-            12 => LocShape::ROOF_STRAIGHT,
-            13 => LocShape::ROOF_DIAGONAL_WITH_ROOFEDGE,
-            14 => LocShape::ROOF_DIAGONAL,
-            15 => LocShape::ROOF_L_CONCAVE,
-            16 => LocShape::ROOF_L_CONVEX,
-            17 => LocShape::ROOF_FLAT,
-            18 => LocShape::ROOFEDGE_STRAIGHT,
-            19 => LocShape::ROOFEDGE_DIAGONAL_CORNER,
-            20 => LocShape::ROOFEDGE_L,
-            21 => LocShape::ROOFEDGE_SQUARE_CORNER,
-            22 => LocShape::GROUND_DECOR,
-            _ => process::abort(), // unreachable!("[LocShape] Invalid value used for shape! {}", value),
+        return match LocShape::try_from_i8(value) {
+            Some(shape) => shape,
+            None => process::abort(), // unreachable!("[LocShape] Invalid value used for shape! {}", value),
         };
     }
 }
@@ -0,0 +1,190 @@
+use crate::rsmod::{CoordOutOfRange, LocLayer, LocShape};
+use crate::rsmod::collision::collision::CollisionFlagMap;
+use crate::rsmod::collision::collision_change::CollisionFlagWriter;
+use crate::rsmod::rotation::{rotate, rotate_flags};
+
+/**
+ * The collision relevant properties of a loc config. `width` and `length` are unrotated.
+ */
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct LocType {
+    pub width: u8,
+    pub length: u8,
+    pub blockwalk: bool,
+    pub blockrange: bool,
+    pub breakroutefinding: bool,
+    pub forceapproach: u8,
+    pub active: bool,
+}
+
+impl LocType {
+    #[inline(always)]
+    pub fn new(
+        width: u8,
+        length: u8,
+        blockwalk: bool,
+        blockrange: bool,
+        breakroutefinding: bool,
+        forceapproach: u8,
+        active: bool,
+    ) -> LocType {
+        return LocType {
+            width,
+            length,
+            blockwalk,
+            blockrange,
+            breakroutefinding,
+            forceapproach,
+            active,
+        };
+    }
+
+    #[inline(always)]
+    pub fn rotated_width(&self, angle: u8) -> u8 {
+        return rotate(angle, self.width, self.length);
+    }
+
+    #[inline(always)]
+    pub fn rotated_length(&self, angle: u8) -> u8 {
+        return rotate(angle, self.length, self.width);
+    }
+
+    /**
+     * The unrotated block access flags, as expected by the pathfinder and [ReachStrategy].
+     */
+    #[inline(always)]
+    pub fn block_access_flags(&self) -> u8 {
+        return self.forceapproach & 0xf;
+    }
+
+    #[inline(always)]
+    pub fn rotated_block_access_flags(&self, angle: u8) -> u8 {
+        return rotate_flags(angle, self.block_access_flags());
+    }
+
+    /**
+     * Ground decoration only blocks when it can be interacted with, everything else
+     * blocks as long as it blocks walking.
+     */
+    #[inline(always)]
+    pub fn has_collision(&self, shape: LocShape) -> bool {
+        return self.blockwalk && (shape.layer() != LocLayer::GROUND_DECOR || self.active);
+    }
+}
+
+/**
+ * Loc configs indexed by id, registered once so collision can be applied by loc id.
+ */
+#[derive(Clone, Default)]
+pub struct LocTypeRegistry {
+    types: Vec<Option<LocType>>,
+}
+
+impl LocTypeRegistry {
+    /**
+     * Ids go up to this, exclusive, so a bad id can't size the table past a few megabytes.
+     */
+    pub const ID_LIMIT: u32 = 0x100000;
+
+    #[inline(always)]
+    pub fn new() -> LocTypeRegistry {
+        return LocTypeRegistry { types: vec![] };
+    }
+
+    /**
+     * Returns false without registering anything if `id` is not below [LocTypeRegistry::ID_LIMIT].
+     */
+    #[inline(always)]
+    pub fn register(&mut self, id: u32, loc: LocType) -> bool {
+        if id >= LocTypeRegistry::ID_LIMIT {
+            return false;
+        }
+        let index: usize = id as usize;
+        if index >= self.types.len() {
+            self.types.resize(index + 1, None);
+        }
+        self.types[index] = Some(loc);
+        return true;
+    }
+
+    #[inline(always)]
+    pub fn unregister(&mut self, id: u32) {
+        if let Some(loc) = self.types.get_mut(id as usize) {
+            *loc = None;
+        }
+    }
+
+    #[inline(always)]
+    pub fn get(&self, id: u32) -> Option<&LocType> {
+        return self.types.get(id as usize).and_then(|loc| loc.as_ref());
+    }
+
+    /**
+     * Applies the collision of loc `id` placed at `x`, `z` with the given shape and angle.
+     * Returns false if no loc is registered under `id`, or [CoordOutOfRange] if it doesn't fit in the map.
+     */
+    #[inline(always)]
+    pub unsafe fn add_loc(
+        &self,
+        flags: &mut CollisionFlagMap,
+        id: u32,
+        x: i32,
+        z: i32,
+        y: i32,
+        shape: LocShape,
+        angle: u8,
+    ) -> Result<bool, CoordOutOfRange> {
+        return self.change_loc(flags, id, x, z, y, shape, angle, true);
+    }
+
+    /**
+     * Reverts the collision of loc `id` placed at `x`, `z` with the given shape and angle.
+     * Returns false if no loc is registered under `id`, or [CoordOutOfRange] if it doesn't fit in the map.
+     */
+    #[inline(always)]
+    pub unsafe fn remove_loc(
+        &self,
+        flags: &mut CollisionFlagMap,
+        id: u32,
+        x: i32,
+        z: i32,
+        y: i32,
+        shape: LocShape,
+        angle: u8,
+    ) -> Result<bool, CoordOutOfRange> {
+        return self.change_loc(flags, id, x, z, y, shape, angle, false);
+    }
+
+    #[inline(always)]
+    unsafe fn change_loc(
+        &self,
+        flags: &mut CollisionFlagMap,
+        id: u32,
+        x: i32,
+        z: i32,
+        y: i32,
+        shape: LocShape,
+        angle: u8,
+        add: bool,
+    ) -> Result<bool, CoordOutOfRange> {
+        let loc: &LocType = match self.get(id) {
+            Some(loc) => loc,
+            None => return Ok(false),
+        };
+        if loc.has_collision(shape) {
+            flags.change_loc_shape(
+                x,
+                z,
+                y,
+                loc.width,
+                loc.length,
+                angle,
+                shape,
+                loc.blockrange,
+                loc.breakroutefinding,
+                add,
+            )?;
+        }
+        return Ok(true);
+    }
+}
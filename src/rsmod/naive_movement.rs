@@ -0,0 +1,224 @@
+use crate::rsmod::collision::collision::CollisionFlagReader;
+use crate::rsmod::collision_strategy::CollisionStrategies;
+use crate::rsmod::coord_grid::{CoordGrid, CoordOutOfRange};
+use crate::rsmod::naive_pathfinder::{intersects, is_adjacent, naive_destination, step_towards, PathRandom, DIRECTIONS};
+use crate::rsmod::step_validator::can_travel_rect;
+
+/**
+ * How many steps a chasing mover takes per tick.
+ */
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum MoveSpeed {
+    WALK = 1,
+    RUN = 2,
+}
+
+/**
+ * What the mover did on one tick of a [simulate_chase].
+ */
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum ChaseState {
+    /** Took at least one step and isn't next to the target yet. */
+    MOVED,
+    /** Next to a side of the target, where it stops. */
+    ARRIVED,
+    /** Couldn't take a single step while away from the target, it stays put until the target moves. */
+    STUCK,
+}
+
+/**
+ * Where the mover ended one tick of a [simulate_chase].
+ */
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct ChaseTick {
+    pub x: i32,
+    pub z: i32,
+    pub state: ChaseState,
+}
+
+/**
+ * The ticks of a [simulate_chase], in order.
+ */
+#[derive(Clone, PartialEq, Debug)]
+pub struct ChaseSimulation {
+    pub y: i32,
+    pub ticks: Vec<ChaseTick>,
+}
+
+impl ChaseSimulation {
+    /**
+     * Returns the south-west tile of the mover at the end of every tick,
+     * or [CoordOutOfRange] if one of them can't be packed into a [CoordGrid].
+     */
+    #[inline(always)]
+    pub fn positions(&self) -> Result<Vec<u32>, CoordOutOfRange> {
+        return self
+            .ticks
+            .iter()
+            .map(|tick| CoordGrid::try_new(self.y, tick.x, tick.z).map(|coord| coord.packed))
+            .collect();
+    }
+
+    /**
+     * Returns the first tick the mover ended next to the target.
+     */
+    #[inline(always)]
+    pub fn arrived_tick(&self) -> Option<usize> {
+        return self.ticks.iter().position(|tick| tick.state == ChaseState::ARRIVED);
+    }
+
+    /**
+     * Returns if the mover ended the simulation stuck, e.g. behind a loc while the target attacks from a safe spot.
+     */
+    #[inline(always)]
+    pub fn is_safe_spotted(&self) -> bool {
+        return self.ticks.last().is_some_and(|tick| tick.state == ChaseState::STUCK);
+    }
+
+    /**
+     * Returns the tick the mover has been stuck since, if it ended the simulation stuck.
+     */
+    #[inline(always)]
+    pub fn stuck_since(&self) -> Option<usize> {
+        if !self.is_safe_spotted() {
+            return None;
+        }
+        let moving: Option<usize> = self.ticks.iter().rposition(|tick| tick.state != ChaseState::STUCK);
+        return Some(moving.map_or(0, |tick| tick + 1));
+    }
+}
+
+/**
+ * Moves a mover with [crate::rsmod::find_naive_path] movement for `ticks` ticks, the way a server
+ * re-calls it every tick for a chasing npc, taking one step at a time and re-aiming after every step.
+ * `targets` holds the target's south-west tile for each tick, the last one being kept once they run out.
+ * A mover under its target steps out of the way in a direction picked by `random`, trying the others
+ * in turn if that one is blocked, and is only [ChaseState::STUCK] when it can't step out at all.
+ */
+#[inline(always)]
+pub unsafe fn simulate_chase(
+    flags: &impl CollisionFlagReader,
+    y: i32,
+    src_x: i32,
+    src_z: i32,
+    src_width: u8,
+    src_height: u8,
+    targets: &[u32],
+    dest_width: u8,
+    dest_height: u8,
+    ticks: usize,
+    speed: MoveSpeed,
+    extra_flag: u32,
+    collision: &CollisionStrategies,
+    random: &mut dyn PathRandom,
+) -> ChaseSimulation {
+    let mut simulation: ChaseSimulation = ChaseSimulation { y, ticks: Vec::with_capacity(ticks) };
+    if targets.is_empty() {
+        return simulation;
+    }
+    let mut currX: i32 = src_x;
+    let mut currZ: i32 = src_z;
+    for tick in 0..ticks {
+        let target: CoordGrid = CoordGrid::from(targets[tick.min(targets.len() - 1)]);
+        let dest_x: i32 = target.x() as i32;
+        let dest_z: i32 = target.z() as i32;
+        let mut state: ChaseState = ChaseState::STUCK;
+        for step in 0..speed as usize {
+            if is_adjacent(
+                currX,
+                currZ,
+                src_width as i32,
+                src_height as i32,
+                dest_x,
+                dest_z,
+                dest_width as i32,
+                dest_height as i32,
+            ) {
+                state = ChaseState::ARRIVED;
+                break;
+            }
+            let next: Option<(i32, i32)> = if intersects(
+                currX,
+                currZ,
+                src_width,
+                src_height,
+                dest_x,
+                dest_z,
+                dest_width,
+                dest_height,
+            ) {
+                // a blocked random direction isn't the end of it, the others are tried in order after it.
+                let first: usize = random.next_int(DIRECTIONS.len() as u32) as usize;
+                (0..DIRECTIONS.len())
+                    .map(|turn| DIRECTIONS[(first + turn) % DIRECTIONS.len()])
+                    .find(|direction| {
+                        can_travel_rect(
+                            flags,
+                            y,
+                            currX,
+                            currZ,
+                            direction[0] as i8,
+                            direction[1] as i8,
+                            src_width,
+                            src_height,
+                            extra_flag,
+                            collision,
+                        )
+                    })
+                    .map(|direction| (currX + direction[0], currZ + direction[1]))
+            } else {
+                match naive_destination(
+                    currX,
+                    currZ,
+                    src_width as i32,
+                    src_height as i32,
+                    dest_x,
+                    dest_z,
+                    dest_width as i32,
+                    dest_height as i32,
+                ) {
+                    Some((to_x, to_z)) => step_towards(
+                        flags,
+                        y,
+                        currX,
+                        currZ,
+                        to_x,
+                        to_z,
+                        src_width,
+                        src_height,
+                        dest_x,
+                        dest_z,
+                        dest_width,
+                        dest_height,
+                        extra_flag,
+                        collision,
+                    ),
+                    None => None,
+                }
+            };
+            match next {
+                Some((nextX, nextZ)) => {
+                    currX = nextX;
+                    currZ = nextZ;
+                    state = ChaseState::MOVED;
+                }
+                None => break,
+            }
+            // the last step of the tick may have been the one that reached the target.
+            if step + 1 == speed as usize && is_adjacent(
+                currX,
+                currZ,
+                src_width as i32,
+                src_height as i32,
+                dest_x,
+                dest_z,
+                dest_width as i32,
+                dest_height as i32,
+            ) {
+                state = ChaseState::ARRIVED;
+            }
+        }
+        simulation.ticks.push(ChaseTick { x: currX, z: currZ, state });
+    }
+    return simulation;
+}
@@ -1,20 +1,139 @@
+#[cfg(feature = "rand")]
 use rand::Rng;
 
-use crate::rsmod::{can_travel, CollisionStrategies, CoordGrid};
-use crate::rsmod::collision::collision::CollisionFlagMap;
+use crate::rsmod::{can_travel_rect, CollisionStrategies, CoordGrid, CoordOutOfRange};
+use crate::rsmod::collision::collision::CollisionFlagReader;
 
-const DIRECTIONS: [[i32; 2]; 4] = [
+pub(crate) const DIRECTIONS: [[i32; 2]; 4] = [
     [-1, 0], // West
     [1, 0],  // East
     [0, 1],  // North
     [0, -1], // South
 ];
 
+/**
+ * Picks the way a source steps out from under its target in [find_naive_path_with],
+ * so the caller's own RNG can drive it and recorded movement replays exactly.
+ */
+pub trait PathRandom {
+    /**
+     * Returns a value in `0..bound`.
+     */
+    fn next_int(&mut self, bound: u32) -> u32;
+}
+
+/**
+ * A small deterministic [PathRandom] (splitmix64), the same seed always giving the same steps.
+ */
+#[derive(Clone, Copy, PartialEq, Debug, Default)]
+pub struct SeededRandom {
+    state: u64,
+}
+
+impl SeededRandom {
+    #[inline(always)]
+    pub fn new(seed: u64) -> SeededRandom {
+        return SeededRandom { state: seed };
+    }
+}
+
+impl PathRandom for SeededRandom {
+    #[inline(always)]
+    fn next_int(&mut self, bound: u32) -> u32 {
+        self.state = self.state.wrapping_add(0x9e3779b97f4a7c15);
+        let mut value: u64 = self.state;
+        value = (value ^ (value >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+        value = (value ^ (value >> 27)).wrapping_mul(0x94d049bb133111eb);
+        value ^= value >> 31;
+        return (((value >> 32) * bound as u64) >> 32) as u32;
+    }
+}
+
+#[cfg(feature = "rand")]
+impl<R: rand::RngCore> PathRandom for R {
+    #[inline(always)]
+    fn next_int(&mut self, bound: u32) -> u32 {
+        return self.gen_range(0..bound);
+    }
+}
+
+/**
+ * Steps out from under the target in a random direction, see [find_naive_path_with] to choose the RNG.
+ * Returns [CoordOutOfRange] if the destination can't be packed into a [CoordGrid].
+ */
+#[inline(always)]
+pub unsafe fn find_naive_path(
+    flags: &impl CollisionFlagReader,
+    y: i32,
+    src_x: i32,
+    src_z: i32,
+    dest_x: i32,
+    dest_z: i32,
+    src_width: u8,
+    src_height: u8,
+    dest_width: u8,
+    dest_height: u8,
+    extra_flag: u32,
+    collision: &CollisionStrategies,
+) -> Result<Vec<u32>, CoordOutOfRange> {
+    return naive_path(
+        flags,
+        y,
+        src_x,
+        src_z,
+        dest_x,
+        dest_z,
+        src_width,
+        src_height,
+        dest_width,
+        dest_height,
+        extra_flag,
+        collision,
+        random_direction,
+    );
+}
+
+/**
+ * Like [find_naive_path], but the way out from under the target is picked by `random`.
+ */
+#[inline(always)]
+pub unsafe fn find_naive_path_with(
+    flags: &impl CollisionFlagReader,
+    y: i32,
+    src_x: i32,
+    src_z: i32,
+    dest_x: i32,
+    dest_z: i32,
+    src_width: u8,
+    src_height: u8,
+    dest_width: u8,
+    dest_height: u8,
+    extra_flag: u32,
+    collision: &CollisionStrategies,
+    random: &mut dyn PathRandom,
+) -> Result<Vec<u32>, CoordOutOfRange> {
+    return naive_path(
+        flags,
+        y,
+        src_x,
+        src_z,
+        dest_x,
+        dest_z,
+        src_width,
+        src_height,
+        dest_width,
+        dest_height,
+        extra_flag,
+        collision,
+        |_, _, _| random.next_int(DIRECTIONS.len() as u32) as usize,
+    );
+}
+
 // https://gist.github.com/Z-Kris/2eb1c2fbc22aa7486a57089c82f293f8
 // https://gist.github.com/Z-Kris/fe476d75a51374f12dca999700f009f7
 #[inline(always)]
-pub unsafe fn find_naive_path(
-    flags: &CollisionFlagMap,
+unsafe fn naive_path(
+    flags: &impl CollisionFlagReader,
     y: i32,
     src_x: i32,
     src_z: i32,
@@ -26,7 +145,8 @@ pub unsafe fn find_naive_path(
     dest_height: u8,
     extra_flag: u32,
     collision: &CollisionStrategies,
-) -> Vec<u32> {
+    direction: impl FnOnce(i32, i32, i32) -> usize,
+) -> Result<Vec<u32>, CoordOutOfRange> {
     // If we are intersecting at all, the path needs to try to move out of the way.
     if intersects(
         src_x,
@@ -38,37 +158,115 @@ pub unsafe fn find_naive_path(
         dest_width,
         dest_height,
     ) {
-        return cardinal_destination(y, src_x, src_z);
+        return cardinal_destination(y, src_x, src_z, direction(y, src_x, src_z));
     }
-    let dest: Vec<u32> = naive_destination(
-        y,
+    let Some((dx, dz)) = naive_destination(
         src_x,
         src_z,
         src_width as i32,
         src_height as i32,
         dest_x,
         dest_z,
-        1,
-        1,
-    );
-    let dx: i32 = CoordGrid::from(dest[0]).x() as i32;
-    let dz: i32 = CoordGrid::from(dest[0]).z() as i32;
-    if is_diagonal(
-        dx,
-        dz,
-        src_width as i32,
-        src_height as i32,
-        dest_x,
-        dest_z,
         dest_width as i32,
         dest_height as i32,
+    ) else {
+        return Ok(vec![]);
+    };
+    /* Walk towards the naive destination, diagonally first, then along whichever axis is still open. */
+    let mut currX: i32 = src_x;
+    let mut currZ: i32 = src_z;
+    while currX != dx || currZ != dz {
+        /* Once we're next to a side of the target there is nowhere closer to go. */
+        if is_adjacent(
+            currX,
+            currZ,
+            src_width as i32,
+            src_height as i32,
+            dest_x,
+            dest_z,
+            dest_width as i32,
+            dest_height as i32,
+        ) {
+            break;
+        }
+        match step_towards(
+            flags, y, currX, currZ, dx, dz, src_width, src_height, dest_x, dest_z, dest_width, dest_height, extra_flag, collision,
+        ) {
+            Some((nextX, nextZ)) => {
+                currX = nextX;
+                currZ = nextZ;
+            }
+            /* If we can't step anywhere, exit out, we've arrived. */
+            None => break,
+        }
+    }
+    return Ok(vec![CoordGrid::try_new(y, currX, currZ)?.packed]);
+}
+
+/**
+ * Takes one step from `x`/`z` towards `to_x`/`to_z`, diagonally first, then along whichever axis is still open.
+ * Returns nothing if every way is blocked.
+ */
+#[inline(always)]
+pub(crate) unsafe fn step_towards(
+    flags: &impl CollisionFlagReader,
+    y: i32,
+    x: i32,
+    z: i32,
+    to_x: i32,
+    to_z: i32,
+    src_width: u8,
+    src_height: u8,
+    dest_x: i32,
+    dest_z: i32,
+    dest_width: u8,
+    dest_height: u8,
+    extra_flag: u32,
+    collision: &CollisionStrategies,
+) -> Option<(i32, i32)> {
+    let stepX: i8 = (to_x - x).signum() as i8;
+    let stepZ: i8 = (to_z - z).signum() as i8;
+    if stepX != 0 && stepZ != 0 && can_step(
+        flags, y, x, z, stepX, stepZ, src_width, src_height, dest_x, dest_z, dest_width, dest_height, extra_flag, collision,
+    ) {
+        return Some((x + stepX as i32, z + stepZ as i32));
+    }
+    if stepX != 0 && can_step(
+        flags, y, x, z, stepX, 0, src_width, src_height, dest_x, dest_z, dest_width, dest_height, extra_flag, collision,
+    ) {
+        return Some((x + stepX as i32, z));
+    }
+    if stepZ != 0 && can_step(
+        flags, y, x, z, 0, stepZ, src_width, src_height, dest_x, dest_z, dest_width, dest_height, extra_flag, collision,
     ) {
-        return dest;
+        return Some((x, z + stepZ as i32));
     }
-    /* If we can interact from this coord(or overlap with the target), allow the pathfinder to exit. */
+    return None;
+}
+
+/**
+ * A step the mover can take without walking into a wall or onto the target.
+ */
+#[inline(always)]
+unsafe fn can_step(
+    flags: &impl CollisionFlagReader,
+    y: i32,
+    x: i32,
+    z: i32,
+    step_x: i8,
+    step_z: i8,
+    src_width: u8,
+    src_height: u8,
+    dest_x: i32,
+    dest_z: i32,
+    dest_width: u8,
+    dest_height: u8,
+    extra_flag: u32,
+    collision: &CollisionStrategies,
+) -> bool {
     if intersects(
-        dx,
-        dz,
+        x + step_x as i32,
+        z + step_z as i32,
         src_width,
         src_height,
         dest_x,
@@ -76,36 +274,11 @@ pub unsafe fn find_naive_path(
         dest_width,
         dest_height,
     ) {
-        return dest;
-    }
-    let mut currX: i32 = dx;
-    let mut currZ: i32 = dz;
-    while currX != dest_x && currZ != dest_z {
-        let dx: i8 = (dest_x - currX).signum() as i8;
-        let dz: i8 = (dest_z - currZ).signum() as i8;
-        if can_travel(
-            flags, y, currX, currZ, dx, dz, src_width, extra_flag, collision,
-        ) {
-            currX += dx as i32;
-            currZ += dz as i32;
-        } else if dx != 0
-            && can_travel(
-                flags, y, currX, currZ, dx, 0, src_width, extra_flag, collision,
-            )
-        {
-            currX += dx as i32;
-        } else if dz != 0
-            && can_travel(
-                flags, y, currX, currZ, 0, dz, src_width, extra_flag, collision,
-            )
-        {
-            currZ += dz as i32;
-        } else {
-            /* If we can't step anywhere, exit out, we've arrived. */
-            break;
-        }
+        return false;
     }
-    return vec![CoordGrid::new(y, currX, currZ).packed];
+    return can_travel_rect(
+        flags, y, x, z, step_x, step_z, src_width, src_height, extra_flag, collision,
+    );
 }
 
 /**
@@ -120,7 +293,7 @@ pub unsafe fn find_naive_path(
  * @param dest_height The end length on the Z axis.
  */
 #[inline(always)]
-fn intersects(
+pub(crate) fn intersects(
     src_x: i32,
     src_z: i32,
     src_width: u8,
@@ -140,8 +313,11 @@ fn intersects(
         || destVertical <= src_z);
 }
 
+/**
+ * Checks if the source shares a side with the target, touching only at a corner is not adjacent.
+ */
 #[inline(always)]
-fn is_diagonal(
+pub(crate) fn is_adjacent(
     src_x: i32,
     src_z: i32,
     src_width: i32,
@@ -151,22 +327,39 @@ fn is_diagonal(
     dest_width: i32,
     dest_height: i32,
 ) -> bool {
-    if src_x + src_width == dest_x && src_z + src_height == dest_z {
-        return true;
+    let overlap_x: bool = src_x < dest_x + dest_width && dest_x < src_x + src_width;
+    let overlap_z: bool = src_z < dest_z + dest_height && dest_z < src_z + src_height;
+    if overlap_x {
+        return src_z + src_height == dest_z || dest_z + dest_height == src_z;
     }
-    if src_x - 1 == dest_x + dest_width - 1 && src_z - 1 == dest_z + dest_height - 1 {
-        return true;
+    if overlap_z {
+        return src_x + src_width == dest_x || dest_x + dest_width == src_x;
     }
-    if src_x + src_width == dest_x && src_z - 1 == dest_z + dest_height - 1 {
-        return true;
-    }
-    return src_x - 1 == dest_x + dest_width - 1 && src_z + src_height == dest_z;
+    return false;
 }
 
 #[inline(always)]
-fn cardinal_destination(y: i32, src_x: i32, src_z: i32) -> Vec<u32> {
-    let direction: [i32; 2] = DIRECTIONS[rand::thread_rng().gen_range(0..DIRECTIONS.len())];
-    vec![CoordGrid::new(y, src_x + direction[0], src_z + direction[1]).packed]
+fn cardinal_destination(y: i32, src_x: i32, src_z: i32, direction: usize) -> Result<Vec<u32>, CoordOutOfRange> {
+    let direction: [i32; 2] = DIRECTIONS[direction];
+    return Ok(vec![CoordGrid::try_new(y, src_x + direction[0], src_z + direction[1])?.packed]);
+}
+
+#[cfg(feature = "rand")]
+#[inline(always)]
+fn random_direction(_: i32, _: i32, _: i32) -> usize {
+    return rand::thread_rng().gen_range(0..DIRECTIONS.len());
+}
+
+/**
+ * Without the `rand` feature the direction is hashed from the source tile,
+ * so a source stuck on the same tile always steps out the same way.
+ */
+#[cfg(not(feature = "rand"))]
+#[inline(always)]
+fn random_direction(y: i32, src_x: i32, src_z: i32) -> usize {
+    let mut hash: u32 = CoordGrid::new(y, src_x, src_z).packed.wrapping_mul(0x9e3779b9);
+    hash ^= hash >> 16;
+    return hash as usize % DIRECTIONS.len();
 }
 
 /**
@@ -191,8 +384,7 @@ fn cardinal_destination(y: i32, src_x: i32, src_z: i32) -> Vec<u32> {
  * ordinal then cardinally until entity side comes into contact with another.
  */
 #[inline(always)]
-fn naive_destination(
-    y: i32,
+pub(crate) fn naive_destination(
     src_x: i32,
     src_z: i32,
     src_width: i32,
@@ -201,7 +393,7 @@ fn naive_destination(
     dest_z: i32,
     dest_width: i32,
     dest_height: i32,
-) -> Vec<u32> {
+) -> Option<(i32, i32)> {
     let diagonal: i32 = src_x - dest_x + (src_z - dest_z);
     let anti: i32 = src_x - dest_x - (src_z - dest_z);
     let southwest_clockwise: bool = anti < 0;
@@ -217,7 +409,7 @@ fn naive_destination(
         } else if anti > -src_width {
             off_z = -(src_width + anti);
         }
-        vec![CoordGrid::new(y, -src_width + dest_x, off_z + dest_z).packed]
+        Some((-src_width + dest_x, off_z + dest_z))
     } else if northwest_clockwise && !northeast_clockwise {
         // North
         let mut off_x: i32 = 0;
@@ -226,7 +418,7 @@ fn naive_destination(
         } else if diagonal < dest_height {
             off_x = coerceAtLeast(diagonal - dest_height, -(src_width - 1));
         }
-        vec![CoordGrid::new(y, off_x + dest_x, dest_height + dest_z).packed]
+        Some((off_x + dest_x, dest_height + dest_z))
     } else if northeast_clockwise && !southeast_clockwise {
         // East
         let mut off_z: i32 = 0;
@@ -235,11 +427,11 @@ fn naive_destination(
         } else if diagonal < dest_width {
             off_z = coerceAtLeast(diagonal - dest_width, -(src_height - 1));
         }
-        vec![CoordGrid::new(y, dest_width + dest_x, off_z + dest_z).packed]
+        Some((dest_width + dest_x, off_z + dest_z))
     } else {
         if !(southeast_clockwise && !southwest_clockwise) {
             // South
-            return vec![]; // throw new Error(`Failed requirement. southEastClockwise was: ${southEastClockwise}, southWestClockwise was: ${southWestClockwise}.`);
+            return None; // throw new Error(`Failed requirement. southEastClockwise was: ${southEastClockwise}, southWestClockwise was: ${southWestClockwise}.`);
         }
         let mut off_x: i32 = 0;
         if diagonal > -src_height {
@@ -247,7 +439,7 @@ fn naive_destination(
         } else if anti < src_height {
             off_x = coerceAtLeast(anti - src_height, -(src_height - 1));
         }
-        vec![CoordGrid::new(y, off_x + dest_x, -src_height + dest_z).packed]
+        Some((off_x + dest_x, -src_height + dest_z))
     };
 }
 
@@ -1,9 +1,11 @@
 use std::collections::VecDeque;
 
-use crate::rsmod::{CollisionStrategies, CollisionStrategy, CoordGrid};
-use crate::rsmod::collision::collision::CollisionFlagMap;
+use crate::rsmod::{CollisionStrategies, CollisionStrategy, CoordGrid, CoordOutOfRange, PackedCoord};
+use crate::rsmod::collision::collision::CollisionFlagReader;
 use crate::rsmod::collision_flag::CollisionFlag;
 use crate::rsmod::direction_flag::DirectionFlag;
+use crate::rsmod::line_validator::has_line_of_sight;
+use crate::rsmod::naive_pathfinder::PathRandom;
 use crate::rsmod::reach_strategy::ReachStrategy;
 use crate::rsmod::rotation::rotate;
 
@@ -55,10 +57,14 @@ impl PathFinder {
         };
     }
 
+    /**
+     * Returns the packed waypoints of the path, or [CoordOutOfRange] if one of them can't be packed
+     * into a [CoordGrid], e.g. far out in a sparse map. See [PathFinder::find_path_wide] for those.
+     */
     #[inline(always)]
     pub unsafe fn find_path(
         &mut self,
-        flags: &CollisionFlagMap,
+        flags: &impl CollisionFlagReader,
         y: i32,
         src_x: i32,
         src_z: i32,
@@ -73,7 +79,87 @@ impl PathFinder {
         block_access_flags: u8,
         max_waypoints: u8,
         collision: &CollisionStrategies,
-    ) -> Vec<u32> {
+    ) -> Result<Vec<u32>, CoordOutOfRange> {
+        return self.find_path_as(
+            flags,
+            y,
+            src_x,
+            src_z,
+            dest_x,
+            dest_z,
+            src_size,
+            dest_width,
+            dest_height,
+            angle,
+            shape,
+            move_near,
+            block_access_flags,
+            max_waypoints,
+            collision,
+        );
+    }
+
+    /**
+     * Like [PathFinder::find_path], but packs the waypoints into [crate::rsmod::WideCoordGrid]s so
+     * paths past 14 bit coordinates in a sparse map can be returned.
+     */
+    #[inline(always)]
+    pub unsafe fn find_path_wide(
+        &mut self,
+        flags: &impl CollisionFlagReader,
+        y: i32,
+        src_x: i32,
+        src_z: i32,
+        dest_x: i32,
+        dest_z: i32,
+        src_size: u8,
+        dest_width: u8,
+        dest_height: u8,
+        angle: u8,
+        shape: i8,
+        move_near: bool,
+        block_access_flags: u8,
+        max_waypoints: u8,
+        collision: &CollisionStrategies,
+    ) -> Result<Vec<u64>, CoordOutOfRange> {
+        return self.find_path_as(
+            flags,
+            y,
+            src_x,
+            src_z,
+            dest_x,
+            dest_z,
+            src_size,
+            dest_width,
+            dest_height,
+            angle,
+            shape,
+            move_near,
+            block_access_flags,
+            max_waypoints,
+            collision,
+        );
+    }
+
+    #[inline(always)]
+    unsafe fn find_path_as<T: PackedCoord>(
+        &mut self,
+        flags: &impl CollisionFlagReader,
+        y: i32,
+        src_x: i32,
+        src_z: i32,
+        dest_x: i32,
+        dest_z: i32,
+        src_size: u8,
+        dest_width: u8,
+        dest_height: u8,
+        angle: u8,
+        shape: i8,
+        move_near: bool,
+        block_access_flags: u8,
+        max_waypoints: u8,
+        collision: &CollisionStrategies,
+    ) -> Result<Vec<T>, CoordOutOfRange> {
         self.reset();
         let base_x: i32 = src_x - self.search_half_map_size;
         let base_z: i32 = src_z - self.search_half_map_size;
@@ -87,7 +173,227 @@ impl PathFinder {
             PathFinder::DEFAULT_SRC_DIRECTION_VALUE,
             0,
         );
-        let path_found: bool = match src_size {
+        let path_found: bool = self.search(
+            flags,
+            base_x,
+            base_z,
+            y,
+            local_dest_x,
+            local_dest_z,
+            dest_width,
+            dest_height,
+            src_size,
+            angle,
+            shape,
+            block_access_flags,
+            collision,
+        );
+        if !path_found {
+            if !move_near {
+                return Ok(vec![]);
+            }
+            let found_approach_point: bool = self.find_closest_approach_point(
+                local_dest_x,
+                local_dest_z,
+                rotate(angle, dest_width, dest_height),
+                rotate(angle, dest_height, dest_width),
+            );
+            if !found_approach_point {
+                return Ok(vec![]);
+            }
+        }
+
+        return self.trace_waypoints(y, base_x, base_z, local_src_x, local_srx_z, max_waypoints);
+    }
+
+    /**
+     * Finds a path for a `src_size` mover away from every threat, looking at the tiles it can walk to in at
+     * most `max_steps` steps and picking the one furthest (chebyshev) from the closest threat, the shortest
+     * walk breaking ties. With `require_hidden` only tiles none of the threats have line of sight to count.
+     * Returns nothing if no tile is better than staying put, and [CoordOutOfRange] like [PathFinder::find_path].
+     */
+    #[inline(always)]
+    pub unsafe fn find_flee_path(
+        &mut self,
+        flags: &impl CollisionFlagReader,
+        y: i32,
+        src_x: i32,
+        src_z: i32,
+        src_size: u8,
+        threats: &[u32],
+        max_steps: i32,
+        require_hidden: bool,
+        max_waypoints: u8,
+        collision: &CollisionStrategies,
+    ) -> Result<Vec<u32>, CoordOutOfRange> {
+        let base_x: i32 = src_x - self.search_half_map_size;
+        let base_z: i32 = src_z - self.search_half_map_size;
+        let local_src_x: i32 = src_x - base_x;
+        let local_src_z: i32 = src_z - base_z;
+        self.flood(flags, y, base_x, base_z, local_src_x, local_src_z, src_size, collision);
+
+        let threats: Vec<(i32, i32)> = threats
+            .iter()
+            .map(|&threat| {
+                let threat: CoordGrid = CoordGrid::from(threat);
+                (threat.x() as i32, threat.z() as i32)
+            })
+            .collect();
+        let size: i32 = src_size as i32;
+        let mut best: Option<(i32, i32, i32, i32)> = None;
+        let min_x: i32 = (local_src_x - max_steps).max(0);
+        let max_x: i32 = (local_src_x + max_steps).min(self.search_map_size - 1);
+        let min_z: i32 = (local_src_z - max_steps).max(0);
+        let max_z: i32 = (local_src_z + max_steps).min(self.search_map_size - 1);
+        for local_x in min_x..=max_x {
+            for local_z in min_z..=max_z {
+                let distance: i32 = *self.distances.as_ptr().add(self.local_index(local_x, local_z));
+                if distance > max_steps {
+                    continue;
+                }
+                let x: i32 = base_x + local_x;
+                let z: i32 = base_z + local_z;
+                let safety: i32 = threats
+                    .iter()
+                    .map(|&(threat_x, threat_z)| {
+                        let delta_x: i32 = (threat_x - (x + size - 1)).max(x - threat_x).max(0);
+                        let delta_z: i32 = (threat_z - (z + size - 1)).max(z - threat_z).max(0);
+                        delta_x.max(delta_z)
+                    })
+                    .min()
+                    .unwrap_or(i32::MAX);
+                let better: bool = match best {
+                    None => true,
+                    Some((best_safety, best_distance, _, _)) => {
+                        safety > best_safety || (safety == best_safety && distance < best_distance)
+                    }
+                };
+                if !better {
+                    continue;
+                }
+                if require_hidden
+                    && threats.iter().any(|&(threat_x, threat_z)| {
+                        has_line_of_sight(flags, y, threat_x, threat_z, x, z, 1, 1, src_size, src_size, 0)
+                    })
+                {
+                    continue;
+                }
+                best = Some((safety, distance, local_x, local_z));
+            }
+        }
+        let Some((_, distance, local_x, local_z)) = best else {
+            return Ok(PathFinder::EMPTY);
+        };
+        if distance == 0 {
+            return Ok(PathFinder::EMPTY);
+        }
+        self.curr_local_x = local_x;
+        self.curr_local_z = local_z;
+        return self.trace_waypoints(y, base_x, base_z, local_src_x, local_src_z, max_waypoints);
+    }
+
+    /**
+     * Picks where a `src_size` mover wandering around its spawn walks to next, out of every tile within
+     * `radius` of the spawn that it can walk to from where it stands in at most `max_steps` steps.
+     * Every such tile is equally likely, `random` picking one, so a [crate::rsmod::SeededRandom]
+     * always picks the same. Returns nothing if the mover can't get anywhere, and [CoordOutOfRange]
+     * if the picked tile can't be packed into a [CoordGrid].
+     */
+    #[inline(always)]
+    pub unsafe fn find_wander_destination(
+        &mut self,
+        flags: &impl CollisionFlagReader,
+        y: i32,
+        src_x: i32,
+        src_z: i32,
+        spawn_x: i32,
+        spawn_z: i32,
+        src_size: u8,
+        radius: i32,
+        max_steps: i32,
+        collision: &CollisionStrategies,
+        random: &mut dyn PathRandom,
+    ) -> Result<Option<u32>, CoordOutOfRange> {
+        let base_x: i32 = src_x - self.search_half_map_size;
+        let base_z: i32 = src_z - self.search_half_map_size;
+        let local_src_x: i32 = src_x - base_x;
+        let local_src_z: i32 = src_z - base_z;
+        self.flood(flags, y, base_x, base_z, local_src_x, local_src_z, src_size, collision);
+
+        let min_x: i32 = (spawn_x - radius - base_x).max(local_src_x - max_steps).max(0);
+        let max_x: i32 = (spawn_x + radius - base_x).min(local_src_x + max_steps).min(self.search_map_size - 1);
+        let min_z: i32 = (spawn_z - radius - base_z).max(local_src_z - max_steps).max(0);
+        let max_z: i32 = (spawn_z + radius - base_z).min(local_src_z + max_steps).min(self.search_map_size - 1);
+        let candidate = |distance: i32| -> bool {
+            return distance > 0 && distance <= max_steps;
+        };
+
+        let mut count: u32 = 0;
+        for local_x in min_x..=max_x {
+            for local_z in min_z..=max_z {
+                if candidate(*self.distances.as_ptr().add(self.local_index(local_x, local_z))) {
+                    count += 1;
+                }
+            }
+        }
+        if count == 0 {
+            return Ok(None);
+        }
+        let mut pick: u32 = random.next_int(count);
+        for local_x in min_x..=max_x {
+            for local_z in min_z..=max_z {
+                if !candidate(*self.distances.as_ptr().add(self.local_index(local_x, local_z))) {
+                    continue;
+                }
+                if pick == 0 {
+                    return Ok(Some(CoordGrid::try_new(y, base_x + local_x, base_z + local_z)?.packed));
+                }
+                pick -= 1;
+            }
+        }
+        return Ok(None);
+    }
+
+    /**
+     * Searches from the source the same way [PathFinder::find_path] does with nothing to reach, leaving the
+     * walking distance to every tile of the search map the source can get to.
+     */
+    #[inline(always)]
+    unsafe fn flood(
+        &mut self,
+        flags: &impl CollisionFlagReader,
+        y: i32,
+        base_x: i32,
+        base_z: i32,
+        local_src_x: i32,
+        local_src_z: i32,
+        src_size: u8,
+        collision: &CollisionStrategies,
+    ) {
+        self.reset();
+        self.append_direction(local_src_x, local_src_z, PathFinder::DEFAULT_SRC_DIRECTION_VALUE, 0);
+        // a shape of -1 is only reached on the tile itself, which is outside the search map.
+        self.search(flags, base_x, base_z, y, -1, -1, 1, 1, src_size, 0, -1, 0, collision);
+    }
+
+    #[inline(always)]
+    unsafe fn search(
+        &mut self,
+        flags: &impl CollisionFlagReader,
+        base_x: i32,
+        base_z: i32,
+        y: i32,
+        local_dest_x: i32,
+        local_dest_z: i32,
+        dest_width: u8,
+        dest_height: u8,
+        src_size: u8,
+        angle: u8,
+        shape: i8,
+        block_access_flags: u8,
+        collision: &CollisionStrategies,
+    ) -> bool {
+        return match src_size {
             1 => self.find_path_1(
                 flags,
                 base_x,
@@ -134,23 +440,23 @@ impl PathFinder {
                 collision,
             ),
         };
-        if !path_found {
-            if !move_near {
-                return PathFinder::EMPTY;
-            }
-            let found_approach_point: bool = self.find_closest_approach_point(
-                local_dest_x,
-                local_dest_z,
-                rotate(angle, dest_width, dest_height),
-                rotate(angle, dest_height, dest_width),
-            );
-            if !found_approach_point {
-                return PathFinder::EMPTY;
-            }
-        }
+    }
 
+    /**
+     * Walks the directions back from the current tile to the source, keeping the last `max_waypoints` turns.
+     */
+    #[inline(always)]
+    unsafe fn trace_waypoints<T: PackedCoord>(
+        &mut self,
+        y: i32,
+        base_x: i32,
+        base_z: i32,
+        local_src_x: i32,
+        local_srx_z: i32,
+        max_waypoints: u8,
+    ) -> Result<Vec<T>, CoordOutOfRange> {
         let limit: usize = max_waypoints as usize;
-        let mut waypoints: VecDeque<u32> = VecDeque::with_capacity(limit);
+        let mut waypoints: VecDeque<T> = VecDeque::with_capacity(limit);
 
         let mut next: i8 = *self
             .directions
@@ -167,10 +473,7 @@ impl PathFinder {
                 if waypoints.len() >= limit {
                     waypoints.pop_back();
                 }
-                waypoints.push_front(
-                    CoordGrid::new(y, base_x + self.curr_local_x, base_z + self.curr_local_z)
-                        .packed,
-                );
+                waypoints.push_front(T::pack(y, base_x + self.curr_local_x, base_z + self.curr_local_z)?);
             }
 
             if curr & DirectionFlag::East != 0 {
@@ -190,13 +493,13 @@ impl PathFinder {
                 .as_ptr()
                 .add(self.local_index(self.curr_local_x, self.curr_local_z));
         }
-        return Vec::from(waypoints);
+        return Ok(Vec::from(waypoints));
     }
 
     #[inline(always)]
     unsafe fn find_path_1(
         &mut self,
-        flags: &CollisionFlagMap,
+        flags: &impl CollisionFlagReader,
         base_x: i32,
         base_z: i32,
         y: i32,
@@ -404,7 +707,7 @@ impl PathFinder {
     #[inline(always)]
     unsafe fn find_path_2(
         &mut self,
-        flags: &CollisionFlagMap,
+        flags: &impl CollisionFlagReader,
         base_x: i32,
         base_z: i32,
         y: i32,
@@ -658,7 +961,7 @@ impl PathFinder {
     #[inline(always)]
     unsafe fn find_path_n(
         &mut self,
-        flags: &CollisionFlagMap,
+        flags: &impl CollisionFlagReader,
         base_x: i32,
         base_z: i32,
         y: i32,
@@ -1170,7 +1473,7 @@ impl PathFinder {
 
     #[inline(always)]
     unsafe fn collision_flag(
-        flags: &CollisionFlagMap,
+        flags: &impl CollisionFlagReader,
         base_x: i32,
         base_z: i32,
         local_x: i32,
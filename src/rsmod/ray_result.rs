@@ -0,0 +1,108 @@
+#![allow(non_camel_case_types)]
+
+use crate::rsmod::collision_flag::CollisionFlag;
+
+/**
+ * What stopped a ray, worked out from the flags of the tile that blocked it.
+ * Walls are named after the side of the blocking tile they are on.
+ */
+#[repr(u8)]
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum RayBlocker {
+    WALL_WEST = 0,
+    WALL_EAST = 1,
+    WALL_SOUTH = 2,
+    WALL_NORTH = 3,
+    LOC = 4,
+    LOC_PROJ_BLOCKER = 5,
+    FLOOR = 6,
+    EXTRA_FLAG = 7,
+}
+
+impl RayBlocker {
+    /**
+     * Classifies the flags that blocked a ray. Anything that is not one of the
+     * flags the ray checks on its own came from the extra flag.
+     */
+    #[inline(always)]
+    pub fn of(hit: u32) -> RayBlocker {
+        if hit & (CollisionFlag::WALL_WEST as u32 | CollisionFlag::WALL_WEST_PROJ_BLOCKER as u32) != 0 {
+            return RayBlocker::WALL_WEST;
+        } else if hit & (CollisionFlag::WALL_EAST as u32 | CollisionFlag::WALL_EAST_PROJ_BLOCKER as u32) != 0 {
+            return RayBlocker::WALL_EAST;
+        } else if hit & (CollisionFlag::WALL_SOUTH as u32 | CollisionFlag::WALL_SOUTH_PROJ_BLOCKER as u32) != 0 {
+            return RayBlocker::WALL_SOUTH;
+        } else if hit & (CollisionFlag::WALL_NORTH as u32 | CollisionFlag::WALL_NORTH_PROJ_BLOCKER as u32) != 0 {
+            return RayBlocker::WALL_NORTH;
+        } else if hit & CollisionFlag::LOC_PROJ_BLOCKER as u32 != 0 {
+            return RayBlocker::LOC_PROJ_BLOCKER;
+        } else if hit & CollisionFlag::LOC as u32 != 0 {
+            return RayBlocker::LOC;
+        } else if hit & CollisionFlag::FLOOR_BLOCKED as u32 != 0 {
+            return RayBlocker::FLOOR;
+        }
+        return RayBlocker::EXTRA_FLAG;
+    }
+}
+
+/**
+ * The tile that blocked a ray. `flags` holds the flags of the tile that the ray checked for.
+ */
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct RayBlock {
+    pub x: i32,
+    pub z: i32,
+    pub y: i32,
+    pub flags: u32,
+    pub blocker: RayBlocker,
+}
+
+/**
+ * The tiles a ray entered, packed like [crate::rsmod::CoordGrid] (or [crate::rsmod::WideCoordGrid]
+ * for a u64 `T`), up to where it was blocked. `blocked` is None if the ray reached its destination.
+ */
+#[derive(Clone, PartialEq, Debug)]
+pub struct RayResult<T = u32> {
+    pub tiles: Vec<T>,
+    pub blocked: Option<RayBlock>,
+}
+
+impl<T> RayResult<T> {
+    #[inline(always)]
+    pub fn is_clear(&self) -> bool {
+        return self.blocked.is_none();
+    }
+
+    /**
+     * Returns the tiles if the ray was clear, otherwise an empty line.
+     */
+    #[inline(always)]
+    pub fn into_line(self) -> Vec<T> {
+        if self.blocked.is_some() {
+            return vec![];
+        }
+        return self.tiles;
+    }
+}
+
+impl RayResult {
+    /**
+     * How many values come before the tiles in [RayResult::flatten].
+     */
+    pub const HEADER: usize = 5;
+
+    /**
+     * Flattens the result for the bindings as `[blocker, x, z, y, flags, tiles...]`, the blocker
+     * being the [RayBlocker] as a number, or -1 with the rest of the header 0 if the ray was clear.
+     */
+    #[inline(always)]
+    pub fn flatten(&self) -> Vec<i32> {
+        let mut flat: Vec<i32> = Vec::with_capacity(RayResult::HEADER + self.tiles.len());
+        match self.blocked {
+            Some(block) => flat.extend([block.blocker as i32, block.x, block.z, block.y, block.flags as i32]),
+            None => flat.extend([-1, 0, 0, 0, 0]),
+        }
+        flat.extend(self.tiles.iter().map(|&tile| tile as i32));
+        return flat;
+    }
+}
@@ -1,5 +1,5 @@
 use crate::rsmod::{LocAngle, LocShape};
-use crate::rsmod::collision::collision::CollisionFlagMap;
+use crate::rsmod::collision::collision::CollisionFlagReader;
 use crate::rsmod::collision_flag::CollisionFlag;
 use crate::rsmod::reach::rectangle_boundary::{collides, reach_rectangle_1, reach_rectangle_n};
 use crate::rsmod::rotation::{rotate, rotate_flags};
@@ -29,6 +29,15 @@ impl ReachStrategy {
         return ReachStrategy::NO_STRATEGY;
     }
 
+    /**
+     * Whether `angle` and `shape` describe a target the path and reach functions can handle without
+     * aborting: an angle in 0..=3 and a [LocShape], -1 for no loc or -2 for an exclusive rectangle.
+     */
+    #[inline(always)]
+    pub fn is_valid_target(angle: u8, shape: i8) -> bool {
+        return angle <= 3 && (shape == -1 || shape == -2 || LocShape::try_from_i8(shape).is_some());
+    }
+
     #[inline(always)]
     pub fn altered_rotation(angle: u8, shape: i8) -> u8 {
         return if shape == 7 { (angle + 2) & 0x3 } else { angle };
@@ -36,7 +45,7 @@ impl ReachStrategy {
 
     #[inline(always)]
     pub unsafe fn reached(
-        flags: &CollisionFlagMap,
+        flags: &impl CollisionFlagReader,
         y: i32,
         src_x: i32,
         src_z: i32,
@@ -99,7 +108,7 @@ impl ReachStrategy {
 
     #[inline(always)]
     pub unsafe fn reach_rectangle(
-        flags: &CollisionFlagMap,
+        flags: &impl CollisionFlagReader,
         y: i32,
         src_x: i32,
         src_z: i32,
@@ -162,7 +171,7 @@ impl ReachStrategy {
 
     #[inline(always)]
     pub unsafe fn reach_exclusive_rectangle(
-        flags: &CollisionFlagMap,
+        flags: &impl CollisionFlagReader,
         y: i32,
         src_x: i32,
         src_z: i32,
@@ -225,7 +234,7 @@ impl ReachStrategy {
 
     #[inline(always)]
     unsafe fn reach_wall(
-        flags: &CollisionFlagMap,
+        flags: &impl CollisionFlagReader,
         y: i32,
         src_x: i32,
         src_z: i32,
@@ -256,7 +265,7 @@ impl ReachStrategy {
 
     #[inline(always)]
     unsafe fn reach_wall_decor(
-        flags: &CollisionFlagMap,
+        flags: &impl CollisionFlagReader,
         y: i32,
         src_x: i32,
         src_z: i32,
@@ -287,7 +296,7 @@ impl ReachStrategy {
 
     #[inline(always)]
     unsafe fn reach_wall_1(
-        flags: &CollisionFlagMap,
+        flags: &impl CollisionFlagReader,
         y: i32,
         src_x: i32,
         src_z: i32,
@@ -484,7 +493,7 @@ impl ReachStrategy {
 
     #[inline(always)]
     unsafe fn reach_wall_n(
-        flags: &CollisionFlagMap,
+        flags: &impl CollisionFlagReader,
         y: i32,
         src_x: i32,
         src_z: i32,
@@ -708,7 +717,7 @@ impl ReachStrategy {
 
     #[inline(always)]
     unsafe fn reach_wall_decor_1(
-        flags: &CollisionFlagMap,
+        flags: &impl CollisionFlagReader,
         y: i32,
         src_x: i32,
         src_z: i32,
@@ -816,7 +825,7 @@ impl ReachStrategy {
 
     #[inline(always)]
     unsafe fn reach_wall_decor_n(
-        flags: &CollisionFlagMap,
+        flags: &impl CollisionFlagReader,
         y: i32,
         src_x: i32,
         src_z: i32,
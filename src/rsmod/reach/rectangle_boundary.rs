@@ -1,5 +1,5 @@
 use crate::rsmod::block_flag::BlockAccessFlag;
-use crate::rsmod::collision::collision::CollisionFlagMap;
+use crate::rsmod::collision::collision::CollisionFlagReader;
 use crate::rsmod::collision_flag::CollisionFlag;
 
 #[inline(always)]
@@ -22,7 +22,7 @@ pub fn collides(
 
 #[inline(always)]
 pub unsafe fn reach_rectangle_1(
-    flags: &CollisionFlagMap,
+    flags: &impl CollisionFlagReader,
     y: i32,
     src_x: i32,
     src_z: i32,
@@ -75,7 +75,7 @@ pub unsafe fn reach_rectangle_1(
 
 #[inline(always)]
 pub unsafe fn reach_rectangle_n(
-    flags: &CollisionFlagMap,
+    flags: &impl CollisionFlagReader,
     y: i32,
     src_x: i32,
     src_z: i32,
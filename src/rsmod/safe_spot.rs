@@ -0,0 +1,126 @@
+use crate::rsmod::collision::collision::CollisionFlagReader;
+use crate::rsmod::collision_flag::CollisionFlag;
+use crate::rsmod::collision_strategy::CollisionStrategies;
+use crate::rsmod::coord_grid::{CoordGrid, CoordOutOfRange};
+use crate::rsmod::line_validator::{has_line_of_sight, has_line_of_walk};
+use crate::rsmod::naive_movement::{simulate_chase, ChaseSimulation, ChaseState, ChaseTick, MoveSpeed};
+use crate::rsmod::naive_pathfinder::{is_adjacent, SeededRandom};
+
+/**
+ * How the player attacks the npc, which decides what has to be clear between them.
+ */
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum AttackType {
+    /** Needs a line of walk, and at a range of 1 a side of the npc rather than a corner. */
+    MELEE,
+    /** Needs a line of sight. */
+    RANGED,
+}
+
+/**
+ * A tile the player can attack the npc from while the npc's naive movement never gets to hit back.
+ */
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct SafeSpot {
+    pub x: i32,
+    pub z: i32,
+    /** The south-west tile the npc ends up on chasing the player. */
+    pub npc_x: i32,
+    pub npc_z: i32,
+}
+
+/**
+ * Returns every tile within `radius` of a `npc_size` x `npc_size` npc that the player can stand on and
+ * attack the npc from with `attack_type` at up to `attack_range`, while the npc chasing from where it
+ * stands with [simulate_chase] never gets next to the player, or only gets next to it across a wall.
+ * `extra_flag` and `collision` are the npc's. Returns [CoordOutOfRange] if a tile the player could stand on
+ * can't be packed into a [CoordGrid] for the chase.
+ */
+#[inline(always)]
+pub unsafe fn find_safe_spots(
+    flags: &impl CollisionFlagReader,
+    y: i32,
+    npc_x: i32,
+    npc_z: i32,
+    npc_size: u8,
+    radius: i32,
+    attack_range: i32,
+    attack_type: AttackType,
+    extra_flag: u32,
+    collision: &CollisionStrategies,
+) -> Result<Vec<SafeSpot>, CoordOutOfRange> {
+    let size: i32 = npc_size as i32;
+    // a naive chase towards a standing target ends well within this, stuck or next to it.
+    let ticks: usize = ((radius.max(0) + size) * 4) as usize;
+    let mut spots: Vec<SafeSpot> = vec![];
+    for z in npc_z - radius..npc_z + size + radius {
+        for x in npc_x - radius..npc_x + size + radius {
+            if flags.get(x, z, y) & CollisionFlag::WALK_BLOCKED as u32 != CollisionFlag::OPEN as u32 {
+                continue;
+            }
+            if !can_attack(flags, y, x, z, npc_x, npc_z, npc_size, attack_range, attack_type) {
+                continue;
+            }
+            let chase: ChaseSimulation = simulate_chase(
+                flags,
+                y,
+                npc_x,
+                npc_z,
+                npc_size,
+                npc_size,
+                &[CoordGrid::try_new(y, x, z)?.packed],
+                1,
+                1,
+                ticks,
+                MoveSpeed::WALK,
+                extra_flag,
+                collision,
+                // the npc starts away from the player so it never has to step out from under.
+                &mut SeededRandom::new(0),
+            );
+            let end: ChaseTick = chase.ticks.last().copied().unwrap_or(ChaseTick {
+                x: npc_x,
+                z: npc_z,
+                state: ChaseState::STUCK,
+            });
+            /* Next to the player with nothing like a fence in between, the npc can hit back. */
+            if end.state == ChaseState::ARRIVED
+                && has_line_of_walk(flags, y, end.x, end.z, x, z, npc_size, npc_size, 1, 1, 0)
+            {
+                continue;
+            }
+            spots.push(SafeSpot { x, z, npc_x: end.x, npc_z: end.z });
+        }
+    }
+    return Ok(spots);
+}
+
+#[inline(always)]
+unsafe fn can_attack(
+    flags: &impl CollisionFlagReader,
+    y: i32,
+    x: i32,
+    z: i32,
+    npc_x: i32,
+    npc_z: i32,
+    npc_size: u8,
+    attack_range: i32,
+    attack_type: AttackType,
+) -> bool {
+    let size: i32 = npc_size as i32;
+    let delta_x: i32 = (npc_x - x).max(x - (npc_x + size - 1)).max(0);
+    let delta_z: i32 = (npc_z - z).max(z - (npc_z + size - 1)).max(0);
+    let distance: i32 = delta_x.max(delta_z);
+    if distance < 1 || distance > attack_range {
+        return false;
+    }
+    return match attack_type {
+        AttackType::MELEE => {
+            if attack_range == 1 && !is_adjacent(x, z, 1, 1, npc_x, npc_z, size, size) {
+                return false;
+            }
+            has_line_of_walk(flags, y, x, z, npc_x, npc_z, 1, 1, npc_size, npc_size, 0)
+        }
+        AttackType::RANGED => has_line_of_sight(flags, y, x, z, npc_x, npc_z, 1, 1, npc_size, npc_size, 0),
+    };
+}
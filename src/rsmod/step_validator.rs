@@ -1,11 +1,11 @@
 use crate::rsmod::{CollisionStrategies, CollisionStrategy};
-use crate::rsmod::collision::collision::CollisionFlagMap;
+use crate::rsmod::collision::collision::CollisionFlagReader;
 use crate::rsmod::collision_flag::CollisionFlag;
 
 #[rustfmt::skip]
 #[inline(always)]
 pub unsafe fn can_travel(
-    flags: &CollisionFlagMap,
+    flags: &impl CollisionFlagReader,
     y: i32,
     x: i32,
     z: i32,
@@ -28,9 +28,106 @@ pub unsafe fn can_travel(
     };
 }
 
+/**
+ * [can_travel] for a `width` x `height` mover, the same as [can_travel] when both sides are equal.
+ * Every tile the mover steps onto is checked like the edges and corners of a square mover,
+ * and a side only one tile long can't cut the corner of a diagonal step like a 1x1 mover can't.
+ */
+#[inline(always)]
+pub unsafe fn can_travel_rect(
+    flags: &impl CollisionFlagReader,
+    y: i32,
+    x: i32,
+    z: i32,
+    offset_x: i8,
+    offset_z: i8,
+    width: u8,
+    height: u8,
+    extra_flag: u32,
+    collision: &CollisionStrategies,
+) -> bool {
+    if width == height {
+        return can_travel(flags, y, x, z, offset_x, offset_z, width, extra_flag, collision);
+    }
+    if offset_x.abs() > 1 || offset_z.abs() > 1 || (offset_x == 0 && offset_z == 0) {
+        return false;
+    }
+    let width: i32 = width as i32;
+    let height: i32 = height as i32;
+    // the column and row the mover steps onto.
+    let column_x: i32 = if offset_x > 0 { x + width } else { x - 1 };
+    let row_z: i32 = if offset_z > 0 { z + height } else { z - 1 };
+    let blocked = |tile_x: i32, tile_z: i32, flag: CollisionFlag| -> bool {
+        return !collision.can_move(flags.get(tile_x, tile_z, y), flag as u32 | extra_flag);
+    };
+
+    if offset_z == 0 {
+        let (single, south, north, mid) = if offset_x > 0 {
+            (CollisionFlag::BLOCK_EAST, CollisionFlag::BLOCK_SOUTH_EAST, CollisionFlag::BLOCK_NORTH_EAST, CollisionFlag::BLOCK_NORTH_AND_SOUTH_WEST)
+        } else {
+            (CollisionFlag::BLOCK_WEST, CollisionFlag::BLOCK_SOUTH_WEST, CollisionFlag::BLOCK_NORTH_WEST, CollisionFlag::BLOCK_NORTH_AND_SOUTH_EAST)
+        };
+        if height == 1 {
+            return !blocked(column_x, z, single);
+        }
+        if blocked(column_x, z, south) || blocked(column_x, z + height - 1, north) {
+            return false;
+        }
+        return !(z + 1..z + height - 1).any(|mid_z| blocked(column_x, mid_z, mid));
+    }
+
+    if offset_x == 0 {
+        let (single, west, east, mid) = if offset_z > 0 {
+            (CollisionFlag::BLOCK_NORTH, CollisionFlag::BLOCK_NORTH_WEST, CollisionFlag::BLOCK_NORTH_EAST, CollisionFlag::BLOCK_SOUTH_EAST_AND_WEST)
+        } else {
+            (CollisionFlag::BLOCK_SOUTH, CollisionFlag::BLOCK_SOUTH_WEST, CollisionFlag::BLOCK_SOUTH_EAST, CollisionFlag::BLOCK_NORTH_EAST_AND_WEST)
+        };
+        if width == 1 {
+            return !blocked(x, row_z, single);
+        }
+        if blocked(x, row_z, west) || blocked(x + width - 1, row_z, east) {
+            return false;
+        }
+        return !(x + 1..x + width - 1).any(|mid_x| blocked(mid_x, row_z, mid));
+    }
+
+    let corner: CollisionFlag = match (offset_x > 0, offset_z > 0) {
+        (false, false) => CollisionFlag::BLOCK_SOUTH_WEST,
+        (false, true) => CollisionFlag::BLOCK_NORTH_WEST,
+        (true, false) => CollisionFlag::BLOCK_SOUTH_EAST,
+        (true, true) => CollisionFlag::BLOCK_NORTH_EAST,
+    };
+    if blocked(column_x, row_z, corner) {
+        return false;
+    }
+    let (column, single_column) = if offset_x > 0 {
+        (CollisionFlag::BLOCK_NORTH_AND_SOUTH_WEST, CollisionFlag::BLOCK_EAST)
+    } else {
+        (CollisionFlag::BLOCK_NORTH_AND_SOUTH_EAST, CollisionFlag::BLOCK_WEST)
+    };
+    let (row, single_row) = if offset_z > 0 {
+        (CollisionFlag::BLOCK_SOUTH_EAST_AND_WEST, CollisionFlag::BLOCK_NORTH)
+    } else {
+        (CollisionFlag::BLOCK_NORTH_EAST_AND_WEST, CollisionFlag::BLOCK_SOUTH)
+    };
+    // the rest of the column and row, next to the corner.
+    let column_start: i32 = if offset_z > 0 { z + 1 } else { z };
+    if (column_start..column_start + height - 1).any(|tile_z| blocked(column_x, tile_z, column)) {
+        return false;
+    }
+    let row_start: i32 = if offset_x > 0 { x + 1 } else { x };
+    if (row_start..row_start + width - 1).any(|tile_x| blocked(tile_x, row_z, row)) {
+        return false;
+    }
+    if height == 1 && blocked(column_x, z, single_column) {
+        return false;
+    }
+    return !(width == 1 && blocked(x, row_z, single_row));
+}
+
 #[inline(always)]
 unsafe fn is_blocked_south(
-    flags: &CollisionFlagMap,
+    flags: &impl CollisionFlagReader,
     y: i32,
     x: i32,
     z: i32,
@@ -79,7 +176,7 @@ unsafe fn is_blocked_south(
 
 #[inline(always)]
 unsafe fn is_blocked_north(
-    flags: &CollisionFlagMap,
+    flags: &impl CollisionFlagReader,
     y: i32,
     x: i32,
     z: i32,
@@ -128,7 +225,7 @@ unsafe fn is_blocked_north(
 
 #[inline(always)]
 unsafe fn is_blocked_west(
-    flags: &CollisionFlagMap,
+    flags: &impl CollisionFlagReader,
     y: i32,
     x: i32,
     z: i32,
@@ -177,7 +274,7 @@ unsafe fn is_blocked_west(
 
 #[inline(always)]
 unsafe fn is_blocked_east(
-    flags: &CollisionFlagMap,
+    flags: &impl CollisionFlagReader,
     y: i32,
     x: i32,
     z: i32,
@@ -226,7 +323,7 @@ unsafe fn is_blocked_east(
 
 #[inline(always)]
 unsafe fn is_blocked_southwest(
-    flags: &CollisionFlagMap,
+    flags: &impl CollisionFlagReader,
     y: i32,
     x: i32,
     z: i32,
@@ -286,7 +383,7 @@ unsafe fn is_blocked_southwest(
 
 #[inline(always)]
 unsafe fn is_blocked_northwest(
-    flags: &CollisionFlagMap,
+    flags: &impl CollisionFlagReader,
     y: i32,
     x: i32,
     z: i32,
@@ -346,7 +443,7 @@ unsafe fn is_blocked_northwest(
 
 #[inline(always)]
 unsafe fn is_blocked_southeast(
-    flags: &CollisionFlagMap,
+    flags: &impl CollisionFlagReader,
     y: i32,
     x: i32,
     z: i32,
@@ -406,7 +503,7 @@ unsafe fn is_blocked_southeast(
 
 #[inline(always)]
 unsafe fn is_blocked_northeast(
-    flags: &CollisionFlagMap,
+    flags: &impl CollisionFlagReader,
     y: i32,
     x: i32,
     z: i32,
@@ -11,3 +11,25 @@ pub fn rotate_flags(angle: u8, block_access_flags: u8) -> u8 {
         ((block_access_flags << angle) & 0xf) | (block_access_flags >> (4 - angle))
     };
 }
+
+/**
+ * Rotates every directional wall flag of a tile clockwise by `rotation` quarter turns,
+ * including the projectile and route blocker variants. Non directional flags are kept as is.
+ * The eight wall flags of each kind run clockwise from north-west, so a quarter turn is a
+ * rotation of that byte by two bits.
+ */
+#[inline(always)]
+pub fn rotate_collision_flags(rotation: u8, flags: u32) -> u32 {
+    let rotation: u32 = (rotation & 0x3) as u32;
+    if rotation == 0 {
+        return flags;
+    }
+    let walk: u8 = (flags & 0xff) as u8;
+    let proj: u8 = ((flags >> 9) & 0xff) as u8;
+    let route: u8 = ((flags >> 22) & 0xff) as u8;
+    let other: u32 = flags & !(0xff | (0xff << 9) | (0xff << 22));
+    return other
+        | walk.rotate_left(rotation * 2) as u32
+        | (proj.rotate_left(rotation * 2) as u32) << 9
+        | (route.rotate_left(rotation * 2) as u32) << 22;
+}
@@ -0,0 +1,301 @@
+use crate::rsmod::collision::collision::CollisionFlagReader;
+use crate::rsmod::collision_flag::CollisionFlag;
+use crate::rsmod::line::Line;
+
+/**
+ * Every tile within a square around a source, with one bit per tile telling whether the source
+ * can see it. `origin_x`, `origin_z` is the south-west tile of the square and `length` its side.
+ */
+#[derive(Clone, PartialEq, Debug)]
+pub struct VisibilityMap {
+    pub origin_x: i32,
+    pub origin_z: i32,
+    pub y: i32,
+    pub length: i32,
+    bits: Vec<u64>,
+}
+
+impl VisibilityMap {
+    #[inline(always)]
+    fn new(origin_x: i32, origin_z: i32, y: i32, length: i32) -> VisibilityMap {
+        let tiles: usize = (length * length) as usize;
+        return VisibilityMap {
+            origin_x,
+            origin_z,
+            y,
+            length,
+            bits: vec![0; tiles.div_ceil(64)],
+        };
+    }
+
+    #[inline(always)]
+    pub fn contains(&self, x: i32, z: i32) -> bool {
+        let local_x: i32 = x - self.origin_x;
+        let local_z: i32 = z - self.origin_z;
+        return local_x >= 0 && local_z >= 0 && local_x < self.length && local_z < self.length;
+    }
+
+    /**
+     * Returns false for tiles outside of the square.
+     */
+    #[inline(always)]
+    pub fn is_visible(&self, x: i32, z: i32) -> bool {
+        if !self.contains(x, z) {
+            return false;
+        }
+        let index: usize = self.index(x, z);
+        return self.bits[index >> 6] & (1 << (index & 0x3f)) != 0;
+    }
+
+    /**
+     * The bitmap, tile `(x, z)` being bit `(x - origin_x) + (z - origin_z) * length`.
+     */
+    #[inline(always)]
+    pub fn bits(&self) -> &[u64] {
+        return &self.bits;
+    }
+
+    #[inline(always)]
+    pub fn visible_count(&self) -> usize {
+        return self.bits.iter().map(|word| word.count_ones() as usize).sum();
+    }
+
+    /**
+     * Iterates over the visible tiles as `(x, z)`, row by row from the south-west.
+     */
+    pub fn visible_tiles(&self) -> impl Iterator<Item = (i32, i32)> + '_ {
+        return (0..self.length * self.length)
+            .filter(move |&index| self.bits[index as usize >> 6] & (1 << (index & 0x3f)) != 0)
+            .map(move |index| (self.origin_x + index % self.length, self.origin_z + index / self.length));
+    }
+
+    #[inline(always)]
+    fn index(&self, x: i32, z: i32) -> usize {
+        return ((x - self.origin_x) + (z - self.origin_z) * self.length) as usize;
+    }
+
+    #[inline(always)]
+    fn set_visible(&mut self, x: i32, z: i32) {
+        let index: usize = self.index(x, z);
+        self.bits[index >> 6] |= 1 << (index & 0x3f);
+    }
+}
+
+/**
+ * The flags of the square plus a one tile margin, read once so the rays don't go through the zones for every tile.
+ */
+struct AreaFlags {
+    origin_x: i32,
+    origin_z: i32,
+    length: i32,
+    flags: Vec<u32>,
+}
+
+impl AreaFlags {
+    #[inline(always)]
+    unsafe fn new(flags: &impl CollisionFlagReader, origin_x: i32, origin_z: i32, y: i32, length: i32) -> AreaFlags {
+        let mut area: Vec<u32> = Vec::with_capacity((length * length) as usize);
+        for z in origin_z..origin_z + length {
+            for x in origin_x..origin_x + length {
+                // tiles of unallocated zones never block, see [CollisionFlagReader::is_flagged].
+                area.push(if flags.is_zone_allocated(x, z, y) { flags.get(x, z, y) } else { CollisionFlag::OPEN as u32 });
+            }
+        }
+        return AreaFlags { origin_x, origin_z, length, flags: area };
+    }
+
+    /**
+     * Rays never leave the square by more than the margin, anything further out is open.
+     */
+    #[inline(always)]
+    fn is_flagged(&self, x: i32, z: i32, masks: u32) -> bool {
+        let index: usize = ((x - self.origin_x) + (z - self.origin_z) * self.length) as usize;
+        return match self.flags.get(index) {
+            Some(flags) => flags & masks != 0,
+            None => false,
+        };
+    }
+}
+
+/**
+ * Finds every tile within `radius` tiles (Chebyshev) of a `src_size` source that it has line of sight to.
+ * A tile is visible exactly when [crate::rsmod::has_line_of_sight] to it with a 1x1 destination is true.
+ *
+ * Rays towards `k * (a, b)` are the first `k` steps of the ray towards `(a, b)`, so only one ray is
+ * cast per direction and every tile along it is decided from the same walk, stopping at the first obstruction.
+ */
+pub unsafe fn visibility_map(
+    flags: &impl CollisionFlagReader,
+    y: i32,
+    src_x: i32,
+    src_z: i32,
+    src_size: u8,
+    radius: i32,
+    extra_flag: u32,
+) -> VisibilityMap {
+    let radius: i32 = radius.max(0);
+    let src_size: u8 = src_size.max(1);
+    let length: i32 = src_size as i32 + radius * 2;
+    let mut map: VisibilityMap = VisibilityMap::new(src_x - radius, src_z - radius, y, length);
+    let max_x: i32 = map.origin_x + length - 1;
+    let max_z: i32 = map.origin_z + length - 1;
+    let flag_loc: u32 = CollisionFlag::LOC as u32 | extra_flag;
+    let area: AreaFlags = AreaFlags::new(flags, map.origin_x - 1, map.origin_z - 1, y, length + 2);
+
+    for dest_z in map.origin_z..=max_z {
+        for dest_x in map.origin_x..=max_x {
+            let start_x: i32 = Line::coordinate(src_x, dest_x, src_size);
+            let start_z: i32 = Line::coordinate(src_z, dest_z, src_size);
+            let delta_x: i32 = dest_x - start_x;
+            let delta_z: i32 = dest_z - start_z;
+            if delta_x == 0 && delta_z == 0 {
+                map.set_visible(dest_x, dest_z);
+                continue;
+            }
+            // every other multiple of this direction is decided by the ray of its primitive.
+            if gcd(delta_x.abs(), delta_z.abs()) != 1 || area.is_flagged(start_x, start_z, flag_loc) {
+                continue;
+            }
+            let multiples_x: i32 = multiples(start_x, delta_x, map.origin_x, max_x);
+            let multiples_z: i32 = multiples(start_z, delta_z, map.origin_z, max_z);
+            cast(&area, &mut map, start_x, start_z, delta_x, delta_z, multiples_x.min(multiples_z), extra_flag);
+        }
+    }
+    return map;
+}
+
+/**
+ * Binary gcd, this runs for every tile so it avoids the divisions of Euclid's.
+ */
+#[inline(always)]
+fn gcd(a: i32, b: i32) -> i32 {
+    let mut a: u32 = a as u32;
+    let mut b: u32 = b as u32;
+    if a == 0 || b == 0 {
+        return (a | b) as i32;
+    }
+    let shift: u32 = (a | b).trailing_zeros();
+    a >>= a.trailing_zeros();
+    loop {
+        b >>= b.trailing_zeros();
+        if a > b {
+            std::mem::swap(&mut a, &mut b);
+        }
+        b -= a;
+        if b == 0 {
+            return (a << shift) as i32;
+        }
+    }
+}
+
+#[inline(always)]
+fn multiples(start: i32, delta: i32, min: i32, max: i32) -> i32 {
+    if delta > 0 {
+        return (max - start) / delta;
+    } else if delta < 0 {
+        return (start - min) / -delta;
+    }
+    return i32::MAX;
+}
+
+/**
+ * Walks the ray from `start` towards `start + multiples * delta` the same way as the line of sight
+ * validator, marking `start + k * delta` visible while the ray leading up to it is clear.
+ * The end tile of each shorter ray ignores the projectile blockers, like the validator does for its destination.
+ */
+#[inline(always)]
+fn cast(
+    flags: &AreaFlags,
+    map: &mut VisibilityMap,
+    start_x: i32,
+    start_z: i32,
+    delta_x: i32,
+    delta_z: i32,
+    multiples: i32,
+    extra_flag: u32,
+) {
+    let absolute_delta_x: i32 = delta_x.abs();
+    let absolute_delta_z: i32 = delta_z.abs();
+
+    let travel_east: bool = delta_x >= 0;
+    let travel_north: bool = delta_z >= 0;
+
+    let x_flags: u32 = if travel_east { Line::SIGHT_BLOCKED_WEST } else { Line::SIGHT_BLOCKED_EAST } | extra_flag;
+    let z_flags: u32 = if travel_north { Line::SIGHT_BLOCKED_SOUTH } else { Line::SIGHT_BLOCKED_NORTH } | extra_flag;
+    let flag_proj: u32 = CollisionFlag::LOC_PROJ_BLOCKER as u32 | extra_flag;
+
+    if absolute_delta_x > absolute_delta_z {
+        let offset_x: i32 = if travel_east { 1 } else { -1 };
+        let offset_z: i32 = if travel_north { 0 } else { -1 };
+
+        let mut scaled_z: i64 = Line::scale_up(start_z) + Line::HALF_TILE + offset_z as i64;
+        let tangent: i64 = Line::scale_up(delta_z) / absolute_delta_x as i64;
+
+        let mut curr_x: i32 = start_x;
+        let mut end_x: i32 = start_x;
+        let mut end_z: i32 = start_z;
+        let mut until_end: i32 = absolute_delta_x;
+        for _ in 0..multiples * absolute_delta_x {
+            curr_x += offset_x;
+            let curr_z: i32 = Line::scale_down(scaled_z);
+            scaled_z += tangent;
+            let next_z: i32 = Line::scale_down(scaled_z);
+
+            until_end -= 1;
+            if until_end == 0 {
+                until_end = absolute_delta_x;
+                end_x += delta_x;
+                end_z += delta_z;
+                let x_mask: u32 = if curr_x == end_x && curr_z == end_z { x_flags & !flag_proj } else { x_flags };
+                let z_mask: u32 = if curr_x == end_x && next_z == end_z { z_flags & !flag_proj } else { z_flags };
+                if !flags.is_flagged(curr_x, curr_z, x_mask)
+                    && (next_z == curr_z || !flags.is_flagged(curr_x, next_z, z_mask)) {
+                    map.set_visible(end_x, end_z);
+                } else {
+                    // the masks without the exemption are a superset.
+                    return;
+                }
+            }
+            if flags.is_flagged(curr_x, curr_z, x_flags)
+                || (next_z != curr_z && flags.is_flagged(curr_x, next_z, z_flags)) {
+                return;
+            }
+        }
+    } else {
+        let offset_x: i32 = if travel_east { 0 } else { -1 };
+        let offset_z: i32 = if travel_north { 1 } else { -1 };
+
+        let mut scaled_x: i64 = Line::scale_up(start_x) + Line::HALF_TILE + offset_x as i64;
+        let tangent: i64 = Line::scale_up(delta_x) / absolute_delta_z as i64;
+
+        let mut curr_z: i32 = start_z;
+        let mut end_x: i32 = start_x;
+        let mut end_z: i32 = start_z;
+        let mut until_end: i32 = absolute_delta_z;
+        for _ in 0..multiples * absolute_delta_z {
+            curr_z += offset_z;
+            let curr_x: i32 = Line::scale_down(scaled_x);
+            scaled_x += tangent;
+            let next_x: i32 = Line::scale_down(scaled_x);
+
+            until_end -= 1;
+            if until_end == 0 {
+                until_end = absolute_delta_z;
+                end_x += delta_x;
+                end_z += delta_z;
+                let z_mask: u32 = if curr_x == end_x && curr_z == end_z { z_flags & !flag_proj } else { z_flags };
+                let x_mask: u32 = if next_x == end_x && curr_z == end_z { x_flags & !flag_proj } else { x_flags };
+                if !flags.is_flagged(curr_x, curr_z, z_mask)
+                    && (next_x == curr_x || !flags.is_flagged(next_x, curr_z, x_mask)) {
+                    map.set_visible(end_x, end_z);
+                } else {
+                    return;
+                }
+            }
+            if flags.is_flagged(curr_x, curr_z, z_flags)
+                || (next_x != curr_x && flags.is_flagged(next_x, curr_z, x_flags)) {
+                return;
+            }
+        }
+    }
+}
@@ -0,0 +1,533 @@
+use wasm_bindgen::prelude::*;
+
+use crate::{COLLISION_FLAGS, PATHFINDER};
+use crate::rsmod::{
+    can_travel, find_naive_path, find_naive_path_with, get_collision_strategy, has_line_of_sight, has_line_of_walk, line_of_sight, line_of_sight_result, line_of_walk, line_of_walk_result,
+    CollisionType, CoordOutOfRange, LocShape, SeededRandom,
+};
+use crate::rsmod::collision_change::CollisionFlagWriter;
+use crate::rsmod::collision_edit::{CollisionEdit, CollisionOp};
+use crate::rsmod::reach_strategy::ReachStrategy;
+
+#[inline(always)]
+fn loc_angle(angle: u8) -> Result<u8, JsError> {
+    if angle > 3 {
+        return Err(JsError::new(&format!("Invalid loc angle: {}", angle)));
+    }
+    return Ok(angle);
+}
+
+/**
+ * Throws for an angle or shape the path and reach functions can't handle.
+ */
+#[inline(always)]
+fn path_target(angle: u8, shape: i8) -> Result<(), JsError> {
+    if !ReachStrategy::is_valid_target(angle, shape) {
+        return Err(JsError::new(&format!("Invalid loc angle or shape: {}, {}", angle, shape)));
+    }
+    return Ok(());
+}
+
+/**
+ * Finds a path with the breadth-first search. Returns the packed waypoints, empty if there is no path.
+ * Throws if a waypoint can't be packed, e.g. far out in a sparse map, or for an angle outside of 0..=3
+ * or a shape that is not a loc shape, -1 or -2.
+ */
+#[wasm_bindgen(js_name = "findPath")]
+pub unsafe fn find_path(
+    y: i32,
+    srcX: i32,
+    srcZ: i32,
+    destX: i32,
+    destZ: i32,
+    srcSize: u8,
+    destWidth: u8,
+    destHeight: u8,
+    angle: u8,
+    shape: i8,
+    moveNear: bool,
+    blockAccessFlags: u8,
+    maxWaypoints: u8,
+    collision: CollisionType,
+) -> Result<Vec<u32>, JsError> {
+    path_target(angle, shape)?;
+    return Ok(PATHFINDER.lock().unwrap().find_path(
+        &*COLLISION_FLAGS.read().unwrap(),
+        y,
+        srcX,
+        srcZ,
+        destX,
+        destZ,
+        srcSize,
+        destWidth,
+        destHeight,
+        angle,
+        shape,
+        moveNear,
+        blockAccessFlags,
+        maxWaypoints,
+        &get_collision_strategy(collision),
+    )?);
+}
+
+/**
+ * Finds the single next step of an npc moving straight towards its target.
+ * Given a `seed`, stepping out from under the target is deterministic.
+ */
+#[wasm_bindgen(js_name = "findNaivePath")]
+pub unsafe fn find_naive_path_wasm(
+    y: i32,
+    srcX: i32,
+    srcZ: i32,
+    destX: i32,
+    destZ: i32,
+    srcWidth: u8,
+    srcHeight: u8,
+    destWidth: u8,
+    destHeight: u8,
+    extraFlag: u32,
+    collision: CollisionType,
+    seed: Option<u32>,
+) -> Result<Vec<u32>, JsError> {
+    let flags = COLLISION_FLAGS.read().unwrap();
+    let waypoints: Result<Vec<u32>, CoordOutOfRange> = match seed {
+        Some(seed) => find_naive_path_with(
+            &*flags,
+            y,
+            srcX,
+            srcZ,
+            destX,
+            destZ,
+            srcWidth,
+            srcHeight,
+            destWidth,
+            destHeight,
+            extraFlag,
+            &get_collision_strategy(collision),
+            &mut SeededRandom::new(seed as u64),
+        ),
+        None => find_naive_path(
+            &*flags,
+            y,
+            srcX,
+            srcZ,
+            destX,
+            destZ,
+            srcWidth,
+            srcHeight,
+            destWidth,
+            destHeight,
+            extraFlag,
+            &get_collision_strategy(collision),
+        ),
+    };
+    return Ok(waypoints?);
+}
+
+#[wasm_bindgen(js_name = "changeFloor")]
+pub unsafe fn change_floor(x: i32, z: i32, y: i32, add: bool) -> Result<(), JsError> {
+    COLLISION_FLAGS.write().unwrap().change_floor(x, z, y, add)?;
+    return Ok(());
+}
+
+#[wasm_bindgen(js_name = "changeLoc")]
+pub unsafe fn change_loc(
+    x: i32,
+    z: i32,
+    y: i32,
+    width: i32,
+    length: i32,
+    blockrange: bool,
+    breakroutefinding: bool,
+    add: bool,
+) -> Result<(), JsError> {
+    COLLISION_FLAGS.write().unwrap().change_loc(x, z, y, width, length, blockrange, breakroutefinding, add)?;
+    return Ok(());
+}
+
+#[wasm_bindgen(js_name = "changeLocShape")]
+pub unsafe fn change_loc_shape(
+    x: i32,
+    z: i32,
+    y: i32,
+    width: u8,
+    length: u8,
+    angle: u8,
+    shape: LocShape,
+    blockrange: bool,
+    breakroutefinding: bool,
+    add: bool,
+) -> Result<(), JsError> {
+    COLLISION_FLAGS.write().unwrap().change_loc_shape(
+        x,
+        z,
+        y,
+        width,
+        length,
+        angle & 0x3,
+        shape,
+        blockrange,
+        breakroutefinding,
+        add,
+    )?;
+    return Ok(());
+}
+
+#[wasm_bindgen(js_name = "changeNpc")]
+pub unsafe fn change_npc(x: i32, z: i32, y: i32, size: i32, add: bool) -> Result<(), JsError> {
+    COLLISION_FLAGS.write().unwrap().change_npc(x, z, y, size, add)?;
+    return Ok(());
+}
+
+#[wasm_bindgen(js_name = "changePlayer")]
+pub unsafe fn change_player(x: i32, z: i32, y: i32, size: i32, add: bool) -> Result<(), JsError> {
+    COLLISION_FLAGS.write().unwrap().change_player(x, z, y, size, add)?;
+    return Ok(());
+}
+
+#[wasm_bindgen(js_name = "changeRoof")]
+pub unsafe fn change_roof(x: i32, z: i32, y: i32, add: bool) -> Result<(), JsError> {
+    COLLISION_FLAGS.write().unwrap().change_roof(x, z, y, add)?;
+    return Ok(());
+}
+
+/**
+ * Flags a wall of shape 0-3. Throws for any other shape, diagonal walls (9) included as they are
+ * ground layer locs, see `changeLocShape` for those.
+ */
+#[wasm_bindgen(js_name = "changeWall")]
+pub unsafe fn change_wall(
+    x: i32,
+    z: i32,
+    y: i32,
+    angle: u8,
+    shape: LocShape,
+    blockrange: bool,
+    breakroutefinding: bool,
+    add: bool,
+) -> Result<(), JsError> {
+    COLLISION_FLAGS.write().unwrap().change_wall(x, z, y, angle & 0x3, shape, blockrange, breakroutefinding, add)?;
+    return Ok(());
+}
+
+#[wasm_bindgen(js_name = "changeWallStraight")]
+pub unsafe fn change_wall_straight(x: i32, z: i32, y: i32, angle: u8, blockrange: bool, breakroutefinding: bool, add: bool) -> Result<(), JsError> {
+    let angle: u8 = loc_angle(angle)?;
+    COLLISION_FLAGS.write().unwrap().change_wall_straight(x, z, y, angle, blockrange, breakroutefinding, add)?;
+    return Ok(());
+}
+
+#[wasm_bindgen(js_name = "changeWallCorner")]
+pub unsafe fn change_wall_corner(x: i32, z: i32, y: i32, angle: u8, blockrange: bool, breakroutefinding: bool, add: bool) -> Result<(), JsError> {
+    let angle: u8 = loc_angle(angle)?;
+    COLLISION_FLAGS.write().unwrap().change_wall_corner(x, z, y, angle, blockrange, breakroutefinding, add)?;
+    return Ok(());
+}
+
+#[wasm_bindgen(js_name = "changeWallL")]
+pub unsafe fn change_wall_l(x: i32, z: i32, y: i32, angle: u8, blockrange: bool, breakroutefinding: bool, add: bool) -> Result<(), JsError> {
+    let angle: u8 = loc_angle(angle)?;
+    COLLISION_FLAGS.write().unwrap().change_wall_l(x, z, y, angle, blockrange, breakroutefinding, add)?;
+    return Ok(());
+}
+
+/**
+ * Replaces the 64 tiles of a zone, indexed by `(x & 7) | ((z & 7) << 3)`.
+ */
+#[wasm_bindgen(js_name = "setZone")]
+pub unsafe fn set_zone(x: i32, z: i32, y: i32, flags: &[u32]) -> Result<(), JsError> {
+    let zone: &[u32; 64] = flags.try_into().map_err(|_| JsError::new("Zone flags must hold 64 masks"))?;
+    COLLISION_FLAGS.write().unwrap().try_set_zone(x, z, y, zone)?;
+    return Ok(());
+}
+
+/**
+ * Applies edits given as groups of x, z, y, mask, op.
+ */
+#[wasm_bindgen(js_name = "applyEdits")]
+pub unsafe fn apply_edits(edits: &[i32]) -> Result<(), JsError> {
+    if edits.len() % 5 != 0 {
+        return Err(JsError::new("Edits must be groups of x, z, y, mask, op"));
+    }
+    let mut parsed: Vec<CollisionEdit> = Vec::with_capacity(edits.len() / 5);
+    for edit in edits.chunks_exact(5) {
+        let op: CollisionOp = u8::try_from(edit[4])
+            .ok()
+            .and_then(CollisionOp::try_from_u8)
+            .ok_or_else(|| JsError::new(&format!("Invalid collision op: {}", edit[4])))?;
+        parsed.push(CollisionEdit::new(edit[0], edit[1], edit[2], edit[3] as u32, op));
+    }
+    COLLISION_FLAGS.write().unwrap().try_apply(&parsed)?;
+    return Ok(());
+}
+
+/**
+ * Replaces all 4 levels of a mapsquare, see `CollisionFlagMap::load_mapsquare` for the layout.
+ * Returns false if `flags` has the wrong length or the mapsquare is outside of the map.
+ */
+#[wasm_bindgen(js_name = "loadMapsquare")]
+pub unsafe fn load_mapsquare(mapsquareX: i32, mapsquareZ: i32, flags: &[u32]) -> bool {
+    return COLLISION_FLAGS.write().unwrap().load_mapsquare(mapsquareX, mapsquareZ, flags);
+}
+
+#[wasm_bindgen(js_name = "allocateIfAbsent")]
+pub unsafe fn allocate_if_absent(x: i32, z: i32, y: i32) -> Result<(), JsError> {
+    COLLISION_FLAGS.write().unwrap().try_allocate_if_absent(x, z, y)?;
+    return Ok(());
+}
+
+#[wasm_bindgen(js_name = "deallocateIfPresent")]
+pub unsafe fn deallocate_if_present(x: i32, z: i32, y: i32) {
+    COLLISION_FLAGS.write().unwrap().deallocate_if_present(x, z, y);
+}
+
+#[wasm_bindgen(js_name = "isZoneAllocated")]
+pub unsafe fn is_zone_allocated(x: i32, z: i32, y: i32) -> bool {
+    return COLLISION_FLAGS.read().unwrap().is_zone_allocated(x, z, y);
+}
+
+#[wasm_bindgen(js_name = "isFlagged")]
+pub unsafe fn is_flagged(x: i32, z: i32, y: i32, masks: u32) -> bool {
+    return COLLISION_FLAGS.read().unwrap().is_flagged(x, z, y, masks);
+}
+
+#[wasm_bindgen(js_name = "canTravel")]
+pub unsafe fn can_travel_wasm(
+    y: i32,
+    x: i32,
+    z: i32,
+    offsetX: i8,
+    offsetZ: i8,
+    size: u8,
+    extraFlag: u32,
+    collision: CollisionType,
+) -> bool {
+    return can_travel(
+        &*COLLISION_FLAGS.read().unwrap(),
+        y,
+        x,
+        z,
+        offsetX,
+        offsetZ,
+        size,
+        extraFlag,
+        &get_collision_strategy(collision),
+    );
+}
+
+#[wasm_bindgen(js_name = "hasLineOfSight")]
+pub unsafe fn has_line_of_sight_wasm(
+    y: i32,
+    srcX: i32,
+    srcZ: i32,
+    destX: i32,
+    destZ: i32,
+    srcWidth: u8,
+    srcHeight: u8,
+    destWidth: u8,
+    destHeight: u8,
+    extraFlag: u32,
+) -> bool {
+    return has_line_of_sight(
+        &*COLLISION_FLAGS.read().unwrap(),
+        y,
+        srcX,
+        srcZ,
+        destX,
+        destZ,
+        srcWidth,
+        srcHeight,
+        destWidth,
+        destHeight,
+        extraFlag,
+    );
+}
+
+#[wasm_bindgen(js_name = "hasLineOfWalk")]
+pub unsafe fn has_line_of_walk_wasm(
+    y: i32,
+    srcX: i32,
+    srcZ: i32,
+    destX: i32,
+    destZ: i32,
+    srcWidth: u8,
+    srcHeight: u8,
+    destWidth: u8,
+    destHeight: u8,
+    extraFlag: u32,
+) -> bool {
+    return has_line_of_walk(
+        &*COLLISION_FLAGS.read().unwrap(),
+        y,
+        srcX,
+        srcZ,
+        destX,
+        destZ,
+        srcWidth,
+        srcHeight,
+        destWidth,
+        destHeight,
+        extraFlag,
+    );
+}
+
+/**
+ * Returns the packed tiles of the line of sight, empty if it is blocked. Throws if a tile can't be packed.
+ */
+#[wasm_bindgen(js_name = "lineOfSight")]
+pub unsafe fn line_of_sight_wasm(
+    y: i32,
+    srcX: i32,
+    srcZ: i32,
+    destX: i32,
+    destZ: i32,
+    srcWidth: u8,
+    srcHeight: u8,
+    destWidth: u8,
+    destHeight: u8,
+    extraFlag: u32,
+) -> Result<Vec<u32>, JsError> {
+    return Ok(line_of_sight(
+        &*COLLISION_FLAGS.read().unwrap(),
+        y,
+        srcX,
+        srcZ,
+        destX,
+        destZ,
+        srcWidth,
+        srcHeight,
+        destWidth,
+        destHeight,
+        extraFlag,
+    )?);
+}
+
+/**
+ * Returns the packed tiles of the line of walk, empty if it is blocked. Throws if a tile can't be packed.
+ */
+#[wasm_bindgen(js_name = "lineOfWalk")]
+pub unsafe fn line_of_walk_wasm(
+    y: i32,
+    srcX: i32,
+    srcZ: i32,
+    destX: i32,
+    destZ: i32,
+    srcWidth: u8,
+    srcHeight: u8,
+    destWidth: u8,
+    destHeight: u8,
+    extraFlag: u32,
+) -> Result<Vec<u32>, JsError> {
+    return Ok(line_of_walk(
+        &*COLLISION_FLAGS.read().unwrap(),
+        y,
+        srcX,
+        srcZ,
+        destX,
+        destZ,
+        srcWidth,
+        srcHeight,
+        destWidth,
+        destHeight,
+        extraFlag,
+    )?);
+}
+
+/**
+ * Like [line_of_sight_wasm], but keeps the tiles up to an obstruction and reports what blocked the line,
+ * laid out as `[blocker, x, z, y, flags, tiles...]` with a blocker of -1 if the line of sight is clear.
+ * Throws if a tile can't be packed.
+ */
+#[wasm_bindgen(js_name = "lineOfSightResult")]
+pub unsafe fn line_of_sight_result_wasm(
+    y: i32,
+    srcX: i32,
+    srcZ: i32,
+    destX: i32,
+    destZ: i32,
+    srcWidth: u8,
+    srcHeight: u8,
+    destWidth: u8,
+    destHeight: u8,
+    extraFlag: u32,
+) -> Result<Vec<i32>, JsError> {
+    return Ok(line_of_sight_result(
+        &*COLLISION_FLAGS.read().unwrap(),
+        y,
+        srcX,
+        srcZ,
+        destX,
+        destZ,
+        srcWidth,
+        srcHeight,
+        destWidth,
+        destHeight,
+        extraFlag,
+    )?
+    .flatten());
+}
+
+/**
+ * Like [line_of_walk_wasm], but keeps the tiles up to an obstruction and reports what blocked the line,
+ * laid out as `[blocker, x, z, y, flags, tiles...]` with a blocker of -1 if the line of walk is clear.
+ * Throws if a tile can't be packed.
+ */
+#[wasm_bindgen(js_name = "lineOfWalkResult")]
+pub unsafe fn line_of_walk_result_wasm(
+    y: i32,
+    srcX: i32,
+    srcZ: i32,
+    destX: i32,
+    destZ: i32,
+    srcWidth: u8,
+    srcHeight: u8,
+    destWidth: u8,
+    destHeight: u8,
+    extraFlag: u32,
+) -> Result<Vec<i32>, JsError> {
+    return Ok(line_of_walk_result(
+        &*COLLISION_FLAGS.read().unwrap(),
+        y,
+        srcX,
+        srcZ,
+        destX,
+        destZ,
+        srcWidth,
+        srcHeight,
+        destWidth,
+        destHeight,
+        extraFlag,
+    )?
+    .flatten());
+}
+
+#[wasm_bindgen(js_name = "reached")]
+pub unsafe fn reached(
+    y: i32,
+    srcX: i32,
+    srcZ: i32,
+    destX: i32,
+    destZ: i32,
+    destWidth: u8,
+    destHeight: u8,
+    srcSize: u8,
+    angle: u8,
+    shape: i8,
+    blockAccessFlags: u8,
+) -> Result<bool, JsError> {
+    path_target(angle, shape)?;
+    return Ok(ReachStrategy::reached(
+        &*COLLISION_FLAGS.read().unwrap(),
+        y,
+        srcX,
+        srcZ,
+        destX,
+        destZ,
+        destWidth,
+        destHeight,
+        srcSize,
+        angle,
+        shape,
+        blockAccessFlags,
+    ));
+}
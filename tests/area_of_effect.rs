@@ -0,0 +1,80 @@
+use rsmod::rsmod::{area_of_effect, area_of_effect_targets, AreaShape, AreaTarget, CoordGrid};
+use rsmod::rsmod::collision::collision::CollisionFlagMap;
+use rsmod::rsmod::collision_flag::CollisionFlag;
+
+unsafe fn build_collision_map() -> CollisionFlagMap {
+    let mut collision = CollisionFlagMap::new();
+    for z in 3190..3210 {
+        for x in 3190..3210 {
+            collision.allocate_if_absent(x, z, 0);
+        }
+    }
+    return collision;
+}
+
+#[test]
+fn test_shapes() {
+    let chebyshev = AreaShape::Chebyshev { radius: 2 };
+    assert!(chebyshev.contains(2, -2));
+    assert!(!chebyshev.contains(3, 0));
+
+    let euclidean = AreaShape::Euclidean { radius: 2 };
+    assert!(euclidean.contains(0, 2));
+    assert!(!euclidean.contains(2, 2));
+
+    let cone = AreaShape::Cone { radius: 3, direction_x: 1, direction_z: 0, half_angle: 45.0 };
+    assert!(cone.contains(0, 0));
+    assert!(cone.contains(2, 2));
+    assert!(cone.contains(3, 0));
+    assert!(!cone.contains(1, 2));
+    assert!(!cone.contains(-1, 0));
+}
+
+#[test]
+fn test_area_of_effect_tiles() {
+    unsafe {
+        let collision = build_collision_map();
+
+        let tiles = area_of_effect(&collision, 0, 3200, 3200, &AreaShape::Chebyshev { radius: 1 }, 0).unwrap();
+        assert_eq!(9, tiles.len());
+        assert_eq!(CoordGrid::new(0, 3199, 3199).packed, tiles[0]);
+
+        let tiles = area_of_effect(&collision, 0, 3200, 3200, &AreaShape::Euclidean { radius: 2 }, 0).unwrap();
+        assert_eq!(13, tiles.len());
+    }
+}
+
+#[test]
+fn test_area_of_effect_needs_line_of_sight() {
+    unsafe {
+        let mut collision = build_collision_map();
+        collision.add(3202, 3200, 0, CollisionFlag::WALL_WEST_PROJ_BLOCKER as u32);
+
+        let shape = AreaShape::Cone { radius: 3, direction_x: 1, direction_z: 0, half_angle: 0.0 };
+        let tiles = area_of_effect(&collision, 0, 3200, 3200, &shape, 0).unwrap();
+        assert_eq!(vec![CoordGrid::new(0, 3200, 3200).packed, CoordGrid::new(0, 3201, 3200).packed], tiles);
+    }
+}
+
+#[test]
+fn test_area_of_effect_targets() {
+    unsafe {
+        let mut collision = build_collision_map();
+        collision.add(3198, 3200, 0, CollisionFlag::LOC_PROJ_BLOCKER as u32);
+
+        let targets = [
+            // a 2x2 target overlapping the blast with one tile.
+            AreaTarget { x: 3202, z: 3202, width: 2, height: 2 },
+            // entirely outside of the blast.
+            AreaTarget { x: 3204, z: 3200, width: 1, height: 1 },
+            // inside the blast, behind a projectile blocker.
+            AreaTarget { x: 3197, z: 3200, width: 1, height: 1 },
+            AreaTarget { x: 3200, z: 3199, width: 1, height: 1 },
+        ];
+        let caught = area_of_effect_targets(&collision, 0, 3200, 3200, &AreaShape::Chebyshev { radius: 3 }, &targets, 0);
+        assert_eq!(vec![0, 3], caught);
+
+        let caught = area_of_effect_targets(&collision, 0, 3200, 3200, &AreaShape::Euclidean { radius: 2 }, &targets, 0);
+        assert_eq!(vec![3], caught);
+    }
+}
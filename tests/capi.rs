@@ -0,0 +1,184 @@
+use rsmod::capi::*;
+use rsmod::rsmod::collision_flag::CollisionFlag;
+use rsmod::rsmod::{CoordGrid, LocShape, RayBlocker, WideCoordGrid};
+use rsmod::rsmod::collision::collision::CollisionFlagMap;
+use rsmod::rsmod::collision::collision_change::{ChangeWallError, CollisionFlagWriter};
+
+#[test]
+fn test_capi_map_status() {
+    unsafe {
+        let map = rsmod_map_new();
+        let mut flags: u32 = 0;
+
+        assert_eq!(RSMOD_OK, rsmod_map_add(map, 3200, 3200, 0, CollisionFlag::LOC as u32));
+        assert_eq!(RSMOD_OK, rsmod_map_get(map, 3200, 3200, 0, &mut flags));
+        assert_eq!(CollisionFlag::LOC as u32, flags);
+
+        assert_eq!(RSMOD_ERROR_OUT_OF_RANGE, rsmod_map_add(map, 16384, 3200, 0, CollisionFlag::LOC as u32));
+        assert_eq!(RSMOD_ERROR_NULL_POINTER, rsmod_map_get(std::ptr::null(), 3200, 3200, 0, &mut flags));
+        assert_eq!(RSMOD_ERROR_INVALID_ARGUMENT, rsmod_map_change_loc_shape(map, 3200, 3200, 0, 1, 1, 0, 23, false, false, true));
+        assert_eq!(RSMOD_ERROR_OUT_OF_RANGE, rsmod_map_change_floor(map, -1, 3200, 0, true));
+        assert_eq!(RSMOD_ERROR_INVALID_ARGUMENT, rsmod_map_load_mapsquare(map, 50, 50, [0; 64].as_ptr(), 64));
+        assert_eq!(RSMOD_ERROR_INVALID_ARGUMENT, rsmod_map_change_wall_straight(map, 3200, 3200, 0, 4, false, false, true));
+        assert_eq!(RSMOD_ERROR_INVALID_ARGUMENT, rsmod_map_change_wall_corner(map, 3200, 3200, 0, 4, false, false, true));
+        assert_eq!(RSMOD_ERROR_INVALID_ARGUMENT, rsmod_map_change_wall_l(map, 3200, 3200, 0, 255, false, false, true));
+        assert_eq!(RSMOD_OK, rsmod_map_change_wall_l(map, 3200, 3200, 0, 3, false, false, true));
+
+        let mut reached: bool = false;
+        assert_eq!(RSMOD_ERROR_INVALID_ARGUMENT, rsmod_reached(map, 0, 3200, 3201, 3200, 3200, 1, 1, 1, 4, 0, 0, &mut reached));
+        assert_eq!(RSMOD_ERROR_INVALID_ARGUMENT, rsmod_reached(map, 0, 3200, 3201, 3200, 3200, 1, 1, 1, 0, 23, 0, &mut reached));
+        assert_eq!(RSMOD_OK, rsmod_reached(map, 0, 3200, 3201, 3200, 3200, 1, 1, 1, 0, -2, 0, &mut reached));
+
+        rsmod_map_free(map);
+    }
+}
+
+#[test]
+fn test_capi_find_path_buffer() {
+    unsafe {
+        let map = rsmod_map_new_sparse();
+        let pathfinder = rsmod_pathfinder_new();
+        for x in 3200..3216 {
+            for z in 3200..3216 {
+                assert_eq!(RSMOD_OK, rsmod_map_allocate_if_absent(map, x, z, 0));
+            }
+        }
+
+        let mut waypoints: [u32; 25] = [0; 25];
+        let mut len: usize = 0;
+        assert_eq!(
+            RSMOD_ERROR_BUFFER_TOO_SMALL,
+            rsmod_find_path(pathfinder, map, 0, 3200, 3200, 3205, 3210, 1, 1, 1, 0, -1, true, 0, 25, 0, waypoints.as_mut_ptr(), 0, &mut len)
+        );
+        assert_eq!(2, len);
+        assert_eq!(
+            RSMOD_OK,
+            rsmod_find_path(pathfinder, map, 0, 3200, 3200, 3205, 3210, 1, 1, 1, 0, -1, true, 0, 25, 0, waypoints.as_mut_ptr(), waypoints.len(), &mut len)
+        );
+        assert_eq!(CoordGrid::new(0, 3205, 3210).packed, waypoints[len - 1]);
+        assert_eq!(
+            RSMOD_ERROR_INVALID_ARGUMENT,
+            rsmod_find_path(pathfinder, map, 0, 3200, 3200, 3205, 3210, 1, 1, 1, 0, -1, true, 0, 25, 5, waypoints.as_mut_ptr(), waypoints.len(), &mut len)
+        );
+        assert_eq!(
+            RSMOD_ERROR_INVALID_ARGUMENT,
+            rsmod_find_path(pathfinder, map, 0, 3200, 3200, 3205, 3210, 1, 1, 1, 4, 0, true, 0, 25, 0, waypoints.as_mut_ptr(), waypoints.len(), &mut len)
+        );
+        assert_eq!(
+            RSMOD_ERROR_INVALID_ARGUMENT,
+            rsmod_find_path(pathfinder, map, 0, 3200, 3200, 3205, 3210, 1, 1, 1, 0, -3, true, 0, 25, 0, waypoints.as_mut_ptr(), waypoints.len(), &mut len)
+        );
+
+        let mut visible: bool = false;
+        assert_eq!(RSMOD_OK, rsmod_has_line_of_sight(map, 0, 3200, 3200, 3205, 3210, 1, 1, 1, 1, 0, &mut visible));
+        assert!(visible);
+
+        rsmod_pathfinder_free(pathfinder);
+        rsmod_map_free(map);
+    }
+}
+
+#[test]
+fn test_capi_line_result() {
+    unsafe {
+        let map = rsmod_map_new();
+        assert_eq!(RSMOD_OK, rsmod_map_set(map, 3203, 3200, 0, CollisionFlag::WALL_WEST_PROJ_BLOCKER as u32));
+
+        let mut tiles: [u32; 8] = [0; 8];
+        let mut len: usize = 0;
+        let mut block = RsmodRayBlock { blocker: 0, x: 0, z: 0, y: 0, flags: 0 };
+        assert_eq!(
+            RSMOD_OK,
+            rsmod_line_of_sight_result(map, 0, 3200, 3200, 3206, 3200, 1, 1, 0, 0, 0, tiles.as_mut_ptr(), tiles.len(), &mut len, &mut block)
+        );
+        assert_eq!(&[CoordGrid::new(0, 3201, 3200).packed, CoordGrid::new(0, 3202, 3200).packed], &tiles[..len]);
+        assert_eq!((RayBlocker::WALL_WEST as i32, 3203, 3200, 0), (block.blocker, block.x, block.z, block.y));
+        assert_eq!(CollisionFlag::WALL_WEST_PROJ_BLOCKER as u32, block.flags);
+
+        assert_eq!(
+            RSMOD_OK,
+            rsmod_line_of_walk_result(map, 0, 3200, 3201, 3206, 3201, 1, 1, 0, 0, 0, tiles.as_mut_ptr(), tiles.len(), &mut len, &mut block)
+        );
+        assert_eq!(6, len);
+        assert_eq!(-1, block.blocker);
+        assert_eq!(
+            RSMOD_ERROR_NULL_POINTER,
+            rsmod_line_of_walk_result(map, 0, 3200, 3201, 3206, 3201, 1, 1, 0, 0, 0, tiles.as_mut_ptr(), tiles.len(), &mut len, std::ptr::null_mut())
+        );
+
+        rsmod_map_free(map);
+    }
+}
+
+#[test]
+fn test_capi_wide_results() {
+    unsafe {
+        let map = rsmod_map_new_sparse();
+        let pathfinder = rsmod_pathfinder_new();
+        for x in 100000..100008 {
+            assert_eq!(RSMOD_OK, rsmod_map_allocate_if_absent(map, x, 100001, 0));
+        }
+
+        let mut waypoints: [u32; 25] = [0; 25];
+        let mut wide: [u64; 25] = [0; 25];
+        let mut len: usize = 0;
+        assert_eq!(
+            RSMOD_ERROR_OUT_OF_RANGE,
+            rsmod_find_path(pathfinder, map, 0, 100001, 100001, 100005, 100001, 1, 1, 1, 0, -1, true, 0, 25, 0, waypoints.as_mut_ptr(), waypoints.len(), &mut len)
+        );
+        assert_eq!(
+            RSMOD_OK,
+            rsmod_find_path_wide(pathfinder, map, 0, 100001, 100001, 100005, 100001, 1, 1, 1, 0, -1, true, 0, 25, 0, wide.as_mut_ptr(), wide.len(), &mut len)
+        );
+        assert_eq!(&[WideCoordGrid::try_new(0, 100005, 100001).unwrap().packed], &wide[..len]);
+
+        let mut block = RsmodRayBlock { blocker: 0, x: 0, z: 0, y: 0, flags: 0 };
+        assert_eq!(
+            RSMOD_OK,
+            rsmod_line_of_walk_wide(map, 0, 100001, 100001, 100003, 100001, 1, 1, 1, 1, 0, wide.as_mut_ptr(), wide.len(), &mut len, &mut block)
+        );
+        assert_eq!(
+            &[WideCoordGrid::try_new(0, 100002, 100001).unwrap().packed, WideCoordGrid::try_new(0, 100003, 100001).unwrap().packed],
+            &wide[..len]
+        );
+        assert_eq!(-1, block.blocker);
+
+        rsmod_pathfinder_free(pathfinder);
+        rsmod_map_free(map);
+    }
+}
+
+#[test]
+fn test_capi_change_wall_matches_core() {
+    unsafe {
+        let map = rsmod_map_new();
+        let mut core = CollisionFlagMap::new();
+        for shape in -1..=22 {
+            let expected: i32 = match LocShape::try_from_i8(shape) {
+                None => RSMOD_ERROR_INVALID_ARGUMENT,
+                Some(loc) => match core.change_wall(3200, 3200, 0, 1, loc, true, false, true) {
+                    Ok(_) => RSMOD_OK,
+                    Err(ChangeWallError::NotAWall(_)) => RSMOD_ERROR_INVALID_ARGUMENT,
+                    Err(ChangeWallError::OutOfRange(_)) => RSMOD_ERROR_OUT_OF_RANGE,
+                },
+            };
+            assert_eq!(expected, rsmod_map_change_wall(map, 3200, 3200, 0, 1, shape, true, false, true), "shape {}", shape);
+        }
+        // diagonal walls are ground layer locs, every binding refuses them like the core does.
+        assert_eq!(RSMOD_ERROR_INVALID_ARGUMENT, rsmod_map_change_wall(map, 3200, 3200, 0, 0, 9, true, false, true));
+        for (x, z) in [(3199, 3200), (3200, 3200), (3201, 3200), (3200, 3201), (3200, 3199)] {
+            let mut flags: u32 = 0;
+            assert_eq!(RSMOD_OK, rsmod_map_get(map, x, z, 0, &mut flags));
+            assert_eq!(core.get(x, z, 0), flags);
+        }
+
+        rsmod_map_free(map);
+    }
+}
+
+#[test]
+fn test_capi_header_up_to_date() {
+    let generated: String = std::fs::read_to_string(concat!(env!("OUT_DIR"), "/rsmod.h")).unwrap();
+    let tracked: String = std::fs::read_to_string(concat!(env!("CARGO_MANIFEST_DIR"), "/include/rsmod.h")).unwrap();
+    assert_eq!(tracked, generated, "include/rsmod.h is out of date, rebuild with RSMOD_WRITE_HEADER=1");
+}
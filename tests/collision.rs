@@ -1,3 +1,4 @@
+use rsmod::rsmod::{CoordGrid, CoordOutOfRange, WideCoordGrid};
 use rsmod::rsmod::collision::collision::CollisionFlagMap;
 use rsmod::rsmod::collision_flag::CollisionFlag;
 
@@ -127,3 +128,76 @@ fn test_collision_deallocate_if_present() {
 fn test_collision_zone_index() {
     assert_eq!(CollisionFlagMap::zone_index(0, 0, 0), 0);
 }
+
+#[test]
+fn test_collision_out_of_range() {
+    let mut collision: CollisionFlagMap = CollisionFlagMap::new();
+
+    unsafe {
+        collision.set(0, 0, 0, CollisionFlag::LOC as u32);
+        assert_eq!(Err(CoordOutOfRange { x: 16384, z: 0, y: 0 }), collision.try_add(16384, 0, 0, CollisionFlag::FLOOR as u32));
+        assert!(collision.try_set(-1, 0, 0, CollisionFlag::FLOOR as u32).is_err());
+        assert!(collision.try_remove(0, 0, 4, CollisionFlag::LOC as u32).is_err());
+        assert!(collision.try_allocate_if_absent(0, 16384, 0).is_err());
+
+        // nothing aliased onto the tile at the origin.
+        assert_eq!(CollisionFlag::LOC as u32, collision.get(0, 0, 0));
+        assert_eq!(CollisionFlag::NULL as u32, collision.get(16384, 0, 0));
+        assert_eq!(CollisionFlag::NULL as u32, collision.get(0, 0, 4));
+        assert_eq!(false, collision.is_flagged(-8192, 0, 0, CollisionFlag::LOC as u32));
+        assert_eq!(false, collision.is_zone_allocated(0, -16384, 0));
+    }
+}
+
+#[test]
+#[should_panic]
+fn test_collision_out_of_range_panics() {
+    let mut collision: CollisionFlagMap = CollisionFlagMap::new();
+
+    unsafe { collision.add(16384, 0, 0, CollisionFlag::LOC as u32) }
+}
+
+#[test]
+fn test_sparse_collision() {
+    let mut collision: CollisionFlagMap = CollisionFlagMap::sparse();
+    assert!(collision.is_sparse());
+
+    unsafe {
+        assert_eq!(CollisionFlag::NULL as u32, collision.get(1_000_000, -50_000, 1));
+        collision.add(1_000_000, -50_000, 1, CollisionFlag::LOC as u32);
+        collision.add(1_000_000 - 16384, -50_000, 1, CollisionFlag::FLOOR as u32);
+        assert_eq!(CollisionFlag::LOC as u32, collision.get(1_000_000, -50_000, 1));
+        assert_eq!(CollisionFlag::FLOOR as u32, collision.get(1_000_000 - 16384, -50_000, 1));
+        assert_eq!(CollisionFlag::OPEN as u32, collision.get(1_000_001, -50_000, 1));
+        assert_eq!(CollisionFlag::NULL as u32, collision.get(1_000_000, -50_000, 0));
+        assert!(collision.try_add(0, 0, 4, CollisionFlag::LOC as u32).is_err());
+        assert!(collision.try_add(WideCoordGrid::MAX_COORD + 1, 0, 0, CollisionFlag::LOC as u32).is_err());
+        assert!(!collision.contains(0, WideCoordGrid::MIN_COORD - 1, 0));
+
+        let zones: Vec<(i32, i32, i32)> = collision.zones().map(|(x, z, y, _)| (x, z, y)).collect();
+        assert_eq!(vec![(1_000_000, -50_000, 1), (1_000_000 - 16384, -50_000, 1)], zones);
+
+        collision.deallocate_if_present(1_000_000, -50_000, 1);
+        assert_eq!(false, collision.is_zone_allocated(1_000_000, -50_000, 1));
+    }
+}
+
+#[test]
+fn test_coord_grid_out_of_range() {
+    assert_eq!(CoordGrid::new(3, 16383, 0).packed, CoordGrid::try_new(3, 16383, 0).unwrap().packed);
+    assert!(CoordGrid::try_new(0, 16384, 0).is_err());
+    assert!(CoordGrid::try_new(0, 0, -1).is_err());
+    assert!(CoordGrid::try_new(4, 0, 0).is_err());
+}
+
+#[test]
+fn test_wide_coord_grid() {
+    for (y, x, z) in [(0, 0, 0), (3, 1_000_000, -50_000), (1, WideCoordGrid::MIN_COORD, WideCoordGrid::MAX_COORD)] {
+        let coord: WideCoordGrid = WideCoordGrid::try_new(y, x, z).unwrap();
+        assert_eq!((y, x, z), (coord.y(), coord.x(), coord.z()));
+    }
+    assert!(WideCoordGrid::try_new(0, WideCoordGrid::MAX_COORD + 1, 0).is_err());
+    assert!(WideCoordGrid::try_new(0, 0, WideCoordGrid::MIN_COORD - 1).is_err());
+    assert!(WideCoordGrid::try_new(-1, 0, 0).is_err());
+}
+
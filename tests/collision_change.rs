@@ -0,0 +1,175 @@
+use rsmod::rsmod::{CoordOutOfRange, LocLayer, LocShape};
+use rsmod::rsmod::collision::collision::CollisionFlagMap;
+use rsmod::rsmod::collision::collision_change::{ChangeWallError, CollisionFlagWriter};
+use rsmod::rsmod::collision_flag::CollisionFlag;
+
+#[test]
+fn test_wall_straight_flags_both_sides() {
+    let mut collision = CollisionFlagMap::new();
+
+    unsafe {
+        collision.change_loc_shape(3200, 3200, 0, 1, 1, 0, LocShape::WALL_STRAIGHT, false, false, true).unwrap();
+        assert_eq!(CollisionFlag::WALL_WEST as u32, collision.get(3200, 3200, 0));
+        assert_eq!(CollisionFlag::WALL_EAST as u32, collision.get(3199, 3200, 0));
+
+        collision.change_loc_shape(3200, 3200, 0, 1, 1, 0, LocShape::WALL_STRAIGHT, false, false, false).unwrap();
+        assert_eq!(CollisionFlag::OPEN as u32, collision.get(3200, 3200, 0));
+        assert_eq!(CollisionFlag::OPEN as u32, collision.get(3199, 3200, 0));
+    }
+}
+
+#[test]
+fn test_wall_route_blocker_includes_proj_and_walk() {
+    let mut collision = CollisionFlagMap::new();
+
+    unsafe {
+        collision.change_wall_straight(3200, 3200, 0, 1, true, true, true).unwrap();
+        assert_eq!(
+            CollisionFlag::WALL_NORTH as u32
+                | CollisionFlag::WALL_NORTH_PROJ_BLOCKER as u32
+                | CollisionFlag::WALL_NORTH_ROUTE_BLOCKER as u32,
+            collision.get(3200, 3200, 0)
+        );
+        assert_eq!(
+            CollisionFlag::WALL_SOUTH as u32
+                | CollisionFlag::WALL_SOUTH_PROJ_BLOCKER as u32
+                | CollisionFlag::WALL_SOUTH_ROUTE_BLOCKER as u32,
+            collision.get(3200, 3201, 0)
+        );
+    }
+}
+
+#[test]
+fn test_wall_square_corner_matches_diagonal_corner() {
+    let mut diagonal = CollisionFlagMap::new();
+    let mut square = CollisionFlagMap::new();
+
+    unsafe {
+        for angle in 0..4 {
+            diagonal.change_loc_shape(3200, 3200, 0, 1, 1, angle, LocShape::WALL_DIAGONAL_CORNER, true, false, true).unwrap();
+            square.change_loc_shape(3200, 3200, 0, 1, 1, angle, LocShape::WALL_SQUARE_CORNER, true, false, true).unwrap();
+        }
+        for x in 3199..3202 {
+            for z in 3199..3202 {
+                assert_eq!(diagonal.get(x, z, 0), square.get(x, z, 0));
+            }
+        }
+    }
+}
+
+#[test]
+fn test_wall_diagonal_flags_loc() {
+    let mut collision = CollisionFlagMap::new();
+
+    unsafe {
+        collision.change_loc_shape(3200, 3200, 0, 1, 1, 1, LocShape::WALL_DIAGONAL, true, false, true).unwrap();
+        assert_eq!(
+            CollisionFlag::LOC as u32 | CollisionFlag::LOC_PROJ_BLOCKER as u32,
+            collision.get(3200, 3200, 0)
+        );
+        assert_eq!(CollisionFlag::OPEN as u32, collision.get(3201, 3200, 0));
+    }
+}
+
+#[test]
+fn test_centrepiece_diagonal_rotates_footprint() {
+    let mut collision = CollisionFlagMap::new();
+
+    unsafe {
+        collision.change_loc_shape(3200, 3200, 0, 3, 1, 1, LocShape::CENTREPIECE_DIAGONAL, false, false, true).unwrap();
+        for z in 3200..3203 {
+            assert_eq!(CollisionFlag::LOC as u32, collision.get(3200, z, 0));
+        }
+        assert_eq!(CollisionFlag::OPEN as u32, collision.get(3201, 3200, 0));
+        assert_eq!(CollisionFlag::OPEN as u32, collision.get(3200, 3203, 0));
+    }
+}
+
+#[test]
+fn test_ground_decor_flags_floor_decoration() {
+    let mut collision = CollisionFlagMap::new();
+
+    unsafe {
+        collision.change_loc_shape(3200, 3200, 0, 1, 1, 0, LocShape::GROUND_DECOR, false, false, true).unwrap();
+        assert_eq!(CollisionFlag::FLOOR_DECORATION as u32, collision.get(3200, 3200, 0));
+    }
+}
+
+#[test]
+fn test_wall_decor_has_no_collision() {
+    let mut collision = CollisionFlagMap::new();
+
+    unsafe {
+        for shape in 4..=8 {
+            collision.change_loc_shape(3200, 3200, 0, 1, 1, 0, LocShape::from(shape), true, true, true).unwrap();
+        }
+        assert_eq!(false, collision.is_zone_allocated(3200, 3200, 0));
+    }
+}
+
+#[test]
+fn test_loc_shape_layers() {
+    assert_eq!(LocLayer::WALL, LocShape::WALL_L.layer());
+    assert_eq!(LocLayer::WALL_DECOR, LocShape::WALLDECOR_DIAGONAL_BOTH.layer());
+    assert_eq!(LocLayer::GROUND, LocShape::WALL_DIAGONAL.layer());
+    assert_eq!(LocLayer::GROUND, LocShape::CENTREPIECE_DIAGONAL.layer());
+    assert_eq!(LocLayer::GROUND_DECOR, LocShape::GROUND_DECOR.layer());
+}
+
+#[test]
+fn test_loc_shape_unknown() {
+    assert_eq!(Some(LocShape::WALL_DIAGONAL), LocShape::try_from_i8(9));
+    assert_eq!(None, LocShape::try_from_i8(23));
+    assert_eq!(None, LocShape::try_from_i8(-1));
+}
+
+#[test]
+fn test_change_out_of_range() {
+    let mut collision = CollisionFlagMap::new();
+
+    unsafe {
+        assert_eq!(Err(CoordOutOfRange { x: -1, z: 3200, y: 0 }), collision.change_floor(-1, 3200, 0, true));
+        assert_eq!(Err(CoordOutOfRange { x: 3200, z: 3200, y: 4 }), collision.change_roof(3200, 3200, 4, true));
+        assert!(collision.change_npc(16384, 3200, 0, 1, true).is_err());
+    }
+}
+
+#[test]
+fn test_change_never_half_applied() {
+    let mut collision = CollisionFlagMap::new();
+
+    unsafe {
+        // the west facing wall also flags the tile west of it, which is off the map.
+        assert_eq!(Err(CoordOutOfRange { x: -1, z: 3200, y: 0 }), collision.change_wall_straight(0, 3200, 0, 0, false, false, true));
+        assert_eq!(false, collision.is_zone_allocated(0, 3200, 0));
+
+        assert_eq!(Err(CoordOutOfRange { x: 16384, z: 3201, y: 0 }), collision.change_npc(16383, 3200, 0, 2, true));
+        assert_eq!(false, collision.is_zone_allocated(16383, 3200, 0));
+
+        assert!(collision.change_loc_shape(16383, 3200, 0, 2, 1, 0, LocShape::CENTREPIECE_STRAIGHT, false, false, true).is_err());
+        assert_eq!(false, collision.is_zone_allocated(16383, 3200, 0));
+    }
+}
+
+#[test]
+fn test_change_wall_rejects_other_layers() {
+    let mut collision = CollisionFlagMap::new();
+
+    unsafe {
+        assert_eq!(
+            Err(ChangeWallError::NotAWall(LocShape::WALL_DIAGONAL)),
+            collision.change_wall(3200, 3200, 0, 0, LocShape::WALL_DIAGONAL, false, false, true)
+        );
+        assert_eq!(
+            Err(ChangeWallError::NotAWall(LocShape::GROUND_DECOR)),
+            collision.change_wall(3200, 3200, 0, 0, LocShape::GROUND_DECOR, false, false, true)
+        );
+        assert_eq!(false, collision.is_zone_allocated(3200, 3200, 0));
+
+        assert_eq!(Ok(()), collision.change_wall(3200, 3200, 0, 0, LocShape::WALL_L, false, false, true));
+        assert_eq!(
+            Err(ChangeWallError::OutOfRange(CoordOutOfRange { x: -1, z: 3200, y: 0 })),
+            collision.change_wall(0, 3200, 0, 0, LocShape::WALL_STRAIGHT, false, false, true)
+        );
+    }
+}
@@ -0,0 +1,98 @@
+use rsmod::rsmod::CoordOutOfRange;
+use rsmod::rsmod::collision::collision::CollisionFlagMap;
+use rsmod::rsmod::collision_flag::CollisionFlag;
+
+#[test]
+fn test_copy_zone_without_rotation() {
+    let mut collision = CollisionFlagMap::new();
+
+    unsafe {
+        collision.add(3201, 3203, 0, CollisionFlag::WALL_NORTH as u32);
+        collision.copy_zone(3200, 3200, 0, 6400, 6408, 1, 0);
+        assert_eq!(CollisionFlag::WALL_NORTH as u32, collision.get(6401, 6411, 1));
+        assert_eq!(CollisionFlag::OPEN as u32, collision.get(6400, 6408, 1));
+        assert_eq!(CollisionFlag::WALL_NORTH as u32, collision.get(3201, 3203, 0));
+    }
+}
+
+#[test]
+fn test_copy_zone_rotates_tiles_and_walls() {
+    let mut collision = CollisionFlagMap::new();
+
+    unsafe {
+        // wall along the north edge of the south-west tile.
+        collision.add(3200, 3200, 0, CollisionFlag::WALL_NORTH as u32 | CollisionFlag::WALL_NORTH_PROJ_BLOCKER as u32);
+        collision.add(3203, 3207, 0, CollisionFlag::LOC as u32);
+
+        collision.copy_zone(3200, 3200, 0, 6400, 6400, 0, 1);
+        assert_eq!(
+            CollisionFlag::WALL_EAST as u32 | CollisionFlag::WALL_EAST_PROJ_BLOCKER as u32,
+            collision.get(6400, 6407, 0)
+        );
+        assert_eq!(CollisionFlag::LOC as u32, collision.get(6407, 6404, 0));
+
+        collision.copy_zone(3200, 3200, 0, 6408, 6400, 0, 2);
+        assert_eq!(
+            CollisionFlag::WALL_SOUTH as u32 | CollisionFlag::WALL_SOUTH_PROJ_BLOCKER as u32,
+            collision.get(6415, 6407, 0)
+        );
+        assert_eq!(CollisionFlag::LOC as u32, collision.get(6412, 6400, 0));
+
+        collision.copy_zone(3200, 3200, 0, 6416, 6400, 0, 3);
+        assert_eq!(
+            CollisionFlag::WALL_WEST as u32 | CollisionFlag::WALL_WEST_PROJ_BLOCKER as u32,
+            collision.get(6423, 6400, 0)
+        );
+        assert_eq!(CollisionFlag::LOC as u32, collision.get(6416, 6403, 0));
+    }
+}
+
+#[test]
+fn test_copy_zone_in_place() {
+    let mut collision = CollisionFlagMap::new();
+
+    unsafe {
+        collision.add(3200, 3200, 0, CollisionFlag::WALL_WEST as u32);
+        for _ in 0..4 {
+            collision.copy_zone(3200, 3200, 0, 3200, 3200, 0, 1);
+        }
+        assert_eq!(CollisionFlag::WALL_WEST as u32, collision.get(3200, 3200, 0));
+    }
+}
+
+#[test]
+fn test_copy_unallocated_zone() {
+    let mut collision = CollisionFlagMap::new();
+
+    unsafe {
+        collision.allocate_if_absent(6400, 6400, 0);
+        collision.copy_zone(3200, 3200, 0, 6400, 6400, 0, 2);
+        assert_eq!(false, collision.is_zone_allocated(6400, 6400, 0));
+    }
+}
+
+#[test]
+fn test_copy_zone_out_of_range() {
+    let mut collision = CollisionFlagMap::new();
+
+    unsafe {
+        collision.add(3200, 3200, 0, CollisionFlag::LOC as u32);
+        collision.allocate_if_absent(6400, 6400, 0);
+        assert_eq!(
+            Err(CoordOutOfRange { x: 16384, z: 6400, y: 0 }),
+            collision.try_copy_zone(3200, 3200, 0, 16384, 6400, 0, 1)
+        );
+        assert_eq!(
+            Err(CoordOutOfRange { x: 6400, z: 6400, y: 4 }),
+            collision.try_copy_zone(3200, 3200, 0, 6400, 6400, 4, 1)
+        );
+        // an out of range source doesn't read as unallocated and clear the destination.
+        assert_eq!(
+            Err(CoordOutOfRange { x: -1, z: 3200, y: 0 }),
+            collision.try_copy_zone(-1, 3200, 0, 6400, 6400, 0, 1)
+        );
+        assert!(collision.is_zone_allocated(6400, 6400, 0));
+        assert_eq!(Ok(()), collision.try_copy_zone(3200, 3200, 0, 6400, 6400, 0, 0));
+        assert_eq!(CollisionFlag::LOC as u32, collision.get(6400, 6400, 0));
+    }
+}
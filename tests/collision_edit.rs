@@ -0,0 +1,81 @@
+use rsmod::rsmod::CoordOutOfRange;
+use rsmod::rsmod::collision::collision::CollisionFlagMap;
+use rsmod::rsmod::collision_edit::{CollisionEdit, CollisionOp};
+use rsmod::rsmod::collision_flag::CollisionFlag;
+
+#[test]
+fn test_set_zone() {
+    let mut collision = CollisionFlagMap::new();
+    let mut zone: [u32; 64] = [CollisionFlag::OPEN as u32; 64];
+    zone[CollisionFlagMap::tile_index(3203, 3205)] = CollisionFlag::LOC as u32;
+
+    unsafe {
+        collision.set_zone(3201, 3201, 1, &zone);
+        assert!(collision.is_zone_allocated(3200, 3200, 1));
+        assert_eq!(CollisionFlag::LOC as u32, collision.get(3203, 3205, 1));
+        assert_eq!(CollisionFlag::OPEN as u32, collision.get(3202, 3205, 1));
+        assert_eq!(false, collision.is_zone_allocated(3200, 3200, 0));
+    }
+}
+
+#[test]
+fn test_apply_edits() {
+    let mut collision = CollisionFlagMap::new();
+
+    unsafe {
+        collision.apply(&[
+            CollisionEdit::new(3200, 3200, 0, CollisionFlag::LOC as u32 | CollisionFlag::NPC as u32, CollisionOp::SET),
+            CollisionEdit::new(3200, 3200, 0, CollisionFlag::FLOOR as u32, CollisionOp::ADD),
+            CollisionEdit::new(3200, 3200, 0, CollisionFlag::NPC as u32, CollisionOp::REMOVE),
+            CollisionEdit::new(3300, 3300, 2, CollisionFlag::ROOF as u32, CollisionOp::ADD),
+        ]);
+        assert_eq!(CollisionFlag::LOC as u32 | CollisionFlag::FLOOR as u32, collision.get(3200, 3200, 0));
+        assert_eq!(CollisionFlag::ROOF as u32, collision.get(3300, 3300, 2));
+    }
+}
+
+#[test]
+fn test_load_mapsquare() {
+    let mut collision = CollisionFlagMap::new();
+    let mut flags: Vec<u32> = vec![CollisionFlag::OPEN as u32; CollisionFlagMap::MAPSQUARE_TILE_COUNT];
+    // level 2, zone (3, 5), tile (1, 6) of mapsquare 50_50.
+    flags[(2 << 12) | (5 << 9) | (3 << 6) | CollisionFlagMap::tile_index(1, 6)] = CollisionFlag::LOC as u32;
+
+    unsafe {
+        assert!(collision.load_mapsquare(50, 50, &flags));
+        for y in 0..4 {
+            for x in (3200..3264).step_by(8) {
+                for z in (3200..3264).step_by(8) {
+                    assert!(collision.is_zone_allocated(x, z, y));
+                }
+            }
+        }
+        assert_eq!(CollisionFlag::LOC as u32, collision.get(3200 + 24 + 1, 3200 + 40 + 6, 2));
+        assert_eq!(CollisionFlag::OPEN as u32, collision.get(3200 + 24 + 1, 3200 + 40 + 6, 1));
+        assert_eq!(false, collision.is_zone_allocated(3264, 3200, 0));
+
+        assert!(!collision.load_mapsquare(50, 51, &flags[1..]));
+        assert_eq!(false, collision.is_zone_allocated(3200, 3264, 0));
+
+        assert!(!collision.load_mapsquare(256, 50, &flags));
+        assert!(!collision.load_mapsquare(-1, 50, &flags));
+        assert!(!collision.load_mapsquare(i32::MAX, 50, &flags));
+    }
+}
+
+#[test]
+fn test_apply_edits_out_of_range() {
+    let mut collision = CollisionFlagMap::new();
+
+    unsafe {
+        assert_eq!(
+            Err(CoordOutOfRange { x: -1, z: 3200, y: 0 }),
+            collision.try_apply(&[
+                CollisionEdit::new(3200, 3200, 0, CollisionFlag::LOC as u32, CollisionOp::ADD),
+                CollisionEdit::new(-1, 3200, 0, CollisionFlag::LOC as u32, CollisionOp::ADD),
+            ])
+        );
+        assert_eq!(false, collision.is_zone_allocated(3200, 3200, 0));
+        assert!(collision.try_set_zone(16384, 0, 0, &[0; 64]).is_err());
+    }
+}
@@ -0,0 +1,108 @@
+use rsmod::rsmod::WideCoordGrid;
+use rsmod::rsmod::collision::collision::CollisionFlagMap;
+use rsmod::rsmod::collision::collision_change::CollisionFlagWriter;
+use rsmod::rsmod::collision_flag::CollisionFlag;
+use rsmod::rsmod::collision_journal::CollisionChange;
+
+#[test]
+fn test_journal_disabled() {
+    let mut collision = CollisionFlagMap::new();
+
+    unsafe {
+        collision.add(3200, 3200, 0, CollisionFlag::LOC as u32);
+    }
+    assert!(collision.journal().is_none());
+    assert_eq!(0, collision.zone_version(3200, 3200, 0));
+}
+
+#[test]
+fn test_journal_zone_versions() {
+    let mut collision = CollisionFlagMap::new();
+    collision.enable_journal(false);
+
+    unsafe {
+        collision.allocate_if_absent(3200, 3200, 0);
+        assert_eq!(1, collision.zone_version(3200, 3200, 0));
+
+        collision.add(3201, 3202, 0, CollisionFlag::LOC as u32);
+        assert_eq!(2, collision.zone_version(3200, 3200, 0));
+
+        // writes that leave the tile as it was don't invalidate anything.
+        collision.add(3201, 3202, 0, CollisionFlag::LOC as u32);
+        collision.remove(3203, 3203, 0, CollisionFlag::LOC as u32);
+        assert_eq!(2, collision.zone_version(3200, 3200, 0));
+
+        collision.remove(3201, 3202, 0, CollisionFlag::LOC as u32);
+        assert_eq!(3, collision.zone_version(3200, 3200, 0));
+        assert_eq!(0, collision.zone_version(3208, 3200, 0));
+        assert_eq!(0, collision.zone_version(3200, 3200, 1));
+    }
+}
+
+#[test]
+fn test_journal_changed_zones() {
+    let mut collision = CollisionFlagMap::new();
+    collision.enable_journal(false);
+
+    unsafe {
+        collision.change_npc(3206, 3206, 0, 3, true).unwrap();
+        collision.set_zone(3200, 3200, 1, &[CollisionFlag::FLOOR as u32; 64]);
+    }
+    let journal = collision.journal_mut().unwrap();
+    assert_eq!(5, journal.changed_zones().len());
+    assert_eq!(
+        vec![
+            WideCoordGrid::try_new(0, 3200, 3200).unwrap(),
+            WideCoordGrid::try_new(0, 3208, 3200).unwrap(),
+            WideCoordGrid::try_new(0, 3200, 3208).unwrap(),
+            WideCoordGrid::try_new(0, 3208, 3208).unwrap(),
+            WideCoordGrid::try_new(1, 3200, 3200).unwrap(),
+        ],
+        journal.drain_changed_zones()
+    );
+    assert!(journal.drain_changed_zones().is_empty());
+    assert!(journal.drain_changes().is_empty());
+}
+
+#[test]
+fn test_journal_sparse_edges() {
+    let mut collision = CollisionFlagMap::sparse();
+    collision.enable_journal(false);
+
+    unsafe {
+        collision.add(WideCoordGrid::MAX_COORD, WideCoordGrid::MIN_COORD, 0, CollisionFlag::LOC as u32);
+        assert!(collision.try_add(0x50000000, 0, 0, CollisionFlag::LOC as u32).is_err());
+    }
+    assert_eq!(
+        vec![WideCoordGrid::try_new(0, WideCoordGrid::MAX_COORD & !0x7, WideCoordGrid::MIN_COORD).unwrap()],
+        collision.journal_mut().unwrap().drain_changed_zones()
+    );
+}
+
+#[test]
+fn test_journal_tile_changes() {
+    let mut collision = CollisionFlagMap::new();
+
+    unsafe {
+        collision.allocate_if_absent(3200, 3200, 0);
+        collision.enable_journal(true);
+        collision.add(3200, 3200, 0, CollisionFlag::WALL_NORTH as u32);
+        collision.set(3200, 3200, 0, CollisionFlag::LOC as u32);
+        collision.deallocate_if_present(3200, 3200, 0);
+    }
+    let changes = collision.journal_mut().unwrap().drain_changes();
+    assert_eq!(66, changes.len());
+    assert_eq!(
+        CollisionChange { x: 3200, z: 3200, y: 0, before: CollisionFlag::OPEN as u32, after: CollisionFlag::WALL_NORTH as u32 },
+        changes[0]
+    );
+    assert_eq!(
+        CollisionChange { x: 3200, z: 3200, y: 0, before: CollisionFlag::WALL_NORTH as u32, after: CollisionFlag::LOC as u32 },
+        changes[1]
+    );
+    assert_eq!(
+        CollisionChange { x: 3200, z: 3200, y: 0, before: CollisionFlag::LOC as u32, after: CollisionFlag::NULL as u32 },
+        changes[2]
+    );
+    assert_eq!(3, collision.zone_version(3207, 3207, 0));
+}
@@ -0,0 +1,19 @@
+use rsmod::rsmod::collision::collision::CollisionFlagMap;
+use rsmod::rsmod::collision_flag::CollisionFlag;
+
+#[test]
+fn test_load_mapsquare_json() {
+    let mut collision = CollisionFlagMap::new();
+    let mut flags: Vec<u32> = vec![CollisionFlag::OPEN as u32; CollisionFlagMap::MAPSQUARE_TILE_COUNT];
+    // level 1, zone (2, 3), tile (5, 6).
+    flags[(1 << 12) | (3 << 9) | (2 << 6) | CollisionFlagMap::tile_index(5, 6)] = CollisionFlag::LOC as u32;
+    let json: String = serde_json::to_string(&flags).unwrap();
+
+    unsafe {
+        assert_eq!(true, collision.load_mapsquare_json(50, 50, &json).unwrap());
+        assert_eq!(CollisionFlag::LOC as u32, collision.get(3200 + 16 + 5, 3200 + 24 + 6, 1));
+        assert_eq!(CollisionFlag::OPEN as u32, collision.get(3200, 3200, 0));
+        assert_eq!(false, collision.load_mapsquare_json(50, 50, "[0, 1, 2]").unwrap());
+        assert!(collision.load_mapsquare_json(50, 50, "{").is_err());
+    }
+}
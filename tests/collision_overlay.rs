@@ -0,0 +1,159 @@
+use rsmod::rsmod::{can_travel, has_line_of_sight, CollisionStrategies, Normal, PathFinder};
+use rsmod::rsmod::{CoordOutOfRange, LocShape};
+use rsmod::rsmod::collision::collision::{CollisionFlagMap, CollisionFlagReader};
+use rsmod::rsmod::collision::collision_change::CollisionFlagWriter;
+use rsmod::rsmod::collision_flag::CollisionFlag;
+use rsmod::rsmod::collision_overlay::CollisionOverlay;
+
+unsafe fn build_collision_map(x1: i32, z1: i32, x2: i32, z2: i32) -> CollisionFlagMap {
+    let mut collision = CollisionFlagMap::new();
+    for z in z1.min(z2)..=z1.max(z2) {
+        for x in x1.min(x2)..=x1.max(x2) {
+            collision.allocate_if_absent(x, z, 0);
+        }
+    }
+    return collision;
+}
+
+#[test]
+fn test_overlay_reads_through_base() {
+    unsafe {
+        let mut base = build_collision_map(3200, 3200, 3207, 3207);
+        base.set(3201, 3201, 0, CollisionFlag::LOC as u32);
+        let overlay = CollisionOverlay::new();
+        let view = overlay.view(&base);
+
+        assert_eq!(CollisionFlag::LOC as u32, view.get(3201, 3201, 0));
+        assert_eq!(CollisionFlag::NULL as u32, view.get(3300, 3300, 0));
+        assert!(view.is_zone_allocated(3200, 3200, 0));
+        assert_eq!(0, overlay.touched_zone_count());
+    }
+}
+
+#[test]
+fn test_overlay_writes_do_not_touch_base() {
+    unsafe {
+        let mut base = build_collision_map(3200, 3200, 3207, 3207);
+        base.set(3201, 3201, 0, CollisionFlag::LOC as u32);
+        let mut overlay = CollisionOverlay::new();
+        overlay.add(&base, 3202, 3202, 0, CollisionFlag::FLOOR as u32);
+        overlay.remove(&base, 3201, 3201, 0, CollisionFlag::LOC as u32);
+        overlay.set(&base, 3300, 3300, 0, CollisionFlag::ROOF as u32);
+
+        let view = overlay.view(&base);
+        assert_eq!(CollisionFlag::FLOOR as u32, view.get(3202, 3202, 0));
+        assert_eq!(CollisionFlag::OPEN as u32, view.get(3201, 3201, 0));
+        assert_eq!(CollisionFlag::ROOF as u32, view.get(3300, 3300, 0));
+
+        assert_eq!(CollisionFlag::OPEN as u32, base.get(3202, 3202, 0));
+        assert_eq!(CollisionFlag::LOC as u32, base.get(3201, 3201, 0));
+        assert_eq!(false, base.is_zone_allocated(3300, 3300, 0));
+        assert_eq!(2, overlay.touched_zone_count());
+    }
+}
+
+#[test]
+fn test_overlay_deallocate_and_revert() {
+    unsafe {
+        let base = build_collision_map(3200, 3200, 3207, 3207);
+        let mut overlay = CollisionOverlay::new();
+        overlay.deallocate_if_present(&base, 3200, 3200, 0);
+        assert_eq!(false, overlay.view(&base).is_zone_allocated(3200, 3200, 0));
+        assert_eq!(CollisionFlag::NULL as u32, overlay.view(&base).get(3200, 3200, 0));
+
+        overlay.revert_zone(3200, 3200, 0);
+        assert!(overlay.view(&base).is_zone_allocated(3200, 3200, 0));
+
+        overlay.add(&base, 3200, 3200, 0, CollisionFlag::LOC as u32);
+        overlay.clear();
+        assert_eq!(0, overlay.touched_zone_count());
+        assert_eq!(CollisionFlag::OPEN as u32, overlay.view(&base).get(3200, 3200, 0));
+    }
+}
+
+#[test]
+fn test_overlay_blocks_pathfinder_and_lines() {
+    let mut pf = PathFinder::new();
+
+    unsafe {
+        let base = build_collision_map(3190, 3190, 3210, 3210);
+        let mut overlay = CollisionOverlay::new();
+        for x in 3184..3216 {
+            overlay.set(&base, x, 3202, 0, CollisionFlag::LOC as u32 | CollisionFlag::LOC_PROJ_BLOCKER as u32);
+        }
+
+        let strategy = CollisionStrategies::Normal(Normal);
+        let shared = pf.find_path(&base, 0, 3200, 3200, 3200, 3205, 1, 1, 1, 0, -1, false, 0, 25, &strategy).unwrap();
+        let instanced = pf.find_path(&overlay.view(&base), 0, 3200, 3200, 3200, 3205, 1, 1, 1, 0, -1, false, 0, 25, &strategy).unwrap();
+        assert!(!shared.is_empty());
+        assert!(instanced.is_empty());
+
+        assert!(can_travel(&base, 0, 3200, 3201, 0, 1, 1, 0, &strategy));
+        assert!(!can_travel(&overlay.view(&base), 0, 3200, 3201, 0, 1, 1, 0, &strategy));
+
+        assert!(has_line_of_sight(&base, 0, 3200, 3200, 3200, 3205, 1, 1, 1, 1, 0));
+        assert!(!has_line_of_sight(&overlay.view(&base), 0, 3200, 3200, 3200, 3205, 1, 1, 1, 1, 0));
+    }
+}
+
+#[test]
+fn test_overlay_writes_out_of_range() {
+    unsafe {
+        let base = build_collision_map(3200, 3200, 3207, 3207);
+        let mut overlay = CollisionOverlay::new();
+        overlay.add(&base, 3200, 3200, 0, CollisionFlag::LOC as u32);
+
+        // y = 4 is not a level of the map and must not alias onto y = 0.
+        assert_eq!(Err(CoordOutOfRange { x: 3200, z: 3200, y: 4 }), overlay.try_set(&base, 3200, 3200, 4, CollisionFlag::FLOOR as u32));
+        assert_eq!(Err(CoordOutOfRange { x: 16384, z: 3200, y: 0 }), overlay.try_add(&base, 16384, 3200, 0, CollisionFlag::FLOOR as u32));
+        assert_eq!(Err(CoordOutOfRange { x: -1, z: 3200, y: 0 }), overlay.try_remove(&base, -1, 3200, 0, CollisionFlag::LOC as u32));
+        assert_eq!(Err(CoordOutOfRange { x: 3200, z: 3200, y: -1 }), overlay.try_set_zone(&base, 3200, 3200, -1, &[0; 64]));
+        assert_eq!(Err(CoordOutOfRange { x: 3200, z: 3200, y: 4 }), overlay.try_allocate_if_absent(&base, 3200, 3200, 4));
+        overlay.deallocate_if_present(&base, 3200, 3200, 4);
+        overlay.revert_zone(3200, 3200, 4);
+
+        let view = overlay.view(&base);
+        assert_eq!(CollisionFlag::LOC as u32, view.get(3200, 3200, 0));
+        assert_eq!(CollisionFlag::NULL as u32, view.get(3200, 3200, 4));
+        assert!(!view.is_zone_allocated(3200, 3200, 4));
+        assert!(!overlay.is_zone_touched(3200, 3200, 4));
+        assert_eq!(1, overlay.touched_zone_count());
+    }
+}
+
+#[test]
+#[should_panic]
+fn test_overlay_set_out_of_range_panics() {
+    unsafe {
+        let base = build_collision_map(3200, 3200, 3207, 3207);
+        CollisionOverlay::new().set(&base, 3200, 3200, 4, CollisionFlag::FLOOR as u32);
+    }
+}
+
+#[test]
+fn test_overlay_change_helpers_do_not_touch_base() {
+    unsafe {
+        let base = build_collision_map(3200, 3200, 3215, 3207);
+        let mut overlay = CollisionOverlay::new();
+        let mut expected = base.clone();
+        overlay.edit(&base).change_loc(3201, 3201, 0, 2, 1, true, false, true).unwrap();
+        overlay.edit(&base).change_wall(3207, 3203, 0, 2, LocShape::WALL_STRAIGHT, false, false, true).unwrap();
+        expected.change_loc(3201, 3201, 0, 2, 1, true, false, true).unwrap();
+        expected.change_wall(3207, 3203, 0, 2, LocShape::WALL_STRAIGHT, false, false, true).unwrap();
+
+        let view = overlay.view(&base);
+        for x in 3200..3216 {
+            for z in 3200..3208 {
+                assert_eq!(expected.get(x, z, 0), view.get(x, z, 0));
+                assert_eq!(CollisionFlag::OPEN as u32, base.get(x, z, 0));
+            }
+        }
+        // the wall spans two zones, both copied into the overlay.
+        assert_eq!(2, overlay.touched_zone_count());
+
+        // a wall half outside of the base changes nothing.
+        let mut edge = CollisionOverlay::new();
+        assert!(edge.edit(&base).change_wall_straight(0, 3200, 0, 0, false, false, true).is_err());
+        assert_eq!(0, edge.touched_zone_count());
+    }
+}
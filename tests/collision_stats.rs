@@ -0,0 +1,164 @@
+use rsmod::rsmod::collision::collision::CollisionFlagMap;
+use rsmod::rsmod::collision::collision_change::CollisionFlagWriter;
+use rsmod::rsmod::collision_flag::CollisionFlag;
+use rsmod::rsmod::collision_stats::{diff, diff_histogram, CollisionDiff};
+
+#[test]
+fn test_zone_coord() {
+    assert_eq!((3200, 3208, 2), CollisionFlagMap::zone_coord(CollisionFlagMap::zone_index(3205, 3214, 2)));
+}
+
+#[test]
+fn test_zones_and_tiles() {
+    let mut collision = CollisionFlagMap::new();
+
+    unsafe {
+        collision.allocate_if_absent(3200, 3200, 0);
+        collision.add(3209, 3203, 1, CollisionFlag::LOC as u32);
+        collision.add(3210, 3204, 1, CollisionFlag::FLOOR as u32);
+    }
+    let zones: Vec<(i32, i32, i32)> = collision.zones().map(|(x, z, y, _)| (x, z, y)).collect();
+    assert_eq!(vec![(3200, 3200, 0), (3208, 3200, 1)], zones);
+    assert_eq!(2, collision.zone_count());
+
+    let tiles: Vec<(i32, i32, i32, u32)> = collision.tiles().collect();
+    assert_eq!(
+        vec![(3209, 3203, 1, CollisionFlag::LOC as u32), (3210, 3204, 1, CollisionFlag::FLOOR as u32)],
+        tiles
+    );
+}
+
+#[test]
+fn test_zones_in_slot_order() {
+    let mut collision = CollisionFlagMap::new();
+
+    unsafe {
+        for &(x, z, y) in &[(16383, 16383, 3), (0, 0, 0), (3263, 3200, 2), (3200, 3263, 2), (3264, 3200, 2), (8000, 12000, 1)] {
+            collision.allocate_if_absent(x, z, y);
+        }
+        collision.allocate_if_absent(3300, 3300, 0);
+        collision.deallocate_if_present(3300, 3300, 0);
+    }
+    // walking every slot of the table gives the same zones in the same order.
+    let every_slot: Vec<(i32, i32, i32)> = collision
+        .flags
+        .iter()
+        .enumerate()
+        .filter(|(_, zone)| zone.is_some())
+        .map(|(slot, _)| CollisionFlagMap::zone_coord(slot))
+        .collect();
+    let zones: Vec<(i32, i32, i32)> = collision.zones().map(|(x, z, y, _)| (x, z, y)).collect();
+    assert_eq!(every_slot, zones);
+    assert_eq!(6, collision.zone_count());
+}
+
+#[test]
+fn test_flag_histogram() {
+    let mut collision = CollisionFlagMap::new();
+
+    unsafe {
+        collision.change_npc(3200, 3200, 0, 2, true).unwrap();
+        collision.add(3200, 3200, 0, CollisionFlag::LOC as u32);
+    }
+    let histogram = collision.flag_histogram();
+    assert_eq!(4, histogram[CollisionFlag::SINGLE.iter().position(|&flag| flag == CollisionFlag::NPC).unwrap()]);
+    assert_eq!(1, histogram[8]);
+    assert_eq!(5, histogram.iter().sum::<usize>());
+}
+
+#[test]
+fn test_memory_usage() {
+    let mut collision = CollisionFlagMap::new();
+    let empty = collision.memory_usage();
+
+    unsafe {
+        collision.allocate_if_absent(3200, 3200, 0);
+    }
+    assert_eq!(empty + 64 * 4, collision.memory_usage());
+}
+
+#[test]
+fn test_diff() {
+    let mut left = CollisionFlagMap::new();
+    let mut right = CollisionFlagMap::new();
+
+    unsafe {
+        left.add(3200, 3200, 0, CollisionFlag::LOC as u32 | CollisionFlag::FLOOR as u32);
+        right.add(3200, 3200, 0, CollisionFlag::LOC as u32 | CollisionFlag::WALL_NORTH as u32);
+        left.add(3201, 3200, 0, CollisionFlag::ROOF as u32);
+        right.add(3201, 3200, 0, CollisionFlag::ROOF as u32);
+    }
+    let diffs = diff(&left, &right);
+    assert_eq!(
+        vec![CollisionDiff {
+            x: 3200,
+            z: 3200,
+            y: 0,
+            left: CollisionFlag::LOC as u32 | CollisionFlag::FLOOR as u32,
+            right: CollisionFlag::LOC as u32 | CollisionFlag::WALL_NORTH as u32,
+            left_allocated: true,
+            right_allocated: true,
+        }],
+        diffs
+    );
+    assert_eq!(vec![CollisionFlag::WALL_NORTH], diffs[0].added());
+    assert_eq!(vec![CollisionFlag::FLOOR], diffs[0].removed());
+
+    let histogram = diff_histogram(&diffs);
+    assert_eq!(1, histogram[1]);
+    assert_eq!(1, histogram[21]);
+    assert_eq!(2, histogram.iter().sum::<usize>());
+}
+
+#[test]
+fn test_diff_unallocated_zone() {
+    let left = CollisionFlagMap::new();
+    let mut right = CollisionFlagMap::new();
+
+    unsafe {
+        right.allocate_if_absent(3200, 3200, 0);
+    }
+    let diffs = diff(&left, &right);
+    assert_eq!(64, diffs.len());
+    assert!(diffs.iter().all(|diff| diff.left == CollisionFlag::OPEN as u32 && diff.right == CollisionFlag::OPEN as u32));
+    assert!(diffs.iter().all(|diff| !diff.left_allocated && diff.right_allocated && diff.is_allocation_only()));
+    assert!(diffs.iter().all(|diff| diff.added().is_empty() && diff.removed().is_empty()));
+    assert_eq!([0; 32], diff_histogram(&diffs));
+}
+
+#[test]
+fn test_diff_unallocated_zone_flags() {
+    let mut left = CollisionFlagMap::new();
+    let mut right = CollisionFlagMap::new();
+
+    unsafe {
+        left.add(3200, 3200, 0, CollisionFlag::FLOOR as u32);
+        right.add(3208, 3200, 0, CollisionFlag::LOC as u32);
+    }
+    let diffs = diff(&left, &right);
+    assert_eq!(128, diffs.len());
+    let changed: Vec<&CollisionDiff> = diffs.iter().filter(|diff| !diff.is_allocation_only()).collect();
+    assert_eq!(2, changed.len());
+    assert_eq!((3200, true, false), (changed[0].x, changed[0].left_allocated, changed[0].right_allocated));
+    assert_eq!(vec![CollisionFlag::FLOOR], changed[0].removed());
+    assert!(changed[0].added().is_empty());
+    assert_eq!((3208, false, true), (changed[1].x, changed[1].left_allocated, changed[1].right_allocated));
+    assert_eq!(vec![CollisionFlag::LOC], changed[1].added());
+    assert!(changed[1].removed().is_empty());
+
+    let histogram = diff_histogram(&diffs);
+    assert_eq!(1, histogram[8]);
+    assert_eq!(1, histogram[21]);
+    assert_eq!(2, histogram.iter().sum::<usize>());
+}
+
+#[test]
+fn test_split_collision_flags() {
+    assert_eq!(
+        vec![CollisionFlag::WALL_NORTH, CollisionFlag::LOC, CollisionFlag::ROOF],
+        CollisionFlag::split(CollisionFlag::WALL_NORTH as u32 | CollisionFlag::LOC as u32 | CollisionFlag::ROOF as u32)
+    );
+    for (bit, flag) in CollisionFlag::SINGLE.iter().enumerate() {
+        assert_eq!(1 << bit, *flag as u32);
+    }
+}
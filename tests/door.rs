@@ -0,0 +1,98 @@
+use rsmod::rsmod::{CoordGrid, Door, DoorState, LocShape};
+use rsmod::rsmod::collision::collision::CollisionFlagMap;
+use rsmod::rsmod::collision::collision_change::CollisionFlagWriter;
+use rsmod::rsmod::collision_flag::CollisionFlag;
+
+fn door() -> Door {
+    return Door::new(
+        DoorState::new(LocShape::WALL_STRAIGHT, 0, 0, 0),
+        DoorState::new(LocShape::WALL_STRAIGHT, 1, -1, 0),
+        true,
+        false,
+    );
+}
+
+unsafe fn build_collision_map() -> CollisionFlagMap {
+    let mut collision = CollisionFlagMap::new();
+    for x in 3192..3216 {
+        for z in 3192..3216 {
+            collision.allocate_if_absent(x, z, 0);
+        }
+    }
+    return collision;
+}
+
+#[test]
+fn test_door_open_moves_wall() {
+    let door = door();
+
+    unsafe {
+        let mut collision = build_collision_map();
+        door.add(&mut collision, 3200, 3200, 0, false).unwrap();
+        assert!(collision.is_flagged(3200, 3200, 0, CollisionFlag::WALL_WEST as u32));
+        assert!(collision.is_flagged(3199, 3200, 0, CollisionFlag::WALL_EAST as u32));
+
+        door.open(&mut collision, 3200, 3200, 0).unwrap();
+        assert_eq!(CollisionFlag::OPEN as u32, collision.get(3200, 3200, 0));
+        assert_eq!(
+            CollisionFlag::WALL_NORTH as u32 | CollisionFlag::WALL_NORTH_PROJ_BLOCKER as u32,
+            collision.get(3199, 3200, 0)
+        );
+        assert_eq!(
+            CollisionFlag::WALL_SOUTH as u32 | CollisionFlag::WALL_SOUTH_PROJ_BLOCKER as u32,
+            collision.get(3199, 3201, 0)
+        );
+    }
+}
+
+#[test]
+fn test_door_open_reports_changed_tiles() {
+    let door = door();
+
+    unsafe {
+        let mut collision = build_collision_map();
+        door.add(&mut collision, 3200, 3200, 0, false).unwrap();
+
+        let mut changed: Vec<(u32, u32)> = door
+            .open(&mut collision, 3200, 3200, 0)
+            .unwrap()
+            .iter()
+            .map(|&packed| (CoordGrid::from(packed).x(), CoordGrid::from(packed).z()))
+            .collect();
+        changed.sort();
+        assert_eq!(vec![(3199, 3200), (3199, 3201), (3200, 3200)], changed);
+    }
+}
+
+#[test]
+fn test_door_close_restores_flags() {
+    let door = door();
+
+    unsafe {
+        let mut collision = build_collision_map();
+        collision.add(3199, 3200, 0, CollisionFlag::LOC as u32);
+        door.add(&mut collision, 3200, 3200, 0, false).unwrap();
+        let before: Vec<u32> = (3198..3202).map(|x| collision.get(x, 3200, 0)).collect();
+
+        door.open(&mut collision, 3200, 3200, 0).unwrap();
+        door.close(&mut collision, 3200, 3200, 0).unwrap();
+
+        let after: Vec<u32> = (3198..3202).map(|x| collision.get(x, 3200, 0)).collect();
+        assert_eq!(before, after);
+        assert_eq!(CollisionFlag::OPEN as u32, collision.get(3199, 3201, 0));
+    }
+}
+
+#[test]
+fn test_door_open_out_of_range() {
+    let door: Door = door();
+
+    unsafe {
+        let mut collision = CollisionFlagMap::new();
+        door.add(&mut collision, 0, 3200, 0, false).unwrap_err();
+        // the open side sits west of the map, so the closed side must stay.
+        collision.change_wall_straight(1, 3200, 0, 0, true, false, true).unwrap();
+        assert!(door.open(&mut collision, 1, 3200, 0).is_err());
+        assert!(collision.is_flagged(1, 3200, 0, CollisionFlag::WALL_WEST as u32));
+    }
+}
@@ -0,0 +1,96 @@
+use rsmod::rsmod::{
+    has_line_of_walk, has_line_of_walk_footprint, line_of_walk, line_of_walk_footprint, CollisionStrategies, CoordGrid, Normal,
+};
+use rsmod::rsmod::collision::collision::CollisionFlagMap;
+use rsmod::rsmod::collision_flag::CollisionFlag;
+
+const BLOCKERS: [CollisionFlag; 6] = [
+    CollisionFlag::LOC,
+    CollisionFlag::FLOOR,
+    CollisionFlag::WALL_NORTH,
+    CollisionFlag::WALL_EAST,
+    CollisionFlag::WALL_SOUTH,
+    CollisionFlag::WALL_WEST,
+];
+
+unsafe fn build_collision_map(seed: u32) -> CollisionFlagMap {
+    let mut collision = CollisionFlagMap::new();
+    let mut state: u32 = seed;
+    for z in 3190..3215 {
+        for x in 3190..3215 {
+            collision.allocate_if_absent(x, z, 0);
+            state = state.wrapping_mul(1103515245).wrapping_add(12345);
+            if (state >> 16) % 11 == 3 {
+                collision.add(x, z, 0, BLOCKERS[(state >> 8) as usize % BLOCKERS.len()] as u32);
+            }
+        }
+    }
+    return collision;
+}
+
+#[test]
+fn test_single_tile_matches_line_of_walk() {
+    let strategy = CollisionStrategies::Normal(Normal);
+    for seed in 0..4 {
+        unsafe {
+            let collision = build_collision_map(seed);
+            for dest_z in 3194..3211 {
+                for dest_x in 3194..3211 {
+                    assert_eq!(
+                        has_line_of_walk(&collision, 0, 3202, 3202, dest_x, dest_z, 1, 1, 1, 1, 0),
+                        has_line_of_walk_footprint(&collision, 0, 3202, 3202, dest_x, dest_z, 1, 1, 1, 0, &strategy),
+                        "seed {} dest {}, {}", seed, dest_x, dest_z,
+                    );
+                    assert_eq!(
+                        line_of_walk(&collision, 0, 3202, 3202, dest_x, dest_z, 1, 1, 1, 1, 0).unwrap(),
+                        line_of_walk_footprint(&collision, 0, 3202, 3202, dest_x, dest_z, 1, 1, 1, 0, &strategy).unwrap(),
+                    );
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn test_footprint_blocked_by_narrow_gap() {
+    let strategy = CollisionStrategies::Normal(Normal);
+    let mut collision = CollisionFlagMap::new();
+
+    unsafe {
+        // a wall of locs across x = 3205 with a 1 tile gap at z = 3201.
+        for z in 3195..3210 {
+            for x in 3195..3215 {
+                collision.allocate_if_absent(x, z, 0);
+            }
+            if z != 3201 {
+                collision.add(3205, z, 0, CollisionFlag::LOC as u32);
+            }
+        }
+
+        // the line from the 3x3 mover's east column passes through the gap.
+        assert!(has_line_of_walk(&collision, 0, 3200, 3200, 3210, 3201, 3, 3, 1, 1, 0));
+        assert!(!has_line_of_walk_footprint(&collision, 0, 3200, 3200, 3210, 3201, 3, 1, 1, 0, &strategy));
+        assert!(has_line_of_walk_footprint(&collision, 0, 3202, 3201, 3210, 3201, 1, 1, 1, 0, &strategy));
+    }
+}
+
+#[test]
+fn test_footprint_line_anchors() {
+    let strategy = CollisionStrategies::Normal(Normal);
+    let mut collision = CollisionFlagMap::new();
+
+    unsafe {
+        collision.allocate_if_absent(3200, 3200, 0);
+
+        // a 2x2 mover traced from its east column towards a tile 3 east.
+        let line = line_of_walk_footprint(&collision, 0, 3200, 3200, 3204, 3200, 2, 1, 1, 0, &strategy).unwrap();
+        assert_eq!(
+            vec![
+                CoordGrid::new(0, 3201, 3200).packed,
+                CoordGrid::new(0, 3202, 3200).packed,
+                CoordGrid::new(0, 3203, 3200).packed,
+            ],
+            line,
+        );
+    }
+}
@@ -1,4 +1,4 @@
-use rsmod::rsmod::{line_of_sight, line_of_walk};
+use rsmod::rsmod::{line_of_sight, line_of_sight_result, line_of_sight_wide, line_of_walk, line_of_walk_result, line_of_walk_wide, CoordGrid, CoordOutOfRange, RayBlocker, RayResult, WideCoordGrid};
 use rsmod::rsmod::collision::collision::CollisionFlagMap;
 use rsmod::rsmod::collision_flag::CollisionFlag;
 
@@ -26,7 +26,7 @@ fn test_low_partial_line_of_walk() {
     unsafe {
         collision.set(3200, 3205, 0, CollisionFlag::LOC as u32);
 
-        let line = line_of_walk(&collision, 0, src_x, src_z, 3200, 3207, 1, 1, 0, 0, 0);
+        let line = line_of_walk(&collision, 0, src_x, src_z, 3200, 3207, 1, 1, 0, 0, 0).unwrap();
         assert!(!(line.len() > 0));
     }
 }
@@ -44,7 +44,7 @@ fn test_low_clear_line_of_walk() {
             for y in 0..4 {
                 collision.allocate_if_absent(src_x + dir_x, src_z + dir_z, y);
 
-                let line = line_of_walk(&collision, y, src_x, src_z, dest_x, dest_z, 1, 1, 0, 0, 0);
+                let line = line_of_walk(&collision, y, src_x, src_z, dest_x, dest_z, 1, 1, 0, 0, 0).unwrap();
                 assert!(line.len() > 0);
             }
         }
@@ -64,7 +64,7 @@ fn test_low_loc_blocking() {
             for y in 0..4 {
                 collision.set(src_x + dir_x, src_z + dir_z, y, CollisionFlag::LOC as u32);
 
-                let line = line_of_walk(&collision, y, src_x, src_z, dest_x, dest_z, 1, 1, 0, 0, 0);
+                let line = line_of_walk(&collision, y, src_x, src_z, dest_x, dest_z, 1, 1, 0, 0, 0).unwrap();
                 assert!(!(line.len() > 0));
             }
         }
@@ -93,7 +93,7 @@ fn test_low_extra_flag_blocking() {
 
                     let line = line_of_walk(
                         &collision, y, src_x, src_z, dest_x, dest_z, 1, 1, 0, 0, flag,
-                    );
+                    ).unwrap();
                     assert!(!(line.len() > 0));
                 }
             }
@@ -123,7 +123,7 @@ fn test_los_with_extra_flag_on_target_coords() {
             1,
             1,
             CollisionFlag::PLAYER as u32,
-        );
+        ).unwrap();
         assert_eq!(line.len(), 2);
         assert_eq!(line[0] & 0x3fff, 3201);
         assert_eq!((line[0] >> 14) & 0x3fff, 3200);
@@ -154,7 +154,7 @@ fn test_los_with_extra_flag_past_target_coords() {
             1,
             1,
             CollisionFlag::PLAYER as u32,
-        );
+        ).unwrap();
         assert!(!(line.len() > 0));
     }
 }
@@ -169,7 +169,7 @@ fn test_los_on_top_of_loc_fails_line_of_sight() {
     unsafe {
         collision.add(src_x, src_z, 0, CollisionFlag::LOC as u32);
 
-        let line = line_of_sight(&collision, 0, src_x, src_z, 3200, 3201, 1, 1, 0, 0, 0);
+        let line = line_of_sight(&collision, 0, src_x, src_z, 3200, 3201, 1, 1, 0, 0, 0).unwrap();
         assert!(!(line.len() > 0));
     }
 }
@@ -196,7 +196,7 @@ fn test_los_on_top_of_extra_flag_fails_line_of_sight() {
             0,
             0,
             CollisionFlag::PLAYER as u32,
-        );
+        ).unwrap();
         assert!(!(line.len() > 0));
     }
 }
@@ -211,7 +211,7 @@ fn test_los_on_top_of_extra_flag_fails_line_of_sight() {
 //     unsafe {
 //         collision.allocate_if_absent(src_x, src_z, 0);
 //
-//         let line = line_of_sight(&collision, 0, src_x, src_z, src_x, src_z, 1, 1, 0, 0, 0);
+//         let line = line_of_sight(&collision, 0, src_x, src_z, src_x, src_z, 1, 1, 0, 0, 0).unwrap();
 //         assert!(line.len() > 0);
 //     }
 // }
@@ -226,7 +226,7 @@ fn test_los_partial_line_of_sight() {
     unsafe {
         collision.set(3200, 3205, 0, CollisionFlag::LOC_PROJ_BLOCKER as u32);
 
-        let line = line_of_sight(&collision, 0, src_x, src_z, 3200, 3207, 1, 1, 0, 0, 0);
+        let line = line_of_sight(&collision, 0, src_x, src_z, 3200, 3207, 1, 1, 0, 0, 0).unwrap();
         assert!(!(line.len() > 0));
     }
 }
@@ -253,7 +253,7 @@ fn test_los_valid_line_of_sight() {
                     collision.set(src_x + dir_x, src_z + dir_z, y, flag);
                 }
                 let line =
-                    line_of_sight(&collision, y, src_x, src_z, dest_x, dest_z, 1, 1, 0, 0, 0);
+                    line_of_sight(&collision, y, src_x, src_z, dest_x, dest_z, 1, 1, 0, 0, 0).unwrap();
                 assert!(line.len() > 0);
             }
         }
@@ -278,7 +278,7 @@ fn test_los_loc_blocking() {
                     CollisionFlag::LOC_PROJ_BLOCKER as u32,
                 );
                 let line =
-                    line_of_sight(&collision, y, src_x, src_z, dest_x, dest_z, 1, 1, 0, 0, 0);
+                    line_of_sight(&collision, y, src_x, src_z, dest_x, dest_z, 1, 1, 0, 0, 0).unwrap();
                 assert!(!(line.len() > 0));
             }
         }
@@ -307,10 +307,123 @@ fn test_los_extra_flag_blocking() {
 
                     let line = line_of_sight(
                         &collision, y, src_x, src_z, dest_x, dest_z, 1, 1, 0, 0, flag,
-                    );
+                    ).unwrap();
                     assert!(!(line.len() > 0));
                 }
             }
         }
     }
 }
+
+#[test]
+fn test_los_result_stops_at_wall() {
+    let mut collision = CollisionFlagMap::new();
+
+    unsafe {
+        collision.set(3203, 3200, 0, CollisionFlag::WALL_WEST_PROJ_BLOCKER as u32);
+
+        let result = line_of_sight_result(&collision, 0, 3200, 3200, 3206, 3200, 1, 1, 0, 0, 0).unwrap();
+        assert_eq!(vec![CoordGrid::new(0, 3201, 3200).packed, CoordGrid::new(0, 3202, 3200).packed], result.tiles);
+        let block = result.blocked.unwrap();
+        assert_eq!((3203, 3200, 0), (block.x, block.z, block.y));
+        assert_eq!(CollisionFlag::WALL_WEST_PROJ_BLOCKER as u32, block.flags);
+        assert_eq!(RayBlocker::WALL_WEST, block.blocker);
+        assert_eq!(
+            vec![
+                RayBlocker::WALL_WEST as i32,
+                3203,
+                3200,
+                0,
+                CollisionFlag::WALL_WEST_PROJ_BLOCKER as i32,
+                CoordGrid::new(0, 3201, 3200).packed as i32,
+                CoordGrid::new(0, 3202, 3200).packed as i32,
+            ],
+            result.flatten()
+        );
+    }
+}
+
+#[test]
+fn test_los_result_on_top_of_loc() {
+    let mut collision = CollisionFlagMap::new();
+
+    unsafe {
+        collision.add(3200, 3200, 0, CollisionFlag::LOC as u32);
+
+        let result = line_of_sight_result(&collision, 0, 3200, 3200, 3200, 3203, 1, 1, 0, 0, 0).unwrap();
+        assert!(result.tiles.is_empty());
+        assert_eq!(RayBlocker::LOC, result.blocked.unwrap().blocker);
+    }
+}
+
+#[test]
+fn test_line_result_blockers() {
+    let mut collision = CollisionFlagMap::new();
+
+    unsafe {
+        collision.set(3200, 3202, 0, CollisionFlag::PLAYER as u32);
+
+        let result = line_of_sight_result(&collision, 0, 3200, 3200, 3200, 3205, 1, 1, 0, 0, CollisionFlag::PLAYER as u32).unwrap();
+        assert_eq!(vec![CoordGrid::new(0, 3200, 3201).packed], result.tiles);
+        assert_eq!(RayBlocker::EXTRA_FLAG, result.blocked.unwrap().blocker);
+
+        collision.set(3200, 3202, 0, CollisionFlag::FLOOR as u32);
+        let result = line_of_walk_result(&collision, 0, 3200, 3200, 3200, 3205, 1, 1, 0, 0, 0).unwrap();
+        assert_eq!(RayBlocker::FLOOR, result.blocked.unwrap().blocker);
+
+        collision.set(3200, 3202, 0, CollisionFlag::OPEN as u32);
+        let result = line_of_walk_result(&collision, 0, 3200, 3200, 3200, 3205, 1, 1, 0, 0, 0).unwrap();
+        assert!(result.is_clear());
+        assert_eq!(vec![-1, 0, 0, 0, 0], result.flatten()[..RayResult::HEADER]);
+        assert_eq!(line_of_walk(&collision, 0, 3200, 3200, 3200, 3205, 1, 1, 0, 0, 0).unwrap(), result.tiles);
+    }
+}
+
+#[test]
+fn test_line_past_coord_grid_range() {
+    let mut collision = CollisionFlagMap::sparse();
+    unsafe {
+        for x in 20000..20008 {
+            collision.allocate_if_absent(x, 20001, 0);
+        }
+        assert_eq!(
+            Err(CoordOutOfRange { x: 20002, z: 20001, y: 0 }),
+            line_of_sight(&collision, 0, 20001, 20001, 20004, 20001, 1, 1, 1, 1, 0)
+        );
+        assert_eq!(
+            Err(CoordOutOfRange { x: 20002, z: 20001, y: 0 }),
+            line_of_walk(&collision, 0, 20001, 20001, 20004, 20001, 1, 1, 1, 1, 0)
+        );
+    }
+}
+
+#[test]
+fn test_line_wide_past_fixed_point_range() {
+    let mut dense = CollisionFlagMap::new();
+    let mut sparse = CollisionFlagMap::sparse();
+    unsafe {
+        for x in 0..8 {
+            for z in 0..8 {
+                dense.allocate_if_absent(1000 + x, 1000 + z, 0);
+                sparse.allocate_if_absent(40000 + x, 40000 + z, 0);
+            }
+        }
+        // past 32767 the tiles no longer fit the 16.16 fixed point of an i32, the line must still match the dense one.
+        let expected: Vec<u64> = line_of_sight(&dense, 0, 1000, 1000, 1006, 1003, 1, 1, 1, 1, 0)
+            .unwrap()
+            .iter()
+            .map(|&packed| {
+                let tile = CoordGrid::from(packed);
+                WideCoordGrid::try_new(0, 39000 + tile.x() as i32, 39000 + tile.z() as i32).unwrap().packed
+            })
+            .collect();
+        assert_eq!(6 + 3, expected.len());
+        assert_eq!(expected, line_of_sight_wide(&sparse, 0, 40000, 40000, 40006, 40003, 1, 1, 1, 1, 0).unwrap().into_line());
+        assert_eq!(expected, line_of_walk_wide(&sparse, 0, 40000, 40000, 40006, 40003, 1, 1, 1, 1, 0).unwrap().into_line());
+
+        sparse.add(40003, 40001, 0, CollisionFlag::LOC_PROJ_BLOCKER as u32);
+        let ray: RayResult<u64> = line_of_sight_wide(&sparse, 0, 40000, 40000, 40006, 40003, 1, 1, 1, 1, 0).unwrap();
+        assert_eq!(RayBlocker::LOC_PROJ_BLOCKER, ray.blocked.unwrap().blocker);
+        assert_eq!((40003, 40001), (ray.blocked.unwrap().x, ray.blocked.unwrap().z));
+    }
+}
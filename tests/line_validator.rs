@@ -312,3 +312,22 @@ fn test_has_los_extra_flag_blocking() {
         }
     }
 }
+
+#[test]
+fn test_line_of_sight_past_fixed_point_range() {
+    let mut collision = CollisionFlagMap::sparse();
+    unsafe {
+        for x in 40000..40008 {
+            for z in 40000..40004 {
+                collision.allocate_if_absent(x, z, 0);
+            }
+        }
+        assert!(has_line_of_sight(&collision, 0, 40000, 40000, 40006, 40003, 1, 1, 1, 1, 0));
+        assert!(has_line_of_walk(&collision, 0, 40000, 40000, 40006, 40003, 1, 1, 1, 1, 0));
+
+        // the tile on the line, not the one 65536 tiles south an i32 fixed point would wrap onto.
+        collision.add(40003, 40001, 0, CollisionFlag::LOC_PROJ_BLOCKER as u32 | CollisionFlag::LOC as u32);
+        assert!(!has_line_of_sight(&collision, 0, 40000, 40000, 40006, 40003, 1, 1, 1, 1, 0));
+        assert!(!has_line_of_walk(&collision, 0, 40000, 40000, 40006, 40003, 1, 1, 1, 1, 0));
+    }
+}
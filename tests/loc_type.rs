@@ -0,0 +1,119 @@
+use rsmod::rsmod::{LocShape, LocType, LocTypeRegistry};
+use rsmod::rsmod::block_flag::BlockAccessFlag;
+use rsmod::rsmod::collision::collision::CollisionFlagMap;
+use rsmod::rsmod::collision_flag::CollisionFlag;
+use rsmod::rsmod::reach_strategy::ReachStrategy;
+
+#[test]
+fn test_add_loc_rotates_footprint() {
+    let mut registry = LocTypeRegistry::new();
+    registry.register(1, LocType::new(2, 1, true, false, false, 0, true));
+
+    for angle in 0..4 {
+        let mut collision = CollisionFlagMap::new();
+        unsafe {
+            assert!(registry.add_loc(&mut collision, 1, 3200, 3200, 0, LocShape::CENTREPIECE_STRAIGHT, angle).unwrap());
+            let rotated: bool = (angle & 0x1) != 0;
+            assert_eq!(CollisionFlag::LOC as u32, collision.get(3200, 3200, 0));
+            assert_eq!(!rotated, collision.is_flagged(3201, 3200, 0, CollisionFlag::LOC as u32));
+            assert_eq!(rotated, collision.is_flagged(3200, 3201, 0, CollisionFlag::LOC as u32));
+        }
+    }
+}
+
+#[test]
+fn test_remove_loc_clears_footprint() {
+    let mut registry = LocTypeRegistry::new();
+    registry.register(1, LocType::new(3, 2, true, true, true, 0, true));
+
+    let mut collision = CollisionFlagMap::new();
+    unsafe {
+        registry.add_loc(&mut collision, 1, 3200, 3200, 0, LocShape::CENTREPIECE_STRAIGHT, 1).unwrap();
+        assert_eq!(
+            CollisionFlag::LOC as u32
+                | CollisionFlag::LOC_PROJ_BLOCKER as u32
+                | CollisionFlag::LOC_ROUTE_BLOCKER as u32,
+            collision.get(3201, 3202, 0)
+        );
+        registry.remove_loc(&mut collision, 1, 3200, 3200, 0, LocShape::CENTREPIECE_STRAIGHT, 1).unwrap();
+        for x in 3200..3208 {
+            for z in 3200..3208 {
+                assert_eq!(CollisionFlag::OPEN as u32, collision.get(x, z, 0));
+            }
+        }
+    }
+}
+
+#[test]
+fn test_add_loc_unknown_id() {
+    let mut registry = LocTypeRegistry::new();
+    registry.register(5, LocType::new(1, 1, true, false, false, 0, true));
+    registry.unregister(5);
+
+    let mut collision = CollisionFlagMap::new();
+    unsafe {
+        assert!(!registry.add_loc(&mut collision, 5, 3200, 3200, 0, LocShape::WALL_STRAIGHT, 0).unwrap());
+        assert!(!registry.add_loc(&mut collision, 100, 3200, 3200, 0, LocShape::WALL_STRAIGHT, 0).unwrap());
+        assert_eq!(false, collision.is_zone_allocated(3200, 3200, 0));
+    }
+}
+
+#[test]
+fn test_add_loc_without_blockwalk() {
+    let mut registry = LocTypeRegistry::new();
+    registry.register(1, LocType::new(1, 1, false, true, false, 0, true));
+
+    let mut collision = CollisionFlagMap::new();
+    unsafe {
+        assert!(registry.add_loc(&mut collision, 1, 3200, 3200, 0, LocShape::WALL_STRAIGHT, 0).unwrap());
+        assert_eq!(false, collision.is_zone_allocated(3200, 3200, 0));
+    }
+}
+
+#[test]
+fn test_add_loc_ground_decor_requires_active() {
+    let mut registry = LocTypeRegistry::new();
+    registry.register(1, LocType::new(1, 1, true, false, false, 0, false));
+    registry.register(2, LocType::new(1, 1, true, false, false, 0, true));
+
+    let mut collision = CollisionFlagMap::new();
+    unsafe {
+        registry.add_loc(&mut collision, 1, 3200, 3200, 0, LocShape::GROUND_DECOR, 0).unwrap();
+        assert_eq!(CollisionFlag::NULL as u32, collision.get(3200, 3200, 0));
+        registry.add_loc(&mut collision, 2, 3200, 3200, 0, LocShape::GROUND_DECOR, 0).unwrap();
+        assert_eq!(CollisionFlag::FLOOR_DECORATION as u32, collision.get(3200, 3200, 0));
+    }
+}
+
+#[test]
+fn test_block_access_flags_reach() {
+    let mut registry = LocTypeRegistry::new();
+    registry.register(1, LocType::new(1, 1, true, false, false, BlockAccessFlag::BLOCK_SOUTH as u8, true));
+    let loc: LocType = *registry.get(1).unwrap();
+    assert_eq!(BlockAccessFlag::BLOCK_WEST as u8, loc.rotated_block_access_flags(1));
+
+    let mut collision = CollisionFlagMap::new();
+    unsafe {
+        registry.add_loc(&mut collision, 1, 3200, 3200, 0, LocShape::CENTREPIECE_STRAIGHT, 0).unwrap();
+        let reached_south: bool = ReachStrategy::reached(
+            &collision, 0, 3200, 3199, 3200, 3200, loc.width, loc.length, 1, 0, 10, loc.block_access_flags(),
+        );
+        let reached_north: bool = ReachStrategy::reached(
+            &collision, 0, 3200, 3201, 3200, 3200, loc.width, loc.length, 1, 0, 10, loc.block_access_flags(),
+        );
+        assert!(!reached_south);
+        assert!(reached_north);
+    }
+}
+
+#[test]
+fn test_register_rejects_huge_ids() {
+    let mut registry = LocTypeRegistry::new();
+    let loc: LocType = LocType::new(1, 1, true, false, false, 0, true);
+    assert!(registry.register(LocTypeRegistry::ID_LIMIT - 1, loc));
+    // a negative jint cast to u32 would otherwise resize the table to billions of slots.
+    assert!(!registry.register(-1i32 as u32, loc));
+    assert!(!registry.register(LocTypeRegistry::ID_LIMIT, loc));
+    assert_eq!(None, registry.get(LocTypeRegistry::ID_LIMIT));
+    assert_eq!(Some(&loc), registry.get(LocTypeRegistry::ID_LIMIT - 1));
+}
@@ -0,0 +1,145 @@
+use rsmod::rsmod::{simulate_chase, ChaseState, CollisionStrategies, CoordGrid, MoveSpeed, Normal, PathRandom, SeededRandom};
+use rsmod::rsmod::collision::collision::CollisionFlagMap;
+use rsmod::rsmod::collision_flag::CollisionFlag;
+
+// always picks the same direction, West being 0.
+struct FixedRandom(u32);
+
+impl PathRandom for FixedRandom {
+    fn next_int(&mut self, _: u32) -> u32 {
+        return self.0;
+    }
+}
+
+unsafe fn build_collision_map() -> CollisionFlagMap {
+    let mut collision = CollisionFlagMap::new();
+    for z in 3190..3215 {
+        for x in 3190..3215 {
+            collision.allocate_if_absent(x, z, 0);
+        }
+    }
+    return collision;
+}
+
+#[test]
+fn test_chase_walk_and_run() {
+    let strategy = CollisionStrategies::Normal(Normal);
+    let targets = [CoordGrid::new(0, 3205, 3203).packed];
+    unsafe {
+        let collision = build_collision_map();
+        let walk = simulate_chase(
+            &collision, 0, 3200, 3200, 1, 1, &targets, 1, 1, 6, MoveSpeed::WALK, 0, &strategy, &mut SeededRandom::new(0),
+        );
+        assert_eq!(
+            vec![
+                CoordGrid::new(0, 3201, 3201).packed,
+                CoordGrid::new(0, 3202, 3202).packed,
+                CoordGrid::new(0, 3203, 3203).packed,
+                CoordGrid::new(0, 3204, 3203).packed,
+                CoordGrid::new(0, 3204, 3203).packed,
+                CoordGrid::new(0, 3204, 3203).packed,
+            ],
+            walk.positions().unwrap()
+        );
+        assert_eq!(Some(3), walk.arrived_tick());
+        assert!(!walk.is_safe_spotted());
+
+        let run = simulate_chase(
+            &collision, 0, 3200, 3200, 1, 1, &targets, 1, 1, 3, MoveSpeed::RUN, 0, &strategy, &mut SeededRandom::new(0),
+        );
+        assert_eq!(
+            vec![
+                CoordGrid::new(0, 3202, 3202).packed,
+                CoordGrid::new(0, 3204, 3203).packed,
+                CoordGrid::new(0, 3204, 3203).packed,
+            ],
+            run.positions().unwrap()
+        );
+        assert_eq!(Some(1), run.arrived_tick());
+    }
+}
+
+#[test]
+fn test_chase_follows_moving_target() {
+    let strategy = CollisionStrategies::Normal(Normal);
+    let targets = [
+        CoordGrid::new(0, 3203, 3200).packed,
+        CoordGrid::new(0, 3204, 3200).packed,
+        CoordGrid::new(0, 3205, 3200).packed,
+    ];
+    unsafe {
+        let collision = build_collision_map();
+        let chase = simulate_chase(
+            &collision, 0, 3200, 3200, 1, 1, &targets, 1, 1, 5, MoveSpeed::WALK, 0, &strategy, &mut SeededRandom::new(0),
+        );
+        // the target moves away as fast as the mover walks until it stops.
+        let xs: Vec<i32> = chase.ticks.iter().map(|tick| tick.x).collect();
+        assert_eq!(vec![3201, 3202, 3203, 3204, 3204], xs);
+        assert_eq!(Some(3), chase.arrived_tick());
+    }
+}
+
+#[test]
+fn test_chase_safe_spotted() {
+    let strategy = CollisionStrategies::Normal(Normal);
+    // the target stands behind a row of locs that the mover walks straight into.
+    let targets = [CoordGrid::new(0, 3200, 3206).packed];
+    unsafe {
+        let mut collision = build_collision_map();
+        for x in 3198..3203 {
+            collision.add(x, 3203, 0, CollisionFlag::LOC as u32);
+        }
+        let chase = simulate_chase(
+            &collision, 0, 3200, 3200, 1, 1, &targets, 1, 1, 5, MoveSpeed::WALK, 0, &strategy, &mut SeededRandom::new(0),
+        );
+        assert_eq!(
+            vec![ChaseState::MOVED, ChaseState::MOVED, ChaseState::STUCK, ChaseState::STUCK, ChaseState::STUCK],
+            chase.ticks.iter().map(|tick| tick.state).collect::<Vec<ChaseState>>()
+        );
+        assert_eq!(CoordGrid::new(0, 3200, 3202).packed, *chase.positions().unwrap().last().unwrap());
+        assert!(chase.is_safe_spotted());
+        assert_eq!(Some(2), chase.stuck_since());
+        assert_eq!(None, chase.arrived_tick());
+    }
+}
+
+#[test]
+fn test_chase_steps_out_from_under() {
+    let strategy = CollisionStrategies::Normal(Normal);
+    let targets = [CoordGrid::new(0, 3200, 3200).packed];
+    unsafe {
+        let collision = build_collision_map();
+        let chase = simulate_chase(
+            &collision, 0, 3200, 3200, 1, 1, &targets, 1, 1, 2, MoveSpeed::WALK, 0, &strategy, &mut SeededRandom::new(7),
+        );
+        // one step out to the side of the target is already next to it.
+        assert_eq!(ChaseState::ARRIVED, chase.ticks[0].state);
+        assert_eq!(1, (chase.ticks[0].x - 3200).abs() + (chase.ticks[0].z - 3200).abs());
+        assert_eq!(chase.ticks[0], chase.ticks[1]);
+    }
+}
+
+#[test]
+fn test_chase_steps_out_from_under_past_blocked_directions() {
+    let strategy = CollisionStrategies::Normal(Normal);
+    let targets = [CoordGrid::new(0, 3200, 3200).packed];
+    unsafe {
+        let mut collision = build_collision_map();
+        // only the tile to the south is open, the random direction is always west.
+        collision.add(3199, 3200, 0, CollisionFlag::LOC as u32);
+        collision.add(3201, 3200, 0, CollisionFlag::LOC as u32);
+        collision.add(3200, 3201, 0, CollisionFlag::LOC as u32);
+        let chase = simulate_chase(
+            &collision, 0, 3200, 3200, 1, 1, &targets, 1, 1, 1, MoveSpeed::WALK, 0, &strategy, &mut FixedRandom(0),
+        );
+        assert_eq!(vec![CoordGrid::new(0, 3200, 3199).packed], chase.positions().unwrap());
+        assert_eq!(ChaseState::ARRIVED, chase.ticks[0].state);
+
+        collision.add(3200, 3199, 0, CollisionFlag::LOC as u32);
+        let chase = simulate_chase(
+            &collision, 0, 3200, 3200, 1, 1, &targets, 1, 1, 1, MoveSpeed::WALK, 0, &strategy, &mut FixedRandom(0),
+        );
+        assert_eq!(ChaseState::STUCK, chase.ticks[0].state);
+        assert!(chase.is_safe_spotted());
+    }
+}
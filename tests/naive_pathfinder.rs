@@ -0,0 +1,156 @@
+use rsmod::rsmod::{find_naive_path, find_naive_path_with, CollisionStrategies, CoordGrid, Normal, PathRandom, SeededRandom};
+use rsmod::rsmod::collision::collision::CollisionFlagMap;
+use rsmod::rsmod::collision_flag::CollisionFlag;
+
+struct FixedRandom(u32);
+
+impl PathRandom for FixedRandom {
+    fn next_int(&mut self, bound: u32) -> u32 {
+        return self.0 % bound;
+    }
+}
+
+unsafe fn build_collision_map() -> CollisionFlagMap {
+    let mut collision = CollisionFlagMap::new();
+    for z in 3190..3215 {
+        for x in 3190..3215 {
+            collision.allocate_if_absent(x, z, 0);
+        }
+    }
+    return collision;
+}
+
+#[test]
+fn test_fixed_random_picks_direction() {
+    let strategy = CollisionStrategies::Normal(Normal);
+    unsafe {
+        let collision = build_collision_map();
+        let expected: [(i32, i32); 4] = [(3199, 3200), (3201, 3200), (3200, 3201), (3200, 3199)];
+        for (index, (x, z)) in expected.iter().enumerate() {
+            let path = find_naive_path_with(
+                &collision, 0, 3200, 3200, 3200, 3200, 1, 1, 1, 1, 0, &strategy, &mut FixedRandom(index as u32),
+            ).unwrap();
+            assert_eq!(vec![CoordGrid::new(0, *x, *z).packed], path);
+        }
+    }
+}
+
+#[test]
+fn test_same_seed_same_path() {
+    let strategy = CollisionStrategies::Normal(Normal);
+    unsafe {
+        let collision = build_collision_map();
+        for seed in 0..32 {
+            let first = find_naive_path_with(
+                &collision, 0, 3200, 3200, 3200, 3200, 1, 1, 1, 1, 0, &strategy, &mut SeededRandom::new(seed),
+            ).unwrap();
+            let second = find_naive_path_with(
+                &collision, 0, 3200, 3200, 3200, 3200, 1, 1, 1, 1, 0, &strategy, &mut SeededRandom::new(seed),
+            ).unwrap();
+            assert_eq!(first, second);
+        }
+    }
+}
+
+#[test]
+fn test_seeded_random_bounds() {
+    let mut random = SeededRandom::new(42);
+    let mut seen: [bool; 4] = [false; 4];
+    for _ in 0..1000 {
+        let value = random.next_int(4);
+        assert!(value < 4);
+        seen[value as usize] = true;
+    }
+    assert_eq!([true; 4], seen);
+}
+
+#[test]
+fn test_naive_path_approach() {
+    let strategy = CollisionStrategies::Normal(Normal);
+    // src x, src z, src width, src height, dest x, dest z, dest width, dest height, expected x, expected z.
+    let cases: [[i32; 10]; 5] = [
+        // diagonally, then east into the west side.
+        [3200, 3200, 1, 1, 3205, 3203, 1, 1, 3204, 3203],
+        // diagonally, then north into the south side rather than stopping once the x lines up.
+        [3200, 3200, 1, 1, 3203, 3206, 1, 1, 3203, 3205],
+        // diagonally past the corner, then west into the north side.
+        [3202, 3202, 1, 1, 3200, 3200, 1, 1, 3200, 3201],
+        // against the east side of a 2x2 target.
+        [3210, 3201, 1, 1, 3200, 3200, 2, 2, 3202, 3200],
+        // a 2x1 mover under a 1x1 target.
+        [3200, 3200, 2, 1, 3200, 3205, 1, 1, 3200, 3204],
+    ];
+    unsafe {
+        let collision = build_collision_map();
+        for [src_x, src_z, src_width, src_height, dest_x, dest_z, dest_width, dest_height, x, z] in cases {
+            let path = find_naive_path(
+                &collision,
+                0,
+                src_x,
+                src_z,
+                dest_x,
+                dest_z,
+                src_width as u8,
+                src_height as u8,
+                dest_width as u8,
+                dest_height as u8,
+                0,
+                &strategy,
+            ).unwrap();
+            assert_eq!(vec![CoordGrid::new(0, x, z).packed], path, "{src_x}, {src_z} to {dest_x}, {dest_z}");
+        }
+    }
+}
+
+#[test]
+fn test_naive_path_reference_cases() {
+    let strategy = CollisionStrategies::Normal(Normal);
+    // traced by hand through rsmod's Kotlin naiveDestination, which naive_destination is a port of, on an
+    // open map where the walk ends on that destination. There is no rsmod build to run these against here.
+    // src x, src z, src width, src height, dest x, dest z, dest width, dest height, expected x, expected z.
+    let cases: [[i32; 10]; 4] = [
+        // north-west of a 2x2 target: diagonal 5, anti -15, the north side at its west column.
+        [3195, 3210, 1, 1, 3200, 3200, 2, 2, 3200, 3202],
+        // a 3x3 mover west of the target: diagonal -11, anti -9, the west side level with the target.
+        [3190, 3199, 3, 3, 3200, 3200, 1, 1, 3197, 3200],
+        // a 1x2 mover south-east of a 2x1 target: diagonal 5, anti 15, the east side.
+        [3210, 3195, 1, 2, 3200, 3200, 2, 1, 3202, 3200],
+        // south-east of a 2x2 target: diagonal -7, anti 13, the south side at its west column.
+        [3203, 3190, 1, 1, 3200, 3200, 2, 2, 3200, 3199],
+    ];
+    unsafe {
+        let collision = build_collision_map();
+        for [src_x, src_z, src_width, src_height, dest_x, dest_z, dest_width, dest_height, x, z] in cases {
+            let path = find_naive_path(
+                &collision,
+                0,
+                src_x,
+                src_z,
+                dest_x,
+                dest_z,
+                src_width as u8,
+                src_height as u8,
+                dest_width as u8,
+                dest_height as u8,
+                0,
+                &strategy,
+            ).unwrap();
+            assert_eq!(vec![CoordGrid::new(0, x, z).packed], path, "{src_x}, {src_z} to {dest_x}, {dest_z}");
+        }
+    }
+}
+
+#[test]
+fn test_naive_path_rectangular_mover_blocked() {
+    let strategy = CollisionStrategies::Normal(Normal);
+    unsafe {
+        let mut collision = build_collision_map();
+        collision.add(3201, 3203, 0, CollisionFlag::LOC as u32);
+        // a 2 wide mover runs into the loc on its east column.
+        let path = find_naive_path(&collision, 0, 3200, 3200, 3200, 3205, 2, 1, 1, 1, 0, &strategy).unwrap();
+        assert_eq!(vec![CoordGrid::new(0, 3200, 3202).packed], path);
+        // a 1 wide, 2 tall mover walks past it.
+        let path = find_naive_path(&collision, 0, 3200, 3200, 3200, 3206, 1, 2, 1, 1, 0, &strategy).unwrap();
+        assert_eq!(vec![CoordGrid::new(0, 3200, 3204).packed], path);
+    }
+}
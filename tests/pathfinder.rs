@@ -1,4 +1,4 @@
-use rsmod::rsmod::{CollisionStrategies, Normal, PathFinder};
+use rsmod::rsmod::{has_line_of_sight, CollisionStrategies, CoordGrid, CoordOutOfRange, Normal, PathFinder, SeededRandom, WideCoordGrid};
 use rsmod::rsmod::collision::collision::CollisionFlagMap;
 use rsmod::rsmod::collision_flag::CollisionFlag;
 
@@ -59,7 +59,7 @@ fn test_pf_coord_matches_level_input() {
             0,
             25,
             &CollisionStrategies::Normal(Normal),
-        );
+        ).unwrap();
         assert!(route.len() > 0);
         for index in 0..route.len() {
             assert_eq!(0, (route[index] >> 28) & 0x3);
@@ -81,7 +81,7 @@ fn test_pf_coord_matches_level_input() {
             0,
             25,
             &CollisionStrategies::Normal(Normal),
-        );
+        ).unwrap();
         assert!(route.len() > 0);
         for index in 0..route.len() {
             assert_eq!(1, (route[index] >> 28) & 0x3);
@@ -103,7 +103,7 @@ fn test_pf_coord_matches_level_input() {
             0,
             25,
             &CollisionStrategies::Normal(Normal),
-        );
+        ).unwrap();
         assert!(route.len() > 0);
         for index in 0..route.len() {
             assert_eq!(2, (route[index] >> 28) & 0x3);
@@ -125,7 +125,7 @@ fn test_pf_coord_matches_level_input() {
             0,
             25,
             &CollisionStrategies::Normal(Normal),
-        );
+        ).unwrap();
         assert!(route.len() > 0);
         for index in 0..route.len() {
             assert_eq!(3, (route[index] >> 28) & 0x3);
@@ -171,7 +171,7 @@ fn test_pf_surrounded_by_locs_allow_move_near() {
             0,
             25,
             &CollisionStrategies::Normal(Normal),
-        );
+        ).unwrap();
         // expect(route.alternative).toBeTruthy();
         assert_eq!(route.len(), 0);
     }
@@ -215,7 +215,7 @@ fn test_pf_surrounded_by_locs_no_move_near() {
             0,
             25,
             &CollisionStrategies::Normal(Normal),
-        );
+        ).unwrap();
         // expect(route.failed).toBeTruthy();
         assert_eq!(route.len(), 0);
     }
@@ -260,7 +260,7 @@ fn test_pf_single_exit_point() {
             0,
             25,
             &CollisionStrategies::Normal(Normal),
-        );
+        ).unwrap();
         // expect(route.success).toBeTruthy();
         assert_eq!(route.len(), 4);
 
@@ -316,7 +316,7 @@ fn test_pf_standing_on_closest_approach_point() {
             0,
             25,
             &CollisionStrategies::Normal(Normal),
-        );
+        ).unwrap();
 
         // expect(route.success).toBeTruthy();
         // expect(route.alternative).toBeTruthy();
@@ -355,10 +355,181 @@ fn test_pf_find_path_any_size() {
                 0,
                 25,
                 &CollisionStrategies::Normal(Normal),
-            );
+            ).unwrap();
 
             assert!(route.len() > 0);
             // expect(route.alternative).toBeFalsy();
         }
     }
 }
+
+#[test]
+fn test_pf_flee_away_from_threat() {
+    let mut pf = PathFinder::new();
+    unsafe {
+        let collision = build_collision_map(3180, 3180, 3220, 3220);
+        let threats = [CoordGrid::new(0, 3198, 3200).packed];
+        let route = pf.find_flee_path(
+            &collision, 0, 3200, 3200, 1, &threats, 5, false, 25, &CollisionStrategies::Normal(Normal),
+        ).unwrap();
+        // as far east as 5 steps go, nothing further from the threat being reachable.
+        let last = CoordGrid::from(*route.last().unwrap());
+        assert_eq!(3205, last.x());
+        assert!((last.z() as i32 - 3200).abs() <= 5);
+
+        // nowhere to run with nothing to run from.
+        assert!(pf
+            .find_flee_path(&collision, 0, 3200, 3200, 1, &[], 5, false, 25, &CollisionStrategies::Normal(Normal)).unwrap()
+            .is_empty());
+    }
+}
+
+#[test]
+fn test_pf_flee_boxed_in() {
+    let mut pf = PathFinder::new();
+    unsafe {
+        let mut collision = build_collision_map(3180, 3180, 3220, 3220);
+        flag(&mut collision, 3199, 3199, 3, 3, CollisionFlag::LOC);
+        flag(&mut collision, 3200, 3200, 1, 1, CollisionFlag::OPEN);
+        let threats = [CoordGrid::new(0, 3198, 3200).packed];
+        assert!(pf
+            .find_flee_path(&collision, 0, 3200, 3200, 1, &threats, 5, false, 25, &CollisionStrategies::Normal(Normal)).unwrap()
+            .is_empty());
+    }
+}
+
+#[test]
+fn test_pf_flee_out_of_sight() {
+    let mut pf = PathFinder::new();
+    unsafe {
+        let mut collision = build_collision_map(3180, 3180, 3220, 3220);
+        for x in 3198..3203 {
+            collision.set(x, 3203, 0, CollisionFlag::LOC as u32 | CollisionFlag::LOC_PROJ_BLOCKER as u32);
+        }
+        let threat_x = 3200;
+        let threat_z = 3195;
+        let threats = [CoordGrid::new(0, threat_x, threat_z).packed];
+
+        let route = pf.find_flee_path(
+            &collision, 0, 3200, 3200, 1, &threats, 8, true, 25, &CollisionStrategies::Normal(Normal),
+        ).unwrap();
+        let last = CoordGrid::from(*route.last().unwrap());
+        assert!(!has_line_of_sight(&collision, 0, threat_x, threat_z, last.x() as i32, last.z() as i32, 1, 1, 1, 1, 0));
+
+        let route = pf.find_flee_path(
+            &collision, 0, 3200, 3200, 1, &threats, 8, false, 25, &CollisionStrategies::Normal(Normal),
+        ).unwrap();
+        let last = CoordGrid::from(*route.last().unwrap());
+        assert!(has_line_of_sight(&collision, 0, threat_x, threat_z, last.x() as i32, last.z() as i32, 1, 1, 1, 1, 0));
+    }
+}
+
+#[test]
+fn test_pf_wander_within_radius_and_steps() {
+    let mut pf = PathFinder::new();
+    unsafe {
+        let mut collision = build_collision_map(3180, 3180, 3220, 3220);
+        // a loc the wanderer can't stand on, right next to the spawn.
+        flag(&mut collision, 3201, 3200, 1, 1, CollisionFlag::LOC);
+        for seed in 0..64 {
+            let destination = pf
+                .find_wander_destination(
+                    &collision,
+                    0,
+                    3202,
+                    3202,
+                    3200,
+                    3200,
+                    1,
+                    3,
+                    4,
+                    &CollisionStrategies::Normal(Normal),
+                    &mut SeededRandom::new(seed),
+                ).unwrap()
+                .unwrap();
+            let destination = CoordGrid::from(destination);
+            let x = destination.x() as i32;
+            let z = destination.z() as i32;
+            assert!((x - 3200).abs() <= 3 && (z - 3200).abs() <= 3);
+            assert!((x - 3202).abs() <= 4 && (z - 3202).abs() <= 4);
+            assert_ne!((3202, 3202), (x, z));
+            assert_ne!((3201, 3200), (x, z));
+            assert!(!pf
+                .find_path(&collision, 0, 3202, 3202, x, z, 1, 1, 1, 0, -1, false, 0, 25, &CollisionStrategies::Normal(Normal)).unwrap()
+                .is_empty());
+        }
+    }
+}
+
+#[test]
+fn test_pf_wander_deterministic() {
+    let mut pf = PathFinder::new();
+    unsafe {
+        let collision = build_collision_map(3180, 3180, 3220, 3220);
+        let mut seen = std::collections::HashSet::new();
+        for seed in 0..64 {
+            let first = pf.find_wander_destination(
+                &collision, 0, 3200, 3200, 3200, 3200, 2, 5, 10, &CollisionStrategies::Normal(Normal), &mut SeededRandom::new(seed),
+            ).unwrap();
+            let second = pf.find_wander_destination(
+                &collision, 0, 3200, 3200, 3200, 3200, 2, 5, 10, &CollisionStrategies::Normal(Normal), &mut SeededRandom::new(seed),
+            ).unwrap();
+            assert_eq!(first, second);
+            seen.insert(first.unwrap());
+        }
+        // different seeds spread over the area rather than all landing on one tile.
+        assert!(seen.len() > 16);
+    }
+}
+
+#[test]
+fn test_pf_wander_boxed_in() {
+    let mut pf = PathFinder::new();
+    unsafe {
+        let mut collision = build_collision_map(3180, 3180, 3220, 3220);
+        flag(&mut collision, 3199, 3199, 3, 3, CollisionFlag::LOC);
+        flag(&mut collision, 3200, 3200, 1, 1, CollisionFlag::OPEN);
+        assert_eq!(
+            None,
+            pf.find_wander_destination(
+                &collision, 0, 3200, 3200, 3200, 3200, 1, 5, 10, &CollisionStrategies::Normal(Normal), &mut SeededRandom::new(0),
+            ).unwrap()
+        );
+    }
+}
+
+#[test]
+fn test_pf_waypoints_past_coord_grid_range() {
+    let mut pf = PathFinder::new();
+    unsafe {
+        let mut collision = CollisionFlagMap::sparse();
+        for x in 100000..100008 {
+            collision.allocate_if_absent(x, 100001, 0);
+        }
+        // the sparse map holds these tiles, but packing them into a CoordGrid would wrap them into its 14 bits.
+        assert_eq!(
+            Err(CoordOutOfRange { x: 100005, z: 100001, y: 0 }),
+            pf.find_path(&collision, 0, 100001, 100001, 100005, 100001, 1, 1, 1, 0, -1, true, 0, 25, &CollisionStrategies::Normal(Normal))
+        );
+    }
+}
+
+#[test]
+fn test_pf_wide_waypoints_past_coord_grid_range() {
+    let mut pf = PathFinder::new();
+    unsafe {
+        let mut collision = CollisionFlagMap::sparse();
+        for x in 100000..100008 {
+            collision.allocate_if_absent(x, 100001, 0);
+            collision.allocate_if_absent(-x, -100001, 0);
+        }
+        assert_eq!(
+            Ok(vec![WideCoordGrid::try_new(0, 100005, 100001).unwrap().packed]),
+            pf.find_path_wide(&collision, 0, 100001, 100001, 100005, 100001, 1, 1, 1, 0, -1, true, 0, 25, &CollisionStrategies::Normal(Normal))
+        );
+        assert_eq!(
+            Ok(vec![WideCoordGrid::try_new(0, -100005, -100001).unwrap().packed]),
+            pf.find_path_wide(&collision, 0, -100001, -100001, -100005, -100001, 1, 1, 1, 0, -1, true, 0, 25, &CollisionStrategies::Normal(Normal))
+        );
+    }
+}
@@ -0,0 +1,126 @@
+import ctypes
+import unittest
+
+import rsmod
+
+PYBUF_WRITABLE = 0x0001
+
+
+class U32BufferTest(unittest.TestCase):
+    def path(self):
+        flags = rsmod.CollisionFlagMap()
+        for x in range(3200, 3216, 8):
+            flags.allocate_if_absent(x, 3200, 0)
+        return rsmod.PathFinder().find_path(flags, 0, 3200, 3200, 3205, 3200)
+
+    def test_memoryview(self):
+        path = self.path()
+        view = memoryview(path)
+        self.assertTrue(view.readonly)
+        self.assertEqual("I", view.format)
+        self.assertEqual(4, view.itemsize)
+        self.assertEqual(1, view.ndim)
+        self.assertEqual((len(path),), view.shape)
+        self.assertEqual((4,), view.strides)
+        self.assertEqual(path.tolist(), view.tolist())
+        self.assertEqual([rsmod.unpack_coord(packed) for packed in view], [(0, 3205, 3200)])
+
+    def test_refuses_writable_views(self):
+        get_buffer = ctypes.pythonapi.PyObject_GetBuffer
+        get_buffer.argtypes = [ctypes.py_object, ctypes.c_void_p, ctypes.c_int]
+        # room for any Py_buffer, which is never filled in.
+        view = ctypes.create_string_buffer(256)
+        with self.assertRaisesRegex(BufferError, "read-only"):
+            get_buffer(self.path(), ctypes.addressof(view), PYBUF_WRITABLE)
+
+    def test_index(self):
+        path = self.path()
+        self.assertEqual(path[0], path[-1])
+        with self.assertRaises(IndexError):
+            path[1]
+        with self.assertRaises(IndexError):
+            path[-2]
+
+
+class ValueErrorTest(unittest.TestCase):
+    def test_change_out_of_range(self):
+        flags = rsmod.CollisionFlagMap()
+        with self.assertRaises(ValueError):
+            flags.change_floor(-1, 3200, 0, True)
+        with self.assertRaises(ValueError):
+            flags.change_roof(16384, 3200, 0, True)
+        with self.assertRaises(ValueError):
+            flags.change_npc(3200, 3200, 4, 1, True)
+        with self.assertRaises(ValueError):
+            flags.change_player(3200, -1, 0, 1, True)
+        with self.assertRaises(ValueError):
+            flags.change_loc(16383, 3200, 0, 2, 1, True, False, True)
+        with self.assertRaises(ValueError):
+            flags.change_loc_shape(-1, 3200, 0, 1, 1, 0, 10, True, False, True)
+        with self.assertRaises(ValueError):
+            flags.change_wall_straight(-1, 3200, 0, 0, True, False, True)
+        with self.assertRaises(ValueError):
+            flags.change_wall_corner(-1, 3200, 0, 0, True, False, True)
+        with self.assertRaises(ValueError):
+            flags.change_wall_l(-1, 3200, 0, 0, True, False, True)
+        self.assertEqual(0, flags.zone_count())
+
+    def test_change_in_range(self):
+        flags = rsmod.CollisionFlagMap()
+        flags.change_floor(3200, 3200, 0, True)
+        self.assertTrue(flags.is_flagged(3200, 3200, 0, 0x200000))
+
+    def test_invalid_arguments(self):
+        flags = rsmod.CollisionFlagMap()
+        with self.assertRaises(ValueError):
+            flags.change_loc_shape(3200, 3200, 0, 1, 1, 0, 23, True, False, True)
+        with self.assertRaises(ValueError):
+            rsmod.PathFinder().find_path(flags, 0, 3200, 3200, 3205, 3200, collision=5)
+        with self.assertRaises(ValueError):
+            flags.change_wall_straight(3200, 3200, 0, 4, True, False, True)
+        with self.assertRaises(ValueError):
+            flags.change_wall_corner(3200, 3200, 0, 4, True, False, True)
+        with self.assertRaises(ValueError):
+            flags.change_wall_l(3200, 3200, 0, 255, True, False, True)
+        with self.assertRaises(ValueError):
+            rsmod.PathFinder().find_path(flags, 0, 3200, 3200, 3205, 3200, angle=4, shape=0)
+        with self.assertRaises(ValueError):
+            rsmod.PathFinder().find_path_wide(flags, 0, 3200, 3200, 3205, 3200, shape=23)
+        with self.assertRaises(ValueError):
+            rsmod.ReachStrategy.reached(flags, 0, 3200, 3201, 3200, 3200, angle=4, shape=0)
+        flags.change_wall_l(3200, 3200, 0, 3, True, False, True)
+
+    def test_change_wall_refuses_other_layers(self):
+        flags = rsmod.CollisionFlagMap()
+        for shape in range(0, 4):
+            flags.change_wall(3200, 3200, 0, 1, shape, True, False, True)
+        # diagonal walls are ground layer locs, refused like in the core and the other bindings.
+        for shape in (9, 10, 22, 23):
+            with self.assertRaises(ValueError):
+                flags.change_wall(3200, 3200, 0, 1, shape, True, False, True)
+        with self.assertRaises(ValueError):
+            flags.change_wall(16384, 3200, 0, 1, 0, True, False, True)
+
+    def test_lines_past_coord_grid_range(self):
+        flags = rsmod.CollisionFlagMap(sparse=True)
+        for x in range(20000, 20016, 8):
+            flags.allocate_if_absent(x, 20001, 0)
+        with self.assertRaises(ValueError):
+            rsmod.line_of_sight(flags, 0, 20000, 20001, 20008, 20001)
+        with self.assertRaises(ValueError):
+            rsmod.line_of_walk(flags, 0, 20000, 20001, 20008, 20001)
+
+    def test_wide_results_past_coord_grid_range(self):
+        flags = rsmod.CollisionFlagMap(sparse=True)
+        for x in range(100000, 100016, 8):
+            flags.allocate_if_absent(x, 100001, 0)
+        path = rsmod.PathFinder().find_path_wide(flags, 0, 100001, 100001, 100005, 100001)
+        self.assertEqual([(0, 100005, 100001)], [rsmod.unpack_wide_coord(packed) for packed in path])
+        line = rsmod.line_of_sight_wide(flags, 0, 100000, 100001, 100003, 100001)
+        self.assertEqual([(0, x, 100001) for x in range(100001, 100004)], [rsmod.unpack_wide_coord(packed) for packed in line])
+        line = rsmod.line_of_walk_wide(flags, 0, 100000, 100001, 100003, 100001)
+        self.assertEqual(3, len(line))
+
+
+if __name__ == "__main__":
+    unittest.main()
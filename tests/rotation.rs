@@ -1,5 +1,6 @@
 use rsmod::rsmod::block_flag::BlockAccessFlag;
-use rsmod::rsmod::rotation::{rotate, rotate_flags};
+use rsmod::rsmod::collision_flag::CollisionFlag;
+use rsmod::rsmod::rotation::{rotate, rotate_collision_flags, rotate_flags};
 
 #[test]
 fn test_rotate_width() {
@@ -56,3 +57,53 @@ fn test_rotate_block_access_flag_west() {
     assert_eq!(BlockAccessFlag::BLOCK_EAST as u8, rotate_flags(2, west));
     assert_eq!(BlockAccessFlag::BLOCK_SOUTH as u8, rotate_flags(3, west));
 }
+
+#[test]
+fn test_rotate_collision_flag_walls() {
+    let north = CollisionFlag::WALL_NORTH as u32;
+    assert_eq!(CollisionFlag::WALL_NORTH as u32, rotate_collision_flags(0, north));
+    assert_eq!(CollisionFlag::WALL_EAST as u32, rotate_collision_flags(1, north));
+    assert_eq!(CollisionFlag::WALL_SOUTH as u32, rotate_collision_flags(2, north));
+    assert_eq!(CollisionFlag::WALL_WEST as u32, rotate_collision_flags(3, north));
+
+    let north_west = CollisionFlag::WALL_NORTH_WEST as u32;
+    assert_eq!(CollisionFlag::WALL_NORTH_EAST as u32, rotate_collision_flags(1, north_west));
+    assert_eq!(CollisionFlag::WALL_SOUTH_EAST as u32, rotate_collision_flags(2, north_west));
+    assert_eq!(CollisionFlag::WALL_SOUTH_WEST as u32, rotate_collision_flags(3, north_west));
+}
+
+#[test]
+fn test_rotate_collision_flag_blockers() {
+    assert_eq!(
+        CollisionFlag::WALL_SOUTH_PROJ_BLOCKER as u32,
+        rotate_collision_flags(1, CollisionFlag::WALL_EAST_PROJ_BLOCKER as u32)
+    );
+    assert_eq!(
+        CollisionFlag::WALL_NORTH_WEST_ROUTE_BLOCKER as u32,
+        rotate_collision_flags(1, CollisionFlag::WALL_SOUTH_WEST_ROUTE_BLOCKER as u32)
+    );
+    assert_eq!(
+        CollisionFlag::WALL_WEST_ROUTE_BLOCKER as u32,
+        rotate_collision_flags(2, CollisionFlag::WALL_EAST_ROUTE_BLOCKER as u32)
+    );
+}
+
+#[test]
+fn test_rotate_collision_flag_keeps_other_flags() {
+    let flags = CollisionFlag::LOC as u32
+        | CollisionFlag::LOC_PROJ_BLOCKER as u32
+        | CollisionFlag::LOC_ROUTE_BLOCKER as u32
+        | CollisionFlag::FLOOR as u32
+        | CollisionFlag::FLOOR_DECORATION as u32
+        | CollisionFlag::NPC as u32
+        | CollisionFlag::PLAYER as u32
+        | CollisionFlag::ROOF as u32;
+    for rotation in 0..4 {
+        assert_eq!(flags, rotate_collision_flags(rotation, flags));
+        assert_eq!(
+            flags | CollisionFlag::WALL_NORTH as u32,
+            rotate_collision_flags(4 - rotation, rotate_collision_flags(rotation, flags | CollisionFlag::WALL_NORTH as u32))
+        );
+    }
+}
+
@@ -0,0 +1,53 @@
+use rsmod::rsmod::{find_safe_spots, AttackType, CollisionStrategies, Normal, SafeSpot};
+use rsmod::rsmod::collision::collision::CollisionFlagMap;
+use rsmod::rsmod::collision_flag::CollisionFlag;
+
+unsafe fn build_collision_map(fence: bool) -> CollisionFlagMap {
+    let mut collision = CollisionFlagMap::new();
+    for z in 3185..3215 {
+        for x in 3185..3215 {
+            collision.allocate_if_absent(x, z, 0);
+        }
+    }
+    if fence {
+        // a fence along the north of z 3203 that can be seen and shot over but not walked through.
+        for x in 3185..3215 {
+            collision.add(x, 3203, 0, CollisionFlag::WALL_NORTH as u32);
+            collision.add(x, 3204, 0, CollisionFlag::WALL_SOUTH as u32);
+        }
+    }
+    return collision;
+}
+
+#[test]
+fn test_no_safe_spots_in_the_open() {
+    let strategy = CollisionStrategies::Normal(Normal);
+    unsafe {
+        let collision = build_collision_map(false);
+        for size in 1..3 {
+            assert!(find_safe_spots(&collision, 0, 3200, 3200, size, 6, 7, AttackType::RANGED, 0, &strategy).unwrap().is_empty());
+            assert!(find_safe_spots(&collision, 0, 3200, 3200, size, 6, 2, AttackType::MELEE, 0, &strategy).unwrap().is_empty());
+        }
+    }
+}
+
+#[test]
+fn test_safe_spots_over_fence() {
+    let strategy = CollisionStrategies::Normal(Normal);
+    unsafe {
+        let collision = build_collision_map(true);
+        let spots: Vec<SafeSpot> = find_safe_spots(&collision, 0, 3200, 3200, 1, 6, 7, AttackType::RANGED, 0, &strategy).unwrap();
+        assert!(!spots.is_empty());
+        // every tile over the fence in range is safe, the npc walking up against the fence.
+        for spot in &spots {
+            assert!(spot.z >= 3204);
+            assert_eq!(3203, spot.npc_z);
+        }
+        assert!(spots.contains(&SafeSpot { x: 3200, z: 3206, npc_x: 3200, npc_z: 3203 }));
+        // right next to the fence the npc gets next to the player, but can't hit across it.
+        assert!(spots.contains(&SafeSpot { x: 3200, z: 3204, npc_x: 3200, npc_z: 3203 }));
+
+        // a halberd can't be swung over the fence either.
+        assert!(find_safe_spots(&collision, 0, 3200, 3200, 1, 6, 2, AttackType::MELEE, 0, &strategy).unwrap().is_empty());
+    }
+}
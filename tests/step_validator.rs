@@ -1,5 +1,5 @@
 use rsmod::rsmod::{
-    Blocked, can_travel, CollisionStrategies, Indoors, LineOfSight, Normal, Outdoors,
+    Blocked, can_travel, can_travel_rect, CollisionStrategies, Indoors, LineOfSight, Normal, Outdoors,
 };
 use rsmod::rsmod::collision::collision::CollisionFlagMap;
 use rsmod::rsmod::collision_flag::CollisionFlag;
@@ -428,3 +428,51 @@ fn test_step_line_of_sight_strategy_player() {
         }
     }
 }
+
+#[test]
+fn test_step_rect_loc_blocking() {
+    let strategy = CollisionStrategies::Normal(Normal);
+    let directions: [[i8; 2]; 8] = [[0, -1], [0, 1], [-1, 0], [1, 0], [-1, -1], [-1, 1], [1, -1], [1, 1]];
+    unsafe {
+        let mut collision = build_collision_map(3198, 3198, 3204, 3204);
+        for width in 1..4 {
+            for height in 1..4 {
+                for [dir_x, dir_z] in directions {
+                    // the mover is at 3200, 3200 and every tile within reach of a step is blocked in turn,
+                    // only the tiles it steps onto count.
+                    for blocked_z in 3199..3200 + height + 1 {
+                        for blocked_x in 3199..3200 + width + 1 {
+                            collision.add(blocked_x, blocked_z, 0, CollisionFlag::LOC as u32);
+                            let in_footprint = |x: i32, z: i32| -> bool {
+                                return blocked_x >= x && blocked_x < x + width && blocked_z >= z && blocked_z < z + height;
+                            };
+                            let column_x = if dir_x > 0 { 3200 + width } else { 3199 };
+                            let row_z = if dir_z > 0 { 3200 + height } else { 3199 };
+                            let mut expected = in_footprint(3200, 3200) || !in_footprint(3200 + dir_x as i32, 3200 + dir_z as i32);
+                            if dir_x != 0 && dir_z != 0 {
+                                if width == 1 && blocked_x == 3200 && blocked_z == row_z {
+                                    expected = false;
+                                }
+                                if height == 1 && blocked_x == column_x && blocked_z == 3200 {
+                                    expected = false;
+                                }
+                            }
+                            assert_eq!(
+                                expected,
+                                can_travel_rect(&collision, 0, 3200, 3200, dir_x, dir_z, width as u8, height as u8, 0, &strategy),
+                                "{width}x{height} stepping {dir_x}, {dir_z} with {blocked_x}, {blocked_z} blocked",
+                            );
+                            if width == height {
+                                assert_eq!(
+                                    can_travel(&collision, 0, 3200, 3200, dir_x, dir_z, width as u8, 0, &strategy),
+                                    can_travel_rect(&collision, 0, 3200, 3200, dir_x, dir_z, width as u8, height as u8, 0, &strategy),
+                                );
+                            }
+                            collision.remove(blocked_x, blocked_z, 0, CollisionFlag::LOC as u32);
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
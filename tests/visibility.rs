@@ -0,0 +1,75 @@
+use rsmod::rsmod::{has_line_of_sight, visibility_map};
+use rsmod::rsmod::collision::collision::CollisionFlagMap;
+use rsmod::rsmod::collision_flag::CollisionFlag;
+
+const BLOCKERS: [CollisionFlag; 7] = [
+    CollisionFlag::LOC_PROJ_BLOCKER,
+    CollisionFlag::WALL_NORTH_PROJ_BLOCKER,
+    CollisionFlag::WALL_EAST_PROJ_BLOCKER,
+    CollisionFlag::WALL_SOUTH_PROJ_BLOCKER,
+    CollisionFlag::WALL_WEST_PROJ_BLOCKER,
+    CollisionFlag::LOC,
+    CollisionFlag::PLAYER,
+];
+
+unsafe fn build_collision_map(seed: u32) -> CollisionFlagMap {
+    let mut collision = CollisionFlagMap::new();
+    let mut state: u32 = seed;
+    for z in 3180..3230 {
+        for x in 3180..3230 {
+            collision.allocate_if_absent(x, z, 0);
+            state = state.wrapping_mul(1103515245).wrapping_add(12345);
+            if (state >> 16) % 9 == 4 {
+                collision.add(x, z, 0, BLOCKERS[(state >> 8) as usize % BLOCKERS.len()] as u32);
+            }
+        }
+    }
+    return collision;
+}
+
+#[test]
+fn test_visibility_matches_line_of_sight() {
+    for seed in 0..4 {
+        for src_size in 1..=3 {
+            for extra_flag in [0, CollisionFlag::PLAYER as u32] {
+                unsafe {
+                    let collision = build_collision_map(seed);
+                    let map = visibility_map(&collision, 0, 3204, 3204, src_size, 10, extra_flag);
+                    assert_eq!(src_size as i32 + 20, map.length);
+                    for z in map.origin_z..map.origin_z + map.length {
+                        for x in map.origin_x..map.origin_x + map.length {
+                            let expected = has_line_of_sight(&collision, 0, 3204, 3204, x, z, src_size, src_size, 1, 1, extra_flag);
+                            assert_eq!(expected, map.is_visible(x, z), "seed {} size {} tile {}, {}", seed, src_size, x, z);
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn test_visibility_open_area() {
+    let mut collision = CollisionFlagMap::new();
+
+    unsafe {
+        collision.allocate_if_absent(3200, 3200, 0);
+
+        let map = visibility_map(&collision, 0, 3200, 3200, 1, 2, 0);
+        assert_eq!(25, map.visible_count());
+        assert_eq!(25, map.visible_tiles().count());
+        assert_eq!(false, map.is_visible(3203, 3200));
+    }
+}
+
+#[test]
+fn test_visibility_on_top_of_loc() {
+    let mut collision = CollisionFlagMap::new();
+
+    unsafe {
+        collision.add(3200, 3200, 0, CollisionFlag::LOC as u32);
+
+        let map = visibility_map(&collision, 0, 3200, 3200, 1, 3, 0);
+        assert_eq!(vec![(3200, 3200)], map.visible_tiles().collect::<Vec<(i32, i32)>>());
+    }
+}